@@ -0,0 +1,37 @@
+// Compares the batch checksum verification with verifying VINs one by one, run with
+// `cargo bench --bench checksum` (no benchmarking framework is needed)
+#![allow(clippy::incompatible_msrv)] // benchmarks run on the current stable toolchain only
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use vin::parse::verify_checksum_batch;
+use vin::registry::VinSequence;
+use vin::verify_checksum;
+
+const COUNT: usize = 10_000;
+const ROUNDS: u32 = 100;
+
+fn measure<F: FnMut() -> usize>(name: &str, mut verify: F) -> Duration {
+    let start = Instant::now();
+    let mut valid = 0;
+    for _ in 0..ROUNDS {
+        valid += verify();
+    }
+    let elapsed = start.elapsed();
+
+    let checks = u128::from(ROUNDS) * COUNT as u128;
+    println!("{}: {} checks ({} valid) in {:?}, {} ns/check", name, checks, valid, elapsed, elapsed.as_nanos() / checks);
+    elapsed
+}
+
+fn main() {
+    let mut sequence = VinSequence::new("1M8", "GDM9A", 'K', 'P').unwrap();
+    let vins: Vec<String> = (0..COUNT).map(|_| sequence.next().unwrap().as_str().to_owned()).collect();
+    let vins: Vec<&str> = vins.iter().map(String::as_str).collect();
+
+    let scalar = measure("verify_checksum", || vins.iter().filter(|x| black_box(verify_checksum(x)).is_ok()).count());
+    let batch = measure("verify_checksum_batch", || {
+        black_box(verify_checksum_batch(black_box(&vins))).iter().filter(|x| x.is_ok()).count()
+    });
+    println!("speedup: {:.2}x", scalar.as_secs_f64() / batch.as_secs_f64());
+}
@@ -0,0 +1,61 @@
+// Compares decoding of the model year symbol by the compile-time table with walking the
+// year symbols in a `cycle()` loop, run with `cargo bench --bench years` (no benchmarking
+// framework is needed)
+#![allow(clippy::incompatible_msrv)] // benchmarks run on the current stable toolchain only
+use std::hint::black_box;
+use std::ops::RangeInclusive;
+use std::time::{Duration, Instant};
+
+use vin::{year_codes, YearWindow};
+
+const ROUNDS: u32 = 1_000_000;
+
+/// Symbols encoding model year, starting from 1980
+const YEAR_LETTERS: &str = "ABCDEFGHJKLMNPRSTVWXY123456789";
+
+/// Returns model years within the window encoded by the given symbol the way it was done
+/// before the table lookup: walking the repeating symbols year by year from 1980
+fn cycle_years(window: &RangeInclusive<u32>, letter: char) -> Vec<u32> {
+    let mut year: u32 = 1979;
+    let mut result = vec![];
+    for x in YEAR_LETTERS.chars().cycle() {
+        year += 1;
+
+        if x == letter && window.contains(&year) {
+            result.push(year);
+        }
+
+        if year >= *window.end() { break; }
+    }
+
+    result
+}
+
+fn measure<F: FnMut(char) -> usize>(name: &str, letters: &[char], mut years: F) -> Duration {
+    let start = Instant::now();
+    let mut count = 0;
+    for _ in 0..ROUNDS {
+        for letter in letters {
+            count += years(black_box(*letter));
+        }
+    }
+    let elapsed = start.elapsed();
+
+    let lookups = u128::from(ROUNDS) * letters.len() as u128;
+    println!("{}: {} lookups ({} years) in {:?}, {} ns/lookup", name, lookups, count, elapsed, elapsed.as_nanos() / lookups);
+    elapsed
+}
+
+fn main() {
+    let range = 1980..=2039;
+    let window = YearWindow::new(range.clone());
+    let letters: Vec<char> = year_codes().iter().map(|(x, _)| *x).chain("IOQUZ".chars()).collect();
+
+    for letter in &letters {
+        assert_eq!(window.years(*letter), cycle_years(&range, *letter));
+    }
+
+    let cycle = measure("cycle loop", &letters, |letter| black_box(cycle_years(&range, letter)).len());
+    let table = measure("table lookup", &letters, |letter| black_box(window.years(letter)).len());
+    println!("speedup: {:.2}x", cycle.as_secs_f64() / table.as_secs_f64());
+}
@@ -0,0 +1,183 @@
+//! Compiles CSV sources from the `data` folder into Rust tables included by `src/dicts.rs`.
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// Source file, name of the table, count of fields, count of leading fields forming the unique
+/// key and the feature required for the table
+const TABLES: [(&str, &str, usize, usize, Option<&str>); 14] = [
+    ("regions.csv", "REGION_TABLE", 3, 1, None),
+    ("countries.csv", "COUNTRY_TABLE", 4, 1, None),
+    ("manufacturers.csv", "MANUFACTURER_TABLE", 2, 1, None),
+    ("manufacturers_full.csv", "MANUFACTURER_FULL_TABLE", 2, 1, Some("FULL_DATA")),
+    ("groups.csv", "GROUP_TABLE", 2, 1, None),
+    ("brands.csv", "BRAND_TABLE", 2, 1, None),
+    ("kba.csv", "KBA_TABLE", 3, 1, Some("KBA")),
+    ("india.csv", "INDIA_TABLE", 4, 1, None),
+    ("korea.csv", "KOREA_TABLE", 5, 4, None),
+    ("ev.csv", "EV_TABLE", 2, 1, None),
+    ("engines.csv", "ENGINE_TABLE", 5, 3, None),
+    ("body_styles.csv", "BODY_STYLE_TABLE", 2, 1, None),
+    ("restraints.csv", "RESTRAINT_TABLE", 3, 1, None),
+    ("pickups.csv", "PICKUP_TABLE", 4, 1, None),
+];
+
+/// Tables sharing the key space, so keys must be unique across them
+const SHARED_KEYS: [&str; 2] = ["MANUFACTURER_TABLE", "MANUFACTURER_FULL_TABLE"];
+
+/// Order of the characters in the ranges of country codes
+const CODE_SEQ: &str = "ABCDEFGHJKLMNPRSTUVWXYZ1234567890";
+
+fn main() {
+    println!("cargo:rerun-if-changed=data");
+
+    // std features newer than the minimum supported Rust version (see `src/sync.rs`)
+    println!("cargo:rustc-check-cfg=cfg(has_once_lock)");
+    if rustc_minor_version().map_or(false, |x| x >= 70) {
+        println!("cargo:rustc-cfg=has_once_lock");
+    }
+
+    let mut code = String::new();
+
+    let version = fs::read_to_string("data/VERSION").expect("data/VERSION can not be read");
+    let mut version = version.split_whitespace();
+    let (number, date) = match (version.next(), version.next()) {
+        (Some(number), Some(date)) => (number, date),
+        _ => panic!("data/VERSION must contain version and date separated by whitespace"),
+    };
+    writeln!(code, "pub(in crate) const DATA_VERSION: &str = {:?};", number).unwrap();
+    writeln!(code, "pub(in crate) const DATA_DATE: &str = {:?};", date).unwrap();
+
+    // key of the row, mapped to the file and the line defining it
+    let mut shared: HashMap<String, String> = HashMap::new();
+
+    // all tables are validated, even the ones not compiled
+    for (file, table, field_count, key_fields, feature) in TABLES.iter() {
+        let path = Path::new("data").join(file);
+        let content = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("{} can not be read: {}", path.display(), e));
+
+        let mut keys: HashMap<String, String> = HashMap::new();
+        let mut rows = vec![];
+        for (i, line) in content.lines().enumerate().skip(1) {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let location = format!("{}:{}", path.display(), i + 1);
+            let fields = parse_line(line);
+            if fields.len() != *field_count {
+                panic!("{}: {} fields expected", location, field_count);
+            }
+
+            let key = fields[..*key_fields].join(",").to_lowercase();
+            let keys = if SHARED_KEYS.contains(table) { &mut shared } else { &mut keys };
+            if let Some(first) = keys.insert(key, location.clone()) {
+                panic!("{}: duplicate of the entry at {}", location, first);
+            }
+            rows.push((location, fields));
+        }
+
+        match *table {
+            "REGION_TABLE" => check_regions(&rows),
+            "COUNTRY_TABLE" => check_countries(&rows),
+            _ => {}
+        }
+
+        if let Some(feature) = feature {
+            if env::var_os(format!("CARGO_FEATURE_{}", feature)).is_none() {
+                continue;
+            }
+        }
+
+        let row_type = vec!["&str"; *field_count].join(", ");
+        writeln!(code, "pub(in crate) static {}: &[({})] = &[", table, row_type).unwrap();
+        for (_, fields) in rows {
+            let fields: Vec<String> = fields.iter().map(|x| format!("{:?}", x)).collect();
+            writeln!(code, "    ({}),", fields.join(", ")).unwrap();
+        }
+        writeln!(code, "];").unwrap();
+    }
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("tables.rs");
+    fs::write(out, code).unwrap();
+}
+
+/// Returns minor version of the compiler, `None` if it can not be determined
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC")?;
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    // e.g. "rustc 1.65.0 (897e37553 2022-11-02)"
+    let version = String::from_utf8(output.stdout).ok()?;
+    version.split_whitespace().nth(1)?.split('.').nth(1)?.parse().ok()
+}
+
+/// Panics if the range of region codes is malformed or the region code is assigned to several regions
+fn check_regions(rows: &[(String, Vec<String>)]) {
+    let mut assigned: HashMap<char, &str> = HashMap::new();
+    for (location, fields) in rows {
+        let chars: Vec<char> = fields[0].chars().collect();
+        let range = match chars.as_slice() {
+            [from, '-', to] => CODE_SEQ.find(*from).zip(CODE_SEQ.find(*to)),
+            [code] => CODE_SEQ.find(*code).map(|x| (x, x)),
+            _ => None,
+        };
+        let (from, to) = match range {
+            Some((from, to)) if from <= to => (from, to),
+            _ => panic!("{}: malformed range of region codes {:?}", location, fields[0]),
+        };
+
+        for ch in CODE_SEQ[from..=to].chars() {
+            if let Some(first) = assigned.insert(ch, location) {
+                panic!("{}: region code {} is already assigned at {}", location, ch, first);
+            }
+        }
+    }
+}
+
+/// Panics if the range of country codes is malformed or overlaps other range
+fn check_countries(rows: &[(String, Vec<String>)]) {
+    let mut assigned: HashMap<String, &str> = HashMap::new();
+    for (location, fields) in rows {
+        let chars: Vec<char> = fields[0].chars().collect();
+        let range = match chars.as_slice() {
+            [first, '-', from, to] => CODE_SEQ.find(*from).zip(CODE_SEQ.find(*to)).map(|x| (*first, x)),
+            _ => None,
+        };
+        let (first, (from, to)) = match range {
+            Some((first, (from, to))) if from <= to => (first, (from, to)),
+            _ => panic!("{}: malformed range of country codes {:?}", location, fields[0]),
+        };
+
+        for ch in CODE_SEQ[from..=to].chars() {
+            let code = format!("{}{}", first, ch);
+            if let Some(first) = assigned.insert(code.clone(), location) {
+                panic!("{}: country code {} is already assigned at {}", location, code, first);
+            }
+        }
+    }
+}
+
+/// Splits CSV line into fields, supporting double-quoted fields with `""` escapes
+fn parse_line(line: &str) -> Vec<String> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            ch => field.push(ch),
+        }
+    }
+    fields.push(field);
+    fields
+}
@@ -0,0 +1,26 @@
+#![no_main]
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Arbitrary, Debug)]
+struct Input<'a> {
+    vin: &'a str,
+    reference_year: u32,
+    window: (u32, u32),
+}
+
+fuzz_target!(|input: Input| {
+    let result = match vin::get_info(input.vin) {
+        Ok(result) => result,
+        Err(_) => return,
+    };
+
+    assert_eq!(result.as_bytes().len(), 17);
+    assert!(result.as_ascii_str().is_ascii());
+    let _ = (result.wmi(), result.vds(), result.vis());
+    let _ = (result.region_code(), result.country_code(), result.small_manufacturer());
+    let _ = result.years_as_of(input.reference_year % 10_000);
+    let _ = result.years_in(input.window.0..=input.window.1.min(input.window.0.saturating_add(1000)));
+    let _ = result.squish();
+    let _ = result.masked();
+});
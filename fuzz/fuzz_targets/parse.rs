@@ -0,0 +1,18 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &str| {
+    let _ = vin::check_validity(data);
+    let _ = vin::verify_checksum(data);
+    let _ = vin::compute_check_digit(data);
+    let _ = vin::mask(data, vin::MaskPolicy::default());
+    let _ = vin::partial::PartialVin::parse(data);
+    let _ = vin::SquishVin::parse(data);
+
+    if let Ok(result) = vin::get_info(data) {
+        let _ = (result.wmi(), result.vds(), result.vis());
+        let _ = result.years_as_of(2020);
+        let _ = result.squish();
+        let _ = result.masked();
+    }
+});
@@ -0,0 +1,15 @@
+#![no_main]
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Arbitrary, Debug)]
+struct Input<'a> {
+    rules: &'a str,
+    vin: &'a str,
+}
+
+fuzz_target!(|input: Input| {
+    if let (Ok(set), Ok(result)) = (vin::patterns::PatternSet::load(input.rules.as_bytes()), vin::get_info(input.vin)) {
+        let _ = set.find(&result);
+    }
+});
@@ -0,0 +1,126 @@
+//! Columnar decoding of Apache Arrow arrays.
+//!
+//! Decodes the whole column of VINs at once into Arrow arrays, which can be written to Parquet
+//! or joined back to the source batch without building per-row structures.
+//!
+//! # Examples
+//! ```
+//! use arrow_array::{Array, StringArray};
+//!
+//! let column = StringArray::from(vec![Some("1M8GDM9AXKP042788"), None, Some("invalid")]);
+//! let decoded = vin::arrow::decode_column(&column);
+//!
+//! assert_eq!(decoded.manufacturer.value(0), "Mercury");
+//! assert!(decoded.checksum_ok.value(0));
+//! assert!(decoded.manufacturer.is_null(1));
+//! assert!(!decoded.valid.value(2));
+//! ```
+use std::sync::Arc;
+
+use arrow_array::builder::{BooleanBuilder, StringBuilder, UInt32Builder};
+use arrow_array::{Array, ArrayRef, BooleanArray, RecordBatch, StringArray, UInt32Array};
+use arrow_schema::{ArrowError, DataType, Field, Schema};
+
+use crate::{get_info, ChecksumStatus};
+
+/// Holds decoded columns, every row corresponds to the row of the source column.
+///
+/// Rows with null or invalid VINs are null in all columns but `valid`.
+#[derive(Debug, Clone)]
+pub struct DecodedBatch {
+    /// Whether VIN is structurally valid (nulls are not valid)
+    pub valid: BooleanArray,
+
+    /// Manufacturer name, if known
+    pub manufacturer: StringArray,
+
+    /// Country of the manufacturer, if known
+    pub country: StringArray,
+
+    /// Region of the manufacturer, if known
+    pub region: StringArray,
+
+    /// The most likely model year, null if it's ambiguous (see
+    /// [`VIN::years`](../struct.VIN.html#method.years))
+    pub model_year: UInt32Array,
+
+    /// Whether checksum of the VIN is valid
+    pub checksum_ok: BooleanArray,
+}
+
+impl DecodedBatch {
+    /// Returns the schema of the record batch built by [`into_record_batch`](#method.into_record_batch)
+    pub fn schema() -> Schema {
+        Schema::new(vec![
+            Field::new("valid", DataType::Boolean, false),
+            Field::new("manufacturer", DataType::Utf8, true),
+            Field::new("country", DataType::Utf8, true),
+            Field::new("region", DataType::Utf8, true),
+            Field::new("model_year", DataType::UInt32, true),
+            Field::new("checksum_ok", DataType::Boolean, true),
+        ])
+    }
+
+    /// Returns count of rows
+    pub fn len(&self) -> usize { self.valid.len() }
+
+    /// Returns whether there are no rows
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Converts the columns into the record batch
+    pub fn into_record_batch(self) -> Result<RecordBatch, ArrowError> {
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(self.valid),
+            Arc::new(self.manufacturer),
+            Arc::new(self.country),
+            Arc::new(self.region),
+            Arc::new(self.model_year),
+            Arc::new(self.checksum_ok),
+        ];
+        RecordBatch::try_new(Arc::new(DecodedBatch::schema()), columns)
+    }
+}
+
+/// Decodes the column of VINs
+pub fn decode_column(vins: &StringArray) -> DecodedBatch {
+    let len = vins.len();
+    let mut valid = BooleanBuilder::with_capacity(len);
+    let mut manufacturer = StringBuilder::with_capacity(len, len * 16);
+    let mut country = StringBuilder::with_capacity(len, len * 16);
+    let mut region = StringBuilder::with_capacity(len, len * 8);
+    let mut model_year = UInt32Builder::with_capacity(len);
+    let mut checksum_ok = BooleanBuilder::with_capacity(len);
+
+    for vin in vins.iter() {
+        match vin.map(get_info) {
+            Some(Ok(vin)) => {
+                valid.append_value(true);
+                manufacturer.append_option(vin.manufacturer.as_ref());
+                country.append_option(vin.country.as_ref());
+                region.append_option(vin.region.as_ref());
+                model_year.append_option(vin.years().best_guess);
+                match vin.valid_checksum {
+                    ChecksumStatus::NotApplicable => checksum_ok.append_null(),
+                    status => checksum_ok.append_value(status.is_valid()),
+                }
+            }
+            _ => {
+                valid.append_value(false);
+                manufacturer.append_null();
+                country.append_null();
+                region.append_null();
+                model_year.append_null();
+                checksum_ok.append_null();
+            }
+        }
+    }
+
+    DecodedBatch {
+        valid: valid.finish(),
+        manufacturer: manufacturer.finish(),
+        country: country.finish(),
+        region: region.finish(),
+        model_year: model_year.finish(),
+        checksum_ok: checksum_ok.finish(),
+    }
+}
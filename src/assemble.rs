@@ -0,0 +1,163 @@
+use crate::VINError::{self, IncorrectLength, InvalidCharacters};
+use crate::years::YEAR_LETTERS;
+use crate::{compute_check_digit, get_info, InvalidChar, VIN};
+
+/// Holds VIN pieces stored separately, e.g. in the columns of a legacy database, to be
+/// assembled with [`VIN::from_components`]
+///
+/// # Examples
+/// ```
+/// use vin::{VinComponents, VIN};
+///
+/// let components = VinComponents {
+///     wmi: "1M8",
+///     attributes: "GDM9A",
+///     year: 'K',
+///     plant: 'P',
+///     serial: "042788",
+///     compute_check_digit: true,
+/// };
+/// assert_eq!(VIN::from_components(&components).unwrap().as_str(), "1M8GDM9AXKP042788");
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct VinComponents<'a> {
+    /// World manufacturer identifier (1-3-th symbols)
+    pub wmi: &'a str,
+
+    /// Vehicle attributes (4-8-th symbols), followed by the check digit (9-th symbol) unless
+    /// `compute_check_digit` is set
+    pub attributes: &'a str,
+
+    /// Model year code (10-th symbol)
+    pub year: char,
+
+    /// Plant code (11-th symbol)
+    pub plant: char,
+
+    /// Serial number (12-17-th symbols)
+    pub serial: &'a str,
+
+    /// Whether the check digit is computed instead of being taken from `attributes`
+    pub compute_check_digit: bool,
+}
+
+/// Holds owned components of the VIN, e.g. to be stored in separate columns
+///
+/// # Examples
+/// ```
+/// let parts = vin::get_info("1M8GDM9AXKP042788").unwrap().into_parts();
+/// assert_eq!((parts.wmi.as_str(), parts.vds.as_str(), parts.serial.as_str()), ("1M8", "GDM9A", "042788"));
+/// assert_eq!((parts.check_digit, parts.year_code, parts.plant_code), ('X', 'K', 'P'));
+/// assert_eq!(parts.assemble().unwrap().as_str(), "1M8GDM9AXKP042788");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VinParts {
+    /// World manufacturer identifier (1-3-th symbols)
+    pub wmi: String,
+
+    /// Vehicle descriptor without the check digit (4-8-th symbols)
+    pub vds: String,
+
+    /// Check digit (9-th symbol)
+    pub check_digit: char,
+
+    /// Model year code (10-th symbol)
+    pub year_code: char,
+
+    /// Plant code (11-th symbol)
+    pub plant_code: char,
+
+    /// Serial number (12-17-th symbols)
+    pub serial: String,
+}
+
+impl VinParts {
+    /// Assembles VIN back from the parts, validating each of them
+    pub fn assemble(&self) -> Result<VIN, VINError> {
+        let attributes = format!("{}{}", self.vds, self.check_digit);
+        VIN::from_components(&VinComponents {
+            wmi: &self.wmi,
+            attributes: &attributes,
+            year: self.year_code,
+            plant: self.plant_code,
+            serial: &self.serial,
+            compute_check_digit: false,
+        })
+    }
+}
+
+impl VIN {
+    /// Splits VIN into owned parts
+    pub fn into_parts(self) -> VinParts {
+        let vin = self.as_str();
+        VinParts {
+            wmi: vin[..3].to_string(),
+            vds: vin[3..8].to_string(),
+            check_digit: self.check_digit(),
+            year_code: self.vin[9] as char,
+            plant_code: self.vin[10] as char,
+            serial: vin[11..].to_string(),
+        }
+    }
+
+    /// Assembles VIN from its WMI (3 symbols), VDS (6 symbols including the check digit)
+    /// and VIS (8 symbols), validating each part before the whole number is parsed
+    ///
+    /// # Examples
+    /// ```
+    /// let result = vin::VIN::from_parts("wp0", "zzz998", "ts392124").unwrap();
+    /// assert_eq!(result.as_str(), "WP0ZZZ998TS392124");
+    /// assert!(vin::VIN::from_parts("WP0", "ZZZ99", "8TS392124").is_err());
+    /// ```
+    pub fn from_parts(wmi: &str, vds: &str, vis: &str) -> Result<VIN, VINError> {
+        let vin = [check_part(wmi, 3, 0)?, check_part(vds, 6, 3)?, check_part(vis, 8, 9)?].concat();
+        get_info(&vin)
+    }
+
+    /// Assembles VIN from the separately stored components, validating each of them
+    /// (including the model year code) and computing the check digit if requested
+    pub fn from_components(components: &VinComponents) -> Result<VIN, VINError> {
+        let attributes_len = if components.compute_check_digit { 5 } else { 6 };
+        let mut vin = [
+            check_part(components.wmi, 3, 0)?,
+            check_part(components.attributes, attributes_len, 3)?,
+        ].concat();
+        if components.compute_check_digit {
+            vin.push('0');
+        }
+
+        let year = components.year.to_ascii_uppercase();
+        if !YEAR_LETTERS.contains(year) {
+            return Err(InvalidCharacters(vec![InvalidChar::new(9, year)]));
+        }
+        vin.push(year);
+        vin.push_str(&check_part(&components.plant.to_string(), 1, 10)?);
+        vin.push_str(&check_part(components.serial, 6, 11)?);
+
+        if components.compute_check_digit {
+            let check_digit = compute_check_digit(&vin)?;
+            vin.replace_range(8..9, &check_digit.to_string());
+        }
+        get_info(&vin)
+    }
+}
+
+/// Returns uppercased part of the VIN if it has expected length and contains only allowed symbols,
+/// invalid ones are reported at their positions in the VIN (the part starts at `offset`)
+fn check_part(part: &str, len: usize, offset: usize) -> Result<String, VINError> {
+    let part = part.to_uppercase();
+
+    // check length
+    if part.chars().count() != len {
+        return Err(IncorrectLength);
+    }
+
+    // check alphabet
+    let mut odd_chars = InvalidChar::find(&part, &[]);
+    odd_chars.iter_mut().for_each(|x| x.position += offset);
+    if !odd_chars.is_empty() {
+        return Err(InvalidCharacters(odd_chars));
+    }
+
+    Ok(part)
+}
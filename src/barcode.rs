@@ -0,0 +1,71 @@
+//! Extraction of VINs from barcode payloads.
+//!
+//! Code 39 labels (e.g. on door jambs) may contain the leading `I` import character or
+//! `*` start/stop symbols, while QR and Data Matrix payloads usually hold several fields
+//! separated by control characters, with the VIN prefixed by ANSI MH10 data identifier.
+//!
+//! # Examples
+//! ```
+//! let result = vin::barcode::parse("I1M8GDM9AXKP042788").unwrap();
+//! assert_eq!(result.as_str(), "1M8GDM9AXKP042788");
+//!
+//! let payload = "[)>\u{1e}06\u{1d}1VWP0ZZZ99ZTS392124\u{1d}1P12345\u{1e}\u{4}";
+//! assert_eq!(vin::barcode::parse(payload).unwrap().as_str(), "WP0ZZZ99ZTS392124");
+//! ```
+use crate::VINError::IncorrectLength;
+use crate::{check_validity, get_info, VINError, VIN};
+
+/// Characters separating fields of the payload
+const SEPARATORS: &[char] = &['\u{1d}', '\u{1e}', '\u{4}', '*', ';', ',', '|', '\r', '\n', '\t', ' '];
+
+/// Prefixes (import character and data identifiers) that may precede the VIN in a field
+const PREFIXES: &[&str] = &["", "I", "1V", "1I", "V"];
+
+/// Extracts the VIN from the barcode payload and returns information about it
+/// (see [`get_info`](../fn.get_info.html)).
+///
+/// If several fields of the payload look like a VIN, the one with valid checksum is preferred.
+/// Fields which can't be decoded are skipped, the error is returned only if none of them can.
+pub fn parse(payload: &str) -> Result<VIN, VINError> {
+    let mut first_error = None;
+    let mut fallback = None;
+
+    for field in payload.split(SEPARATORS).filter(|x| !x.is_empty()) {
+        for prefix in PREFIXES {
+            let candidate = match strip_prefix(field, prefix) {
+                Some(candidate) => candidate,
+                None => continue,
+            };
+
+            match check_validity(candidate) {
+                Ok(()) => match get_info(candidate) {
+                    Ok(result) if result.valid_checksum.is_valid() => return Ok(result),
+                    Ok(result) => { fallback.get_or_insert(result); }
+                    Err(err) => { first_error.get_or_insert(err); }
+                },
+                Err(err) => { first_error.get_or_insert(err); }
+            }
+        }
+    }
+
+    match fallback {
+        Some(result) => Ok(result),
+        None => Err(first_error.unwrap_or(IncorrectLength)),
+    }
+}
+
+/// Strips the prefix if the rest of the field has the length of VIN
+fn strip_prefix<'a>(field: &'a str, prefix: &str) -> Option<&'a str> {
+    let prefix_len = prefix.chars().count();
+    if field.chars().count() != prefix_len + 17 {
+        return None;
+    }
+
+    // prefixes are ASCII-only, so comparing uppercased bytes is enough
+    let head = field.get(..prefix.len())?;
+    if head.eq_ignore_ascii_case(prefix) {
+        field.get(prefix.len()..)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,101 @@
+//! Decoding of VIN batches with aggregated statistics.
+//!
+//! # Examples
+//! ```
+//! use vin::batch::decode_with_stats;
+//! use vin::FindingCode;
+//!
+//! let (outcomes, stats) = decode_with_stats(vec!["WP0ZZZ99ZTS392124", "1M8GDM9AXKP042788", "invalid"]);
+//! assert!(outcomes[2].result.is_err());
+//! assert_eq!((stats.total, stats.decoded), (3, 2));
+//! assert_eq!(stats.errors[&FindingCode::Length], 1);
+//! assert_eq!(stats.regions["Europe"], 1);
+//! assert_eq!(stats.manufacturers["Porsche car"], 1);
+//! ```
+use std::collections::HashMap;
+
+use crate::{get_info, Finding, FindingCode, VINError, VIN};
+
+/// Holds result of decoding of one VIN of the batch
+#[derive(Debug)]
+pub struct DecodeOutcome {
+    /// Position (0-based) of the VIN in the batch
+    pub index: usize,
+
+    /// VIN as received
+    pub input: String,
+
+    /// Decoded VIN or the error
+    pub result: Result<VIN, VINError>,
+}
+
+/// Aggregated statistics of the decoded batch
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchStats {
+    /// Count of all VINs
+    pub total: usize,
+
+    /// Count of successfully decoded VINs
+    pub decoded: usize,
+
+    /// Count of VINs with invalid checksum in the region mandating the check digit
+    /// (such VINs are decoded, so they are counted in `decoded` as well)
+    pub invalid_checksum: usize,
+
+    /// Count of rejected VINs per kind of the error
+    pub errors: HashMap<FindingCode, usize>,
+
+    /// Count of decoded VINs per region (VINs of unknown regions are not counted)
+    pub regions: HashMap<String, usize>,
+
+    /// Count of decoded VINs per manufacturer (VINs of unknown manufacturers are not counted)
+    pub manufacturers: HashMap<String, usize>,
+}
+
+impl BatchStats {
+    /// Creates empty statistics
+    pub fn new() -> BatchStats { BatchStats::default() }
+
+    /// Records result of the decoding, e.g. to aggregate a stream processed elsewhere
+    pub fn record(&mut self, result: &Result<VIN, VINError>) {
+        self.total += 1;
+        match result {
+            Ok(vin) => {
+                self.decoded += 1;
+                if vin.valid_checksum.is_invalid() {
+                    self.invalid_checksum += 1;
+                }
+                if let Some(region) = &vin.region {
+                    *self.regions.entry(region.clone()).or_insert(0) += 1;
+                }
+                if let Some(manufacturer) = &vin.manufacturer {
+                    *self.manufacturers.entry(manufacturer.clone()).or_insert(0) += 1;
+                }
+            }
+            Err(err) => *self.errors.entry(Finding::from_error(err).code).or_insert(0) += 1,
+        }
+    }
+
+    /// Returns count of rejected VINs
+    pub fn rejected(&self) -> usize { self.errors.values().sum() }
+}
+
+/// Decodes every VIN of the batch with [`get_info`](../fn.get_info.html), returning results
+/// in the order of the input together with the aggregated statistics
+pub fn decode_with_stats<I, S>(vins: I) -> (Vec<DecodeOutcome>, BatchStats)
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut stats = BatchStats::new();
+    let outcomes = vins
+        .into_iter()
+        .enumerate()
+        .map(|(index, input)| {
+            let result = get_info(input.as_ref());
+            stats.record(&result);
+            DecodeOutcome { index, input: input.as_ref().to_string(), result }
+        })
+        .collect();
+    (outcomes, stats)
+}
@@ -0,0 +1,17 @@
+//! Packs external dataset of manufacturers for `vin::mmap::MmapSource`.
+//!
+//! Usage: `vin-pack <input.csv> <output.bin>`, see `vin::mmap::pack_csv` for the input format.
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
+
+fn main() -> io::Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() != 3 {
+        eprintln!("Usage: vin-pack <input.csv> <output.bin>");
+        std::process::exit(2);
+    }
+    let reader = BufReader::new(File::open(&args[1])?);
+    let count = vin::mmap::pack_csv(reader, BufWriter::new(File::create(&args[2])?))?;
+    println!("Packed {} records into {}", count, args[2]);
+    Ok(())
+}
@@ -0,0 +1,10 @@
+//! HTTP service decoding VINs, see `vin::service` for the API.
+//!
+//! Listens on the address given by `VIN_SERVICE_ADDR` environment variable (`0.0.0.0:8080`
+//! by default).
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let address = std::env::var("VIN_SERVICE_ADDR").unwrap_or_else(|_| "0.0.0.0:8080".to_owned());
+    let listener = tokio::net::TcpListener::bind(&address).await?;
+    axum::serve(listener, vin::service::router()).await
+}
@@ -0,0 +1,60 @@
+//! Decoding of body style for covered manufacturers.
+//!
+//! Body style is derived from the WMI (for manufacturers using separate WMIs for trucks and
+//! SUVs) or from the VDS, covered VIN prefixes are listed in `data/body_styles.csv`.
+//!
+//! # Examples
+//! ```
+//! use vin::body::BodyStyle;
+//!
+//! assert_eq!(vin::get_info("1FTFW1ET5DFC10312").unwrap().body_style(), Some(BodyStyle::Pickup));
+//! assert_eq!(vin::get_info("WP0ZZZ99ZTS392124").unwrap().body_style(), None);
+//! ```
+use crate::dicts::{match_pattern, BODY_STYLE_TABLE};
+use crate::VIN;
+
+/// Describes body style of the vehicle
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum BodyStyle {
+    /// Sedan (saloon)
+    Sedan,
+
+    /// Coupe
+    Coupe,
+
+    /// Convertible (cabriolet, roadster)
+    Convertible,
+
+    /// Hatchback or liftback
+    Hatchback,
+
+    /// Station wagon (estate)
+    Wagon,
+
+    /// Sport utility vehicle or crossover
+    Suv,
+
+    /// Pickup truck
+    Pickup,
+
+    /// Van or minivan
+    Van,
+}
+
+impl VIN {
+    /// Returns body style of the vehicle, or `None` if the VIN is not covered by the table
+    pub fn body_style(&self) -> Option<BodyStyle> {
+        match_pattern(BODY_STYLE_TABLE, |x| x.0, self.as_bytes()).and_then(|(_, style)| match *style {
+            "sedan" => Some(BodyStyle::Sedan),
+            "coupe" => Some(BodyStyle::Coupe),
+            "convertible" => Some(BodyStyle::Convertible),
+            "hatchback" => Some(BodyStyle::Hatchback),
+            "wagon" => Some(BodyStyle::Wagon),
+            "suv" => Some(BodyStyle::Suv),
+            "pickup" => Some(BodyStyle::Pickup),
+            "van" => Some(BodyStyle::Van),
+            _ => None,
+        })
+    }
+}
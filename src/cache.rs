@@ -0,0 +1,55 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+/// Cache evicting the least recently used entries
+#[derive(Debug, Clone)]
+pub(in crate) struct LruCache<K, V> {
+    capacity: usize,
+    tick: u64,
+    entries: HashMap<K, (V, u64)>,
+    // tick of the last usage -> key, the first one is the least recently used
+    order: BTreeMap<u64, K>,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> LruCache<K, V> {
+    /// Creates cache holding up to `capacity` entries
+    pub(in crate) fn new(capacity: usize) -> LruCache<K, V> {
+        LruCache { capacity, tick: 0, entries: HashMap::new(), order: BTreeMap::new() }
+    }
+
+    /// Returns the cached value and marks it as recently used
+    pub(in crate) fn get(&mut self, key: &K) -> Option<V> {
+        self.tick += 1;
+        let (value, used) = self.entries.get_mut(key)?;
+        self.order.remove(used);
+        *used = self.tick;
+        self.order.insert(self.tick, key.clone());
+        Some(value.clone())
+    }
+
+    /// Caches the value, evicting the least recently used entry if the cache is full
+    pub(in crate) fn insert(&mut self, key: K, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.tick += 1;
+        if let Some((_, used)) = self.entries.insert(key.clone(), (value, self.tick)) {
+            self.order.remove(&used);
+        } else if self.entries.len() > self.capacity {
+            let oldest = self.order.keys().next().copied();
+            if let Some(evicted) = oldest.and_then(|x| self.order.remove(&x)) {
+                self.entries.remove(&evicted);
+            }
+        }
+        self.order.insert(self.tick, key);
+    }
+
+    /// Returns count of cached entries
+    pub(in crate) fn len(&self) -> usize { self.entries.len() }
+
+    /// Removes all entries
+    pub(in crate) fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+}
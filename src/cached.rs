@@ -0,0 +1,95 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+use crate::cache::LruCache;
+use crate::parse::{ascii_bytes, uppercase};
+use crate::{VINError, VinParser, VIN};
+
+/// Count of independently locked parts of the cache, so threads decoding different VINs
+/// rarely wait for each other
+const SHARDS: usize = 16;
+
+/// Parser memoizing decoded VINs, so repeated decodes of the same VIN (after normalization)
+/// across threads return the shared result
+///
+/// Unlike [`VinParser::with_cache`](struct.VinParser.html#method.with_cache), which caches
+/// the decoded manufacturer information by squish VIN, whole results are cached by the full VIN.
+/// Rejected VINs are not cached.
+///
+/// # Examples
+/// ```
+/// use std::sync::Arc;
+/// use vin::{CachedParser, VinParser};
+///
+/// let parser = CachedParser::new(VinParser::new(), 10_000);
+/// let first = parser.parse("wp0zzz99zts392124").unwrap();
+/// let second = parser.parse("WP0ZZZ99ZTS392124").unwrap();
+/// assert!(Arc::ptr_eq(&first, &second));
+/// assert_eq!(parser.len(), 1);
+/// ```
+pub struct CachedParser {
+    parser: VinParser,
+    shards: Vec<Mutex<LruCache<[u8; 17], Arc<VIN>>>>,
+}
+
+impl CachedParser {
+    /// Creates parser caching up to `capacity` decoded VINs, the least recently used ones are
+    /// evicted (the capacity is split evenly between the parts of the cache)
+    pub fn new(parser: VinParser, capacity: usize) -> CachedParser {
+        let capacity = capacity / SHARDS + usize::from(capacity % SHARDS != 0);
+        CachedParser { parser, shards: (0..SHARDS).map(|_| Mutex::new(LruCache::new(capacity))).collect() }
+    }
+
+    /// Returns the wrapped parser
+    pub fn parser(&self) -> &VinParser { &self.parser }
+
+    /// Returns the decoded VIN, decoding it with the wrapped parser only if it is not cached
+    /// (see [`VinParser::parse`](struct.VinParser.html#method.parse))
+    pub fn parse(&self, vin: &str) -> Result<Arc<VIN>, VINError> {
+        let normalized = self.parser.normalization().apply(vin);
+        let vin = uppercase(&normalized);
+        let bytes = ascii_bytes(&vin)?;
+
+        let shard = &self.shards[shard_index(bytes)];
+        if let Some(cached) = shard.lock().ok().and_then(|mut x| x.get(bytes)) {
+            return Ok(cached);
+        }
+        let decoded = Arc::new(self.parser.parse_unchecked_ascii(bytes)?);
+        if let Ok(mut shard) = shard.lock() {
+            shard.insert(*bytes, decoded.clone());
+        }
+        Ok(decoded)
+    }
+
+    /// Returns count of cached VINs
+    pub fn len(&self) -> usize {
+        self.shards.iter().filter_map(|x| x.lock().ok()).map(|x| x.len()).sum()
+    }
+
+    /// Returns whether no VINs are cached
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Removes all cached VINs
+    pub fn clear(&self) {
+        for mut shard in self.shards.iter().filter_map(|x| x.lock().ok()) {
+            shard.clear();
+        }
+    }
+}
+
+fn shard_index(vin: &[u8; 17]) -> usize {
+    let mut hasher = DefaultHasher::new();
+    vin.hash(&mut hasher);
+    hasher.finish() as usize % SHARDS
+}
+
+impl fmt::Debug for CachedParser {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CachedParser")
+            .field("parser", &self.parser)
+            .field("cached", &self.len())
+            .finish()
+    }
+}
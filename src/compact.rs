@@ -0,0 +1,56 @@
+use crate::tables::ALLOWED_CHARS;
+use crate::{config, VIN};
+
+/// Packs valid uppercased VIN into the base-33 number (takes 86 bits), symbols are valued by
+/// their positions in [`ALLOWED_CHARS`]
+pub(in crate) fn pack(vin: &[u8]) -> u128 {
+    vin.iter().fold(0, |acc, ch| {
+        let value = ALLOWED_CHARS.iter().position(|x| *x as u8 == *ch).unwrap_or(0);
+        acc * 33 + value as u128
+    })
+}
+
+/// Unpacks base-33 number into VIN, returns `None` if the number is too big
+pub(in crate) fn unpack(mut packed: u128) -> Option<[u8; 17]> {
+    let mut vin = [b'0'; 17];
+    for ch in vin.iter_mut().rev() {
+        *ch = ALLOWED_CHARS[(packed % 33) as usize] as u8;
+        packed /= 33;
+    }
+    if packed == 0 { Some(vin) } else { None }
+}
+
+/// Returns the lowest 11 bytes of the packed VIN (big-endian)
+pub(in crate) fn to_bytes(packed: u128) -> [u8; 11] {
+    let mut result = [0; 11];
+    result.copy_from_slice(&packed.to_be_bytes()[5..]);
+    result
+}
+
+fn from_bytes(bytes: &[u8; 11]) -> u128 {
+    let mut buffer = [0; 16];
+    buffer[5..].copy_from_slice(bytes);
+    u128::from_be_bytes(buffer)
+}
+
+impl VIN {
+    /// Returns VIN packed into 11 bytes (base-33 number, big-endian), e.g. for fixed-width
+    /// binary columns. Byte order of packed VINs matches the alphabetical order of VINs.
+    ///
+    /// # Examples
+    /// ```
+    /// let result = vin::get_info("1M8GDM9AXKP042788").unwrap();
+    /// let compact = result.to_compact();
+    /// assert_eq!(vin::VIN::from_compact(&compact).unwrap().as_str(), "1M8GDM9AXKP042788");
+    /// ```
+    pub fn to_compact(&self) -> [u8; 11] { to_bytes(pack(self.as_bytes())) }
+
+    /// Unpacks VIN from 11 bytes produced by [`to_compact`](#method.to_compact) and returns
+    /// information about it, `None` is returned if the bytes do not represent any VIN.
+    ///
+    /// The VIN is decoded by the configured parser, but isn't validated against its profile.
+    pub fn from_compact(compact: &[u8; 11]) -> Option<VIN> {
+        // unpacked VIN consists of allowed characters only
+        unpack(from_bytes(compact)).map(|vin| config::parser().restore(vin))
+    }
+}
@@ -0,0 +1,210 @@
+//! Process-wide defaults of the free functions.
+//!
+//! [`configure`] may be called once at startup, afterwards [`get_info`](../fn.get_info.html)
+//! uses the configured source, validation profile and normalization, while
+//! [`verify_checksum`](../fn.verify_checksum.html), [`check_validity`](../fn.check_validity.html)
+//! and functions validating the input with it (e.g. [`mask`](../fn.mask.html)) use the configured
+//! normalization. Model
+//! years of the VINs decoded by the free functions are bounded by the configured clock and year
+//! policy.
+//!
+//! # Examples
+//! ```
+//! use vin::config::{configure, GlobalConfig, Normalization, ValidationProfile};
+//!
+//! configure(
+//!     GlobalConfig::new()
+//!         .with_profile(ValidationProfile::RequireChecksum)
+//!         .with_normalization(Normalization::StripSeparators)
+//! ).unwrap();
+//!
+//! assert!(vin::get_info("1M8-GDM9A-XKP042788").is_ok());
+//! assert!(vin::get_info("WP0ZZZ99ZTS392124").is_err());
+//! ```
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::source::{BuiltinSource, WmiSource};
+use crate::sync::OnceLock;
+use crate::{Clock, SystemClock, VinParser, YearPolicy};
+
+/// Describes which VINs are accepted as valid
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum ValidationProfile {
+    /// Checksum is verified, but invalid checksum is reported via
+    /// [`VIN::valid_checksum`](../struct.VIN.html#structfield.valid_checksum) only
+    /// (as not applicable for regions which do not mandate the check digit)
+    #[default]
+    Standard,
+
+    /// VINs with invalid checksum are rejected with
+    /// [`VINError::ChecksumError`](../enum.VINError.html#variant.ChecksumError)
+    RequireChecksum,
+
+    /// Chinese GB 16735: check digit is mandatory for vehicles of the Chinese market (WMIs
+    /// starting with `L`), such VINs with invalid checksum are rejected with
+    /// [`VINError::ChecksumError`](../enum.VINError.html#variant.ChecksumError),
+    /// other VINs are validated as with [`Standard`](#variant.Standard)
+    Gb16735,
+
+    /// North American VINs (starting with `1`-`5`) with invalid checksum are rejected with
+    /// [`VINError::ChecksumError`](../enum.VINError.html#variant.ChecksumError), the ones with
+    /// non-numeric serial number with [`VINError::NonNumericSerial`](../enum.VINError.html#variant.NonNumericSerial),
+    /// other VINs are validated as with [`Standard`](#variant.Standard)
+    NorthAmerica,
+}
+
+impl ValidationProfile {
+    /// Returns whether VIN with invalid checksum must be rejected
+    pub(in crate) fn requires_checksum(self, vin: &str) -> bool {
+        match self {
+            ValidationProfile::Standard => false,
+            ValidationProfile::RequireChecksum => true,
+            ValidationProfile::Gb16735 => vin.starts_with('L'),
+            ValidationProfile::NorthAmerica => is_north_american(vin),
+        }
+    }
+
+    /// Returns whether VIN with non-numeric serial number must be rejected
+    pub(in crate) fn requires_numeric_serial(self, vin: &str) -> bool {
+        self == ValidationProfile::NorthAmerica && is_north_american(vin)
+    }
+}
+
+fn is_north_american(vin: &str) -> bool { matches!(vin.as_bytes().first(), Some(b'1'..=b'5')) }
+
+/// Describes how input is cleaned up before validation
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum Normalization {
+    /// Input is validated as is
+    #[default]
+    Exact,
+
+    /// Leading and trailing whitespaces are removed
+    Trim,
+
+    /// All whitespaces and dashes are removed
+    StripSeparators,
+}
+
+impl Normalization {
+    /// Returns normalized input
+    ///
+    /// # Examples
+    /// ```
+    /// use vin::config::Normalization;
+    ///
+    /// assert_eq!(Normalization::Trim.apply(" WP0ZZZ99ZTS392124\n"), "WP0ZZZ99ZTS392124");
+    /// assert_eq!(Normalization::StripSeparators.apply("WP0 ZZZ99Z-TS392124"), "WP0ZZZ99ZTS392124");
+    /// ```
+    pub fn apply(self, vin: &str) -> Cow<'_, str> {
+        match self {
+            Normalization::Exact => Cow::Borrowed(vin),
+            Normalization::Trim => Cow::Borrowed(vin.trim()),
+            Normalization::StripSeparators if vin.contains(is_separator) =>
+                Cow::Owned(vin.chars().filter(|x| !is_separator(*x)).collect()),
+            Normalization::StripSeparators => Cow::Borrowed(vin),
+        }
+    }
+}
+
+fn is_separator(ch: char) -> bool { ch.is_whitespace() || ch == '-' }
+
+/// Holds process-wide defaults
+pub struct GlobalConfig {
+    source: Box<dyn WmiSource>,
+    profile: ValidationProfile,
+    normalization: Normalization,
+    clock: Box<dyn Clock>,
+    year_policy: YearPolicy,
+}
+
+impl GlobalConfig {
+    /// Creates configuration with built-in dictionary and default profile and normalization
+    pub fn new() -> GlobalConfig {
+        GlobalConfig {
+            source: Box::new(BuiltinSource),
+            profile: ValidationProfile::default(),
+            normalization: Normalization::default(),
+            clock: Box::new(SystemClock),
+            year_policy: YearPolicy::default(),
+        }
+    }
+
+    /// Sets source of manufacturers information (e.g. dictionary overrides)
+    pub fn with_source<S: WmiSource + 'static>(mut self, source: S) -> GlobalConfig {
+        self.source = Box::new(source);
+        self
+    }
+
+    /// Sets validation profile
+    pub fn with_profile(mut self, profile: ValidationProfile) -> GlobalConfig {
+        self.profile = profile;
+        self
+    }
+
+    /// Sets normalization of the input
+    pub fn with_normalization(mut self, normalization: Normalization) -> GlobalConfig {
+        self.normalization = normalization;
+        self
+    }
+
+    /// Sets source of the current year bounding the decoded model years, e.g. the time of
+    /// the host in environments without system time
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> GlobalConfig {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    /// Sets how many model years in advance of the current year are plausible
+    pub fn with_year_policy(mut self, policy: YearPolicy) -> GlobalConfig {
+        self.year_policy = policy;
+        self
+    }
+}
+
+impl Default for GlobalConfig {
+    fn default() -> Self { GlobalConfig::new() }
+}
+
+impl fmt::Debug for GlobalConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GlobalConfig")
+            .field("profile", &self.profile)
+            .field("normalization", &self.normalization)
+            .field("year_policy", &self.year_policy)
+            .finish()
+    }
+}
+
+static PARSER: OnceLock<VinParser> = OnceLock::new();
+
+static DEFAULT_PARSER: OnceLock<VinParser> = OnceLock::new();
+
+/// Sets process-wide defaults, returns the given configuration back if they were already set
+pub fn configure(config: GlobalConfig) -> Result<(), GlobalConfig> {
+    let mut config = Some(config);
+    PARSER.get_or_init(|| {
+        let config = config.take().unwrap_or_default();
+        let clock = config.clock;
+        VinParser::with_source(config.source)
+            .with_profile(config.profile)
+            .with_normalization(config.normalization)
+            .with_clock(move || clock.current_year())
+            .with_year_policy(config.year_policy)
+    });
+    match config {
+        None => Ok(()),
+        Some(config) => Err(config),
+    }
+}
+
+/// Returns the configured parser
+pub(in crate) fn parser() -> &'static VinParser {
+    PARSER.get().unwrap_or_else(|| DEFAULT_PARSER.get_or_init(VinParser::new))
+}
+
+/// Returns the configured normalization
+pub(in crate) fn normalization() -> Normalization { parser().normalization() }
@@ -0,0 +1,167 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::dicts::{
+    countries, manufacturer_rows, region_name, region_row, CountryRow, CODE_SEQ, COUNTRY_TABLE, REGION_TABLE,
+};
+use crate::VIN;
+
+/// Holds country assigned to the VIN country code by the built-in dictionary
+///
+/// # Examples
+/// ```
+/// let result = vin::get_info("WP0ZZZ99ZTS392124").unwrap();
+/// let country = result.country().unwrap();
+/// assert_eq!(country.name(), "Germany/West Germany");
+/// assert_eq!((country.iso_alpha2(), country.iso_alpha3()), (Some("DE"), Some("DEU")));
+/// assert_eq!(country.codes(), ("WA".to_owned(), "W0".to_owned()));
+/// assert_eq!(country.region(), "Europe");
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Country {
+    row: &'static CountryRow,
+    region: &'static str,
+}
+
+impl Country {
+    /// Returns country with the given VIN country code (first 2 characters of the VIN)
+    pub fn from_code(code: &str) -> Option<Country> {
+        let code = code.to_uppercase();
+        let row = *countries().get(&code)?;
+        let region = region_name(&code)?;
+        Some(Country { row, region })
+    }
+
+    /// Returns name of the country
+    pub fn name(&self) -> &'static str { self.row.1 }
+
+    /// Returns ISO 3166-1 alpha-2 code, if the country has one (e.g. not for `USSR/CIS`)
+    pub fn iso_alpha2(&self) -> Option<&'static str> { Some(self.row.2).filter(|x| !x.is_empty()) }
+
+    /// Returns ISO 3166-1 alpha-3 code, if the country has one
+    pub fn iso_alpha3(&self) -> Option<&'static str> { Some(self.row.3).filter(|x| !x.is_empty()) }
+
+    /// Returns the first and the last VIN country code of the range assigned to the country
+    /// (ranges follow the `A..Z1..90` order)
+    pub fn codes(&self) -> (String, String) {
+        let mut chars = self.row.0.chars();
+        let (first, from, to) = (chars.next(), chars.nth(1), chars.next());
+        let code = |ch| first.into_iter().chain(ch).collect();
+        (code(from), code(to))
+    }
+
+    /// Returns whether the VIN country code belongs to the range assigned to the country
+    pub fn contains_code(&self, code: &str) -> bool {
+        countries().get(&code.to_uppercase()).map_or(false, |row| std::ptr::eq(*row, self.row))
+    }
+
+    /// Returns region of the country
+    pub fn region(&self) -> &'static str { self.region }
+
+    fn from_row(row: &'static CountryRow) -> Option<Country> {
+        Some(Country { row, region: region_name(row.0)? })
+    }
+
+    fn is(&self, name: &str) -> bool {
+        [self.row.1, self.row.2, self.row.3].iter().any(|x| !x.is_empty() && x.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Holds region assigned to the first VIN character by the built-in dictionary
+///
+/// # Examples
+/// ```
+/// use vin::Region;
+///
+/// let region = Region::from_code('0').unwrap();
+/// assert_eq!(region.name(), "South America");
+/// assert_eq!(region.codes(), "890");
+/// assert_eq!(Region::from_code('L'), Region::from_code('j'));
+/// assert_eq!(Region::from_code('I'), None);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Region {
+    name: &'static str,
+}
+
+impl Region {
+    /// Returns region with the given code (first character of the VIN)
+    pub fn from_code(code: char) -> Option<Region> {
+        region_row(code).map(|(_, name, _)| Region { name })
+    }
+
+    /// Returns name of the region
+    pub fn name(&self) -> &'static str { self.name }
+
+    /// Returns all codes assigned to the region (in the `A..Z1..90` order)
+    pub fn codes(&self) -> String {
+        CODE_SEQ.chars().filter(|x| region_row(*x).map_or(false, |(_, name, _)| *name == self.name)).collect()
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// Returns names of all regions of the built-in dictionary
+///
+/// # Examples
+/// ```
+/// let regions: Vec<&str> = vin::all_regions().collect();
+/// assert_eq!(regions, ["Africa", "Asia", "Europe", "North America", "Oceania", "South America"]);
+/// ```
+pub fn all_regions() -> impl Iterator<Item = &'static str> {
+    REGION_TABLE
+        .iter()
+        .enumerate()
+        .filter(|(i, (_, region, _))| !REGION_TABLE[..*i].iter().any(|x| x.1 == *region))
+        .map(|(_, (_, region, _))| *region)
+}
+
+/// Returns countries of the region (matched case-insensitively), one for every range of
+/// VIN country codes, so a country may be returned several times
+///
+/// # Examples
+/// ```
+/// let countries: Vec<&str> = vin::countries_in("oceania").map(|x| x.name()).collect();
+/// assert_eq!(countries, ["Australia", "New Zealand"]);
+/// ```
+pub fn countries_in(region: &str) -> impl Iterator<Item = Country> + '_ {
+    COUNTRY_TABLE
+        .iter()
+        .filter_map(Country::from_row)
+        .filter(move |x| x.region.eq_ignore_ascii_case(region))
+}
+
+/// Returns WMIs (or their first 2 characters for groups of WMIs) of the built-in dictionary
+/// assigned to the country, given by its name or ISO 3166-1 code (matched case-insensitively)
+///
+/// # Examples
+/// ```
+/// let wmis: Vec<&str> = vin::wmis_in_country("CHE").collect();
+/// assert!(wmis.contains(&"TCC"));
+/// assert!(wmis.iter().all(|x| vin::Country::from_code(&x[..2]).unwrap().name() == "Switzerland"));
+/// ```
+pub fn wmis_in_country(country: &str) -> impl Iterator<Item = &'static str> + '_ {
+    let mut seen = HashSet::new();
+    manufacturer_rows()
+        .map(|(code, _)| *code)
+        // extended codes of small manufacturers are not WMIs
+        .filter(|code| code.len() <= 3)
+        .filter(move |code| seen.insert(*code))
+        .filter(move |code| Country::from_code(&code[..2]).map_or(false, |x| x.is(country)))
+}
+
+impl fmt::Display for Country {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl VIN {
+    /// Returns country of the manufacturer assigned to the VIN country code by the built-in
+    /// dictionary, along with its ISO codes and region
+    pub fn country(&self) -> Option<Country> { Country::from_code(&self.as_str()[..2]) }
+}
@@ -0,0 +1,136 @@
+//! Reporting of codes missing in the built-in dictionary.
+//!
+//! Allows to aggregate which WMIs can not be decoded in production, so that the dataset
+//! can be improved.
+//!
+//! # Examples
+//! ```
+//! use vin::coverage::{CoverageReport, DecodeOutcome};
+//!
+//! let mut report = CoverageReport::new();
+//! for vin_number in &["WP0ZZZ99ZTS392124", "AP0ZZZ99ZTS392124", "AP0ZZZ99ZTS392125"] {
+//!     report.record(vin_number).unwrap();
+//! }
+//! assert_eq!(report.count(DecodeOutcome::Decoded), 1);
+//! assert_eq!(report.unknown()[0].wmi, "AP0");
+//! assert_eq!(report.unknown()[0].count, 2);
+//! ```
+use std::collections::HashMap;
+
+use crate::dicts::{get_country, get_manufacturer, get_region};
+use crate::parse::validated;
+use crate::VINError;
+
+/// Describes how completely VIN was decoded by the built-in dictionary
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum DecodeOutcome {
+    /// Region, country and manufacturer are known
+    Decoded,
+
+    /// Region and country are known, but manufacturer is not
+    UnknownWmi,
+
+    /// Region is known, but country is not
+    UnknownCountry,
+
+    /// Region is not known
+    UnknownRegion,
+}
+
+/// Returns how completely the VIN is decoded by the built-in dictionary
+///
+/// # Examples
+/// ```
+/// use vin::coverage::{outcome, DecodeOutcome};
+///
+/// assert_eq!(outcome("WP0ZZZ99ZTS392124").unwrap(), DecodeOutcome::Decoded);
+/// assert_eq!(outcome("WZZZZZ99ZTS392124").unwrap(), DecodeOutcome::UnknownWmi);
+/// assert_eq!(outcome("AZZZZZ99ZTS392124").unwrap(), DecodeOutcome::UnknownCountry);
+/// ```
+pub fn outcome(vin: &str) -> Result<DecodeOutcome, VINError> {
+    let vin = validated(vin)?;
+    Ok(wmi_outcome(&vin[..3]))
+}
+
+fn wmi_outcome(wmi: &str) -> DecodeOutcome {
+    if get_region(&wmi[..1]).is_none() {
+        DecodeOutcome::UnknownRegion
+    } else if get_country(&wmi[..2]).is_none() {
+        DecodeOutcome::UnknownCountry
+    } else if get_manufacturer(wmi).is_none() {
+        DecodeOutcome::UnknownWmi
+    } else {
+        DecodeOutcome::Decoded
+    }
+}
+
+/// Holds count of occurrences of the WMI which was not fully decoded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownCode {
+    /// WMI of the VINs
+    pub wmi: String,
+
+    /// What is missing in the dictionary
+    pub outcome: DecodeOutcome,
+
+    /// Count of recorded occurrences
+    pub count: usize,
+}
+
+/// Aggregates decode outcomes of the VINs
+#[derive(Debug, Clone, Default)]
+pub struct CoverageReport {
+    outcomes: HashMap<DecodeOutcome, usize>,
+    unknown: HashMap<String, (DecodeOutcome, usize)>,
+}
+
+impl CoverageReport {
+    /// Creates an empty report
+    pub fn new() -> CoverageReport { CoverageReport::default() }
+
+    /// Decodes the VIN with the built-in dictionary and records the outcome
+    pub fn record(&mut self, vin: &str) -> Result<DecodeOutcome, VINError> {
+        let vin = validated(vin)?;
+        let outcome = wmi_outcome(&vin[..3]);
+        self.record_outcome(&vin[..3], outcome);
+        Ok(outcome)
+    }
+
+    /// Records the outcome of the decoding done elsewhere (e.g. with a custom source)
+    pub fn record_outcome(&mut self, wmi: &str, outcome: DecodeOutcome) {
+        *self.outcomes.entry(outcome).or_insert(0) += 1;
+        if outcome != DecodeOutcome::Decoded {
+            self.record_unknown(wmi, outcome);
+        }
+    }
+
+    /// Records WMI which was not fully decoded
+    pub fn record_unknown(&mut self, wmi: &str, outcome: DecodeOutcome) {
+        let entry = self.unknown.entry(wmi.to_uppercase()).or_insert((outcome, 0));
+        entry.0 = outcome;
+        entry.1 += 1;
+    }
+
+    /// Returns count of recorded VINs with the given outcome
+    pub fn count(&self, outcome: DecodeOutcome) -> usize {
+        self.outcomes.get(&outcome).cloned().unwrap_or(0)
+    }
+
+    /// Returns count of all recorded VINs
+    pub fn total(&self) -> usize { self.outcomes.values().sum() }
+
+    /// Returns not fully decoded WMIs, most frequent first
+    pub fn unknown(&self) -> Vec<UnknownCode> {
+        let mut result: Vec<UnknownCode> = self.unknown
+            .iter()
+            .map(|(wmi, (outcome, count))| UnknownCode {
+                wmi: wmi.clone(),
+                outcome: *outcome,
+                count: *count,
+            })
+            .collect();
+        result.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.wmi.cmp(&b.wmi)));
+        result
+    }
+}
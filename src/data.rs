@@ -0,0 +1,91 @@
+//! Integrity audit of the dataset.
+//!
+//! # Examples
+//! ```
+//! let audit = vin::data::audit();
+//! assert!(audit.wmis > 0 && audit.countries > 0);
+//! for wmi in &audit.missing_country {
+//!     println!("{} is not linked to a country", wmi);
+//! }
+//! ```
+use std::collections::{HashMap, HashSet};
+
+#[cfg(any(feature = "json", feature = "toml"))]
+use crate::dicts::Overrides;
+use crate::dicts::{countries, manufacturers, region_name};
+
+/// Holds results of the dataset audit
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DataAudit {
+    /// Count of manufacturer entries (WMIs or their first 2 characters)
+    pub wmis: usize,
+
+    /// Count of distinct countries
+    pub countries: usize,
+
+    /// Count of assigned country codes
+    pub country_codes: usize,
+
+    /// Entries for the first 2 characters of WMIs and the WMIs overriding them with other
+    /// manufacturer, in alphabetical order
+    pub overlapping: Vec<(String, String)>,
+
+    /// Manufacturer entries which country code is not assigned to any country, in alphabetical order
+    pub missing_country: Vec<String>,
+
+    /// Manufacturer entries which region code is not assigned to any region, in alphabetical order
+    pub missing_region: Vec<String>,
+}
+
+impl DataAudit {
+    /// Returns whether every manufacturer entry is linked to a country and a region
+    /// (overlapping entries are intended and are not considered)
+    pub fn is_consistent(&self) -> bool { self.missing_country.is_empty() && self.missing_region.is_empty() }
+}
+
+/// Audits the built-in dataset
+pub fn audit() -> DataAudit {
+    audit_entries(manufacturers(), &builtin_countries())
+}
+
+/// Audits the built-in dataset merged with the overrides
+#[cfg(any(feature = "json", feature = "toml"))]
+pub fn audit_with(overrides: &Overrides) -> DataAudit {
+    let (countries, overridden) = overrides.entries();
+    let mut merged_countries = builtin_countries();
+    merged_countries.extend(countries.iter().map(|(code, x)| (code.clone(), x.clone())));
+    let mut merged_manufacturers = manufacturers().clone();
+    merged_manufacturers.extend(overridden.iter().map(|(code, x)| (code.clone(), x.clone())));
+    audit_entries(&merged_manufacturers, &merged_countries)
+}
+
+fn builtin_countries() -> HashMap<String, String> {
+    countries().iter().map(|(code, row)| (code.clone(), row.1.to_string())).collect()
+}
+
+fn audit_entries(manufacturers: &HashMap<String, String>, countries: &HashMap<String, String>) -> DataAudit {
+    let mut overlapping: Vec<(String, String)> = manufacturers
+        .iter()
+        .filter(|(code, _)| code.len() == 3)
+        .filter_map(|(code, name)| match manufacturers.get(&code[..2]) {
+            Some(group) if group != name => Some((code[..2].to_string(), code.clone())),
+            _ => None,
+        })
+        .collect();
+    overlapping.sort();
+
+    let sorted = |filter: &dyn Fn(&str) -> bool| {
+        let mut result: Vec<String> = manufacturers.keys().filter(|x| filter(x)).cloned().collect();
+        result.sort();
+        result
+    };
+
+    DataAudit {
+        wmis: manufacturers.len(),
+        countries: countries.values().collect::<HashSet<_>>().len(),
+        country_codes: countries.len(),
+        overlapping,
+        missing_country: sorted(&|code| !countries.contains_key(&code[..2])),
+        missing_region: sorted(&|code| region_name(code).is_none()),
+    }
+}
@@ -0,0 +1,239 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::dicts::{countries, get_country, get_manufacturer, get_region, lookup_longest};
+use crate::source::{WmiRecord, WmiSource};
+use crate::tables::is_allowed;
+
+/// Format of the overrides file
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Format {
+    /// JSON document (requires `json` feature)
+    #[cfg(feature = "json")]
+    Json,
+
+    /// TOML document (requires `toml` feature)
+    #[cfg(feature = "toml")]
+    Toml,
+}
+
+impl Format {
+    /// Guesses format by the extension of the file
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Format> {
+        match path.as_ref().extension()?.to_str()?.to_lowercase().as_str() {
+            #[cfg(feature = "json")]
+            "json" => Some(Format::Json),
+            #[cfg(feature = "toml")]
+            "toml" => Some(Format::Toml),
+            _ => None,
+        }
+    }
+}
+
+/// Provides possible errors during overrides loading
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum OverridesError {
+    /// Format of the file can not be guessed by its extension
+    UnknownFormat,
+
+    /// Overrides can not be read
+    Io(io::Error),
+
+    /// Overrides can not be parsed (contains description of the problem)
+    Parse(String),
+
+    /// Code of the entry is malformed (contains the code)
+    InvalidCode(String),
+}
+
+impl fmt::Display for OverridesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OverridesError::UnknownFormat =>
+                write!(f, "Unknown format of overrides file, .json or .toml expected."),
+            OverridesError::Io(err) =>
+                write!(f, "Overrides can not be read: {}.", err),
+            OverridesError::Parse(err) =>
+                write!(f, "Overrides can not be parsed: {}.", err),
+            OverridesError::InvalidCode(code) =>
+                write!(f, "Invalid code in overrides: {:?}.", code),
+        }
+    }
+}
+
+impl From<io::Error> for OverridesError {
+    fn from(err: io::Error) -> Self { OverridesError::Io(err) }
+}
+
+/// Describes built-in entry replaced by the override
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    /// Code of the entry (2 characters for countries, 2 or 3 characters for manufacturers)
+    pub code: String,
+
+    /// Built-in value
+    pub builtin: String,
+
+    /// Value provided by the override
+    pub replacement: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RawOverrides {
+    countries: BTreeMap<String, String>,
+    manufacturers: BTreeMap<String, String>,
+}
+
+/// Built-in dictionary merged with user-supplied entries.
+///
+/// Can be used as a source of [`VinParser`](../struct.VinParser.html).
+#[derive(Debug, Clone, Default)]
+pub struct Overrides {
+    countries: HashMap<String, String>,
+    manufacturers: HashMap<String, String>,
+    conflicts: Vec<Conflict>,
+}
+
+impl Overrides {
+    /// Returns built-in entries replaced by the overrides
+    pub fn conflicts(&self) -> &[Conflict] { &self.conflicts }
+
+    /// Returns country and manufacturer entries
+    pub(in crate) fn entries(&self) -> (&HashMap<String, String>, &HashMap<String, String>) {
+        (&self.countries, &self.manufacturers)
+    }
+
+    /// Returns count of country entries
+    pub fn countries_count(&self) -> usize { self.countries.len() }
+
+    /// Returns count of manufacturer entries
+    pub fn manufacturers_count(&self) -> usize { self.manufacturers.len() }
+
+    fn from_raw(raw: RawOverrides) -> Result<Overrides, OverridesError> {
+        let mut result = Overrides::default();
+
+        for (code, country) in raw.countries {
+            let code = validate_code(&code, 2..=2)?;
+            if let Some(builtin) = countries().get(&code).map(|row| row.1).filter(|x| *x != country) {
+                result.conflicts.push(Conflict {
+                    code: code.clone(),
+                    builtin: builtin.to_string(),
+                    replacement: country.clone(),
+                });
+            }
+            result.countries.insert(code, country);
+        }
+
+        for (code, manufacturer) in raw.manufacturers {
+            let code = validate_code(&code, 2..=3)?;
+            if let Some(builtin) = get_manufacturer(&code).filter(|x| *x != manufacturer) {
+                result.conflicts.push(Conflict {
+                    code: code.clone(),
+                    builtin,
+                    replacement: manufacturer.clone(),
+                });
+            }
+            result.manufacturers.insert(code, manufacturer);
+        }
+
+        Ok(result)
+    }
+}
+
+impl Overrides {
+    /// Returns manufacturer of the VIN prefix (WMI or the whole VIN). Overridden WMI is
+    /// preferred to the built-in one (or the extended code of the small manufacturer), which
+    /// is preferred to the overridden and built-in first 2 characters.
+    fn manufacturer(&self, prefix: &str) -> Option<String> {
+        let builtin = lookup_longest(prefix);
+        let builtin_name = |min_len| builtin.as_ref().filter(|x| x.code.len() >= min_len)?.manufacturer;
+
+        self.manufacturers
+            .get(&prefix[..3])
+            .map(String::as_str)
+            .or_else(|| builtin_name(3))
+            .or_else(|| self.manufacturers.get(&prefix[..2]).map(String::as_str))
+            .or_else(|| builtin_name(0))
+            .map(str::to_string)
+    }
+}
+
+impl WmiSource for Overrides {
+    fn lookup(&self, wmi: &str) -> Option<WmiRecord> {
+        Some(WmiRecord {
+            manufacturer: self.manufacturer(wmi)?,
+            country: self.lookup_country(&wmi[..2]),
+            region: get_region(&wmi[..1]),
+        })
+    }
+
+    fn lookup_country(&self, code: &str) -> Option<String> {
+        self.countries.get(code).cloned().or_else(|| get_country(code))
+    }
+
+    fn lookup_vin(&self, vin: &str) -> Option<WmiRecord> {
+        Some(WmiRecord {
+            manufacturer: self.manufacturer(vin)?,
+            country: self.lookup_country(&vin[..2]),
+            region: get_region(&vin[..1]),
+        })
+    }
+}
+
+fn validate_code(code: &str, length: std::ops::RangeInclusive<usize>) -> Result<String, OverridesError> {
+    let code = code.to_uppercase();
+    if length.contains(&code.chars().count()) && code.chars().all(is_allowed) {
+        Ok(code)
+    } else {
+        Err(OverridesError::InvalidCode(code))
+    }
+}
+
+/// Loads overrides from the file, guessing its format by extension (`.json` or `.toml`)
+pub fn load_overrides<P: AsRef<Path>>(path: P) -> Result<Overrides, OverridesError> {
+    let format = Format::from_path(&path).ok_or(OverridesError::UnknownFormat)?;
+    load_overrides_from(File::open(path)?, format)
+}
+
+/// Loads overrides from the reader.
+///
+/// Document may contain `countries` table (2-characters code to country name) and
+/// `manufacturers` table (WMI or its first 2 characters to manufacturer name).
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "json")] {
+/// use vin::VinParser;
+/// use vin::dicts::{load_overrides_from, Format};
+///
+/// let document = r#"{"manufacturers": {"WP0": "Porsche AG", "XTA": "Lada"}}"#;
+/// let overrides = load_overrides_from(document.as_bytes(), Format::Json).unwrap();
+/// assert_eq!(overrides.conflicts()[0].builtin, "Porsche car");
+///
+/// let parser = VinParser::with_source(overrides);
+/// assert_eq!(parser.parse("XTA21099043576182").unwrap().manufacturer.unwrap(), "Lada");
+/// # }
+/// ```
+pub fn load_overrides_from<R: Read>(mut reader: R, format: Format) -> Result<Overrides, OverridesError> {
+    let mut document = String::new();
+    reader.read_to_string(&mut document)?;
+
+    let raw: RawOverrides = match format {
+        #[cfg(feature = "json")]
+        Format::Json => serde_json::from_str(&document)
+            .map_err(|e| OverridesError::Parse(e.to_string()))?,
+        #[cfg(feature = "toml")]
+        Format::Toml => toml::from_str(&document)
+            .map_err(|e| OverridesError::Parse(e.to_string()))?,
+    };
+
+    Overrides::from_raw(raw)
+}
@@ -0,0 +1,121 @@
+use crate::VIN;
+
+/// Describes decoded attribute of the VIN compared by [`diff`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Attribute {
+    /// Name of the manufacturer
+    Manufacturer,
+
+    /// Country of the manufacturer
+    Country,
+
+    /// Region of the manufacturer
+    Region,
+
+    /// Validity of the checksum
+    Checksum,
+
+    /// Possible model years
+    ModelYears,
+}
+
+/// Holds symbols of the VINs differing at the position
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct SymbolDiff {
+    /// Position (0-based) of the symbols
+    pub position: usize,
+
+    /// Symbol of the first VIN
+    pub left: char,
+
+    /// Symbol of the second VIN
+    pub right: char,
+}
+
+/// Holds values of the decoded attribute differing between the VINs (`None` if unknown)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AttributeDiff {
+    /// Compared attribute
+    pub attribute: Attribute,
+
+    /// Value of the first VIN
+    pub left: Option<String>,
+
+    /// Value of the second VIN
+    pub right: Option<String>,
+}
+
+/// Holds differences between two decoded VINs (see [`diff`])
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct VinDiff {
+    /// Differing symbols, in the order of their positions
+    pub positions: Vec<SymbolDiff>,
+
+    /// Differing decoded attributes
+    pub attributes: Vec<AttributeDiff>,
+}
+
+impl VinDiff {
+    /// Returns whether the VINs are identical
+    pub fn is_empty(&self) -> bool { self.positions.is_empty() && self.attributes.is_empty() }
+
+    /// Returns whether the VINs differ only by two swapped adjacent symbols, a common typo
+    pub fn is_transposition(&self) -> bool {
+        match self.positions.as_slice() {
+            [a, b] => b.position == a.position + 1 && a.left == b.right && a.right == b.left,
+            _ => false,
+        }
+    }
+}
+
+/// Returns which positions and which decoded attributes differ between two VINs, e.g. to
+/// compare the VIN on a title against the one on the vehicle
+///
+/// # Examples
+/// ```
+/// use vin::{diff, Attribute};
+///
+/// let title = vin::get_info("1M8GDM9AXKP042788").unwrap();
+/// let vehicle = vin::get_info("2M8GDM9AXKP042788").unwrap();
+/// let result = diff(&title, &vehicle);
+/// assert_eq!(result.positions.len(), 1);
+/// assert_eq!((result.positions[0].left, result.positions[0].right), ('1', '2'));
+/// assert!(result.attributes.iter().any(|x| x.attribute == Attribute::Checksum));
+/// ```
+pub fn diff(a: &VIN, b: &VIN) -> VinDiff {
+    let positions = a.as_bytes()
+        .iter()
+        .zip(b.as_bytes().iter())
+        .enumerate()
+        .filter(|(_, (left, right))| left != right)
+        .map(|(position, (left, right))| SymbolDiff { position, left: *left as char, right: *right as char })
+        .collect();
+
+    let checksum = |x: &VIN| {
+        let status = match (x.valid_checksum.is_valid(), x.valid_checksum.is_invalid()) {
+            (true, _) => "valid",
+            (_, true) => "invalid",
+            _ => "not applicable",
+        };
+        Some(status.to_string())
+    };
+    let years = |x: &VIN| {
+        let years: Vec<String> = x.years().candidates.iter().map(u32::to_string).collect();
+        Some(years.join(";")).filter(|x| !x.is_empty())
+    };
+    let values = [
+        (Attribute::Manufacturer, a.manufacturer.clone(), b.manufacturer.clone()),
+        (Attribute::Country, a.country.clone(), b.country.clone()),
+        (Attribute::Region, a.region.clone(), b.region.clone()),
+        (Attribute::Checksum, checksum(a), checksum(b)),
+        (Attribute::ModelYears, years(a), years(b)),
+    ];
+    let attributes = values
+        .iter()
+        .filter(|(_, left, right)| left != right)
+        .map(|(attribute, left, right)| AttributeDiff { attribute: *attribute, left: left.clone(), right: right.clone() })
+        .collect();
+
+    VinDiff { positions, attributes }
+}
@@ -0,0 +1,69 @@
+//! Decoding of fuel type and displacement from engine codes.
+//!
+//! Some manufacturers encode the engine in the VDS (e.g. 8-th position for North American
+//! vehicles), covered manufacturers and codes are listed in `data/engines.csv`.
+//! Other VINs are not covered, which is not the same as having no engine information.
+//!
+//! # Examples
+//! ```
+//! use vin::engine::{EngineInfo, FuelType};
+//!
+//! let result = vin::get_info("1C6RR7LT5ES123456").unwrap();
+//! assert_eq!(result.engine_info(), Some(EngineInfo { fuel: FuelType::Gasoline, displacement_cc: Some(5700) }));
+//! assert_eq!(vin::get_info("WP0ZZZ99ZTS392124").unwrap().engine_info(), None);
+//! ```
+use crate::dicts::ENGINE_TABLE;
+use crate::VIN;
+
+/// Describes fuel type of the vehicle
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FuelType {
+    /// Gasoline (petrol) engine
+    Gasoline,
+
+    /// Diesel engine
+    Diesel,
+
+    /// Hybrid of the combustion engine and electric motor
+    Hybrid,
+
+    /// Battery-electric vehicle
+    Electric,
+}
+
+/// Holds decoded information about the engine
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct EngineInfo {
+    /// Fuel type
+    pub fuel: FuelType,
+
+    /// Displacement in cubic centimeters (`None` for electric vehicles)
+    pub displacement_cc: Option<u32>,
+}
+
+impl VIN {
+    /// Returns decoded information about the engine, or `None` if the manufacturer or
+    /// the engine code is not covered by the table
+    pub fn engine_info(&self) -> Option<EngineInfo> {
+        let vin = self.as_bytes();
+        ENGINE_TABLE
+            .iter()
+            .filter(|(prefix, position, code, _, _)| {
+                self.as_str().starts_with(prefix)
+                    && position.parse().ok()
+                        .and_then(|x: usize| vin.get(x.checked_sub(1)?))
+                        .map_or(false, |x| *code == "?" || code.as_bytes() == [*x])
+            })
+            .max_by_key(|(prefix, _, code, _, _)| (prefix.len(), *code != "?"))
+            .map(|(_, _, _, fuel, displacement)| EngineInfo {
+                fuel: match *fuel {
+                    "diesel" => FuelType::Diesel,
+                    "hybrid" => FuelType::Hybrid,
+                    "electric" => FuelType::Electric,
+                    _ => FuelType::Gasoline,
+                },
+                displacement_cc: displacement.parse().ok(),
+            })
+    }
+}
@@ -0,0 +1,41 @@
+//! Offline detection of battery-electric vehicles.
+//!
+//! Detection is based on WMIs of manufacturers producing only electric vehicles and on VDS
+//! codes of electric models listed in `data/ev.csv`, so it is a heuristic for quick filtering,
+//! not a replacement for the registration data.
+//!
+//! # Examples
+//! ```
+//! use vin::ev::EvStatus;
+//!
+//! assert_eq!(vin::get_info("5YJ3E1EA7KF317000").unwrap().is_probably_ev(), EvStatus::Yes);
+//! assert_eq!(vin::get_info("WP0ZZZ99ZTS392124").unwrap().is_probably_ev(), EvStatus::No);
+//! assert_eq!(vin::get_info("1M8GDM9AXKP042788").unwrap().is_probably_ev(), EvStatus::Unknown);
+//! ```
+use crate::dicts::{match_pattern, EV_TABLE};
+use crate::VIN;
+
+/// Describes whether the vehicle is battery-electric
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum EvStatus {
+    /// Manufacturer or model produces only battery-electric vehicles
+    Yes,
+
+    /// Model is known to have no battery-electric versions
+    No,
+
+    /// Model is not covered by the table
+    Unknown,
+}
+
+impl VIN {
+    /// Returns whether the vehicle is battery-electric, judging by the VIN only
+    pub fn is_probably_ev(&self) -> EvStatus {
+        match match_pattern(EV_TABLE, |x| x.0, self.as_bytes()) {
+            Some((_, "yes")) => EvStatus::Yes,
+            Some(_) => EvStatus::No,
+            None => EvStatus::Unknown,
+        }
+    }
+}
@@ -0,0 +1,218 @@
+//! Position-by-position breakdown of the VIN for tutorials and support tooling.
+//!
+//! # Examples
+//! ```
+//! use vin::explain::Role;
+//!
+//! let explanation = vin::get_info("1M8GDM9AXKP042789").unwrap().explain();
+//! let check_digit = &explanation.positions[16];
+//! assert_eq!((check_digit.symbol, check_digit.weight, check_digit.contribution), ('9', 2, 18));
+//! assert_eq!(explanation.positions[8].role, Role::CheckDigit);
+//! assert_eq!(explanation.positions[8].meaning.as_deref(), Some("check digit, 1 expected"));
+//! ```
+use std::fmt;
+
+use crate::{dicts, ChecksumStatus, VIN};
+
+/// Describes role of the VIN position
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Role {
+    /// Region of the manufacturer (the 1-st symbol)
+    Region,
+
+    /// Country of the manufacturer (the 2-nd symbol, together with the 1-st one)
+    Country,
+
+    /// Manufacturer (the 3-rd symbol, together with the first two)
+    Manufacturer,
+
+    /// Vehicle attributes defined by the manufacturer (4-8-th symbols)
+    VehicleDescriptor,
+
+    /// Check digit (the 9-th symbol)
+    CheckDigit,
+
+    /// Model year (the 10-th symbol)
+    ModelYear,
+
+    /// Assembly plant (the 11-th symbol)
+    Plant,
+
+    /// Manufacturer code of small manufacturers (12-14-th symbols, if the 3-rd one is 9)
+    SmallManufacturer,
+
+    /// Serial number (12-17-th symbols, or 15-17-th ones for small manufacturers)
+    SerialNumber,
+}
+
+impl Role {
+    /// Returns English description of the role
+    pub fn description(&self) -> &'static str {
+        match self {
+            Role::Region => "region",
+            Role::Country => "country",
+            Role::Manufacturer => "manufacturer",
+            Role::VehicleDescriptor => "vehicle descriptor",
+            Role::CheckDigit => "check digit",
+            Role::ModelYear => "model year",
+            Role::Plant => "plant",
+            Role::SmallManufacturer => "small manufacturer",
+            Role::SerialNumber => "serial number",
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "{}", self.description()) }
+}
+
+/// Holds explanation of a single VIN position
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Position {
+    /// Position (1-based) of the symbol
+    pub position: usize,
+
+    /// The symbol
+    pub symbol: char,
+
+    /// Role of the position
+    pub role: Role,
+
+    /// Decoded meaning of the symbol, if known
+    pub meaning: Option<String>,
+
+    /// Weight of the position in the checksum calculation
+    pub weight: u32,
+
+    /// Value of the symbol multiplied by the weight of the position
+    pub contribution: u32,
+}
+
+/// Holds position-by-position breakdown of the VIN (see [`VIN::explain`](../struct.VIN.html#method.explain))
+#[derive(Debug, Clone)]
+pub struct Explanation {
+    /// Explained VIN
+    pub vin: String,
+
+    /// Explanation of all 17 positions
+    pub positions: Vec<Position>,
+
+    /// Weighted sum of the symbols
+    pub sum: u32,
+
+    /// Check digit computed from the weighted sum
+    pub expected_check_digit: char,
+
+    /// Whether checksum of the VIN is valid, invalid or not applicable in the region
+    pub valid_checksum: ChecksumStatus,
+}
+
+impl VIN {
+    /// Returns position-by-position breakdown of the VIN: symbols, their roles, decoded
+    /// meanings and contributions into the checksum
+    pub fn explain(&self) -> Explanation {
+        let small = self.small_manufacturer();
+        let mut positions = Vec::with_capacity(17);
+        let mut sum = 0;
+        for (i, (byte, weight)) in self.as_bytes().iter().zip(dicts::WEIGHTS.iter()).enumerate() {
+            let contribution = u32::from(dicts::BYTE_VALUES[usize::from(*byte)]) * weight;
+            sum += contribution;
+            let role = match i {
+                0 => Role::Region,
+                1 => Role::Country,
+                2 => Role::Manufacturer,
+                3..=7 => Role::VehicleDescriptor,
+                8 => Role::CheckDigit,
+                9 => Role::ModelYear,
+                10 => Role::Plant,
+                11..=13 if small => Role::SmallManufacturer,
+                _ => Role::SerialNumber,
+            };
+            positions.push(Position { position: i + 1, symbol: *byte as char, role, meaning: None, weight: *weight, contribution });
+        }
+
+        let expected_check_digit = match sum % 11 {
+            10 => 'X',
+            x => (b'0' + x as u8) as char,
+        };
+        positions[0].meaning = self.region.clone();
+        positions[1].meaning = self.country.clone();
+        positions[2].meaning = match (&self.manufacturer, small) {
+            (Some(manufacturer), _) => Some(manufacturer.clone()),
+            (None, true) => Some("small manufacturer, see 12-14-th symbols".to_string()),
+            (None, false) => None,
+        };
+        positions[8].meaning = Some(match self.check_digit() == expected_check_digit {
+            true => "check digit, valid".to_string(),
+            false => format!("check digit, {} expected", expected_check_digit),
+        });
+        let years: Vec<String> = self.years().candidates.iter().map(u32::to_string).collect();
+        if !years.is_empty() {
+            positions[9].meaning = Some(years.join(" or "));
+        }
+
+        Explanation {
+            vin: self.as_str().to_string(),
+            positions,
+            sum,
+            expected_check_digit,
+            valid_checksum: self.valid_checksum,
+        }
+    }
+}
+
+impl Explanation {
+    /// Returns aligned text table of the positions with the checksum summary, e.g. for CLI output
+    ///
+    /// # Examples
+    /// ```
+    /// let table = vin::get_info("1M8GDM9AXKP042788").unwrap().explain().render_table();
+    /// let lines: Vec<&str> = table.lines().collect();
+    /// assert_eq!(lines[0], "Pos  Symbol  Role                Weight  Contribution  Meaning");
+    /// assert_eq!(lines[9], "  9  X       check digit              0             0  check digit, valid");
+    /// assert_eq!(lines.len(), 19);
+    /// ```
+    pub fn render_table(&self) -> String { self.render(|_, cell| cell) }
+
+    /// Returns the table of [`render_table`](#method.render_table) with ANSI colors: WMI, VDS and
+    /// VIS roles are colored differently, the check digit is green if valid and red otherwise
+    #[cfg(feature = "color")]
+    pub fn render_colored_table(&self) -> String {
+        self.render(|position, cell| {
+            let color = match position.role {
+                Role::Region | Role::Country | Role::Manufacturer => "36",
+                Role::VehicleDescriptor => "33",
+                Role::CheckDigit if position.symbol == self.expected_check_digit => "32",
+                Role::CheckDigit => "31",
+                _ => "35",
+            };
+            format!("\u{1b}[{}m{}\u{1b}[0m", color, cell)
+        })
+    }
+
+    /// Renders the table, passing the padded role cell of every position through `paint`
+    fn render<F>(&self, paint: F) -> String
+    where
+        F: Fn(&Position, String) -> String,
+    {
+        let role_width = self.positions.iter().map(|x| x.role.description().len()).max().unwrap_or(0).max(4);
+        let mut table = format!("Pos  Symbol  {:<width$}  Weight  Contribution  Meaning\n", "Role", width = role_width);
+        for position in &self.positions {
+            let role = paint(position, format!("{:<width$}", position.role.description(), width = role_width));
+            let line = format!(
+                "{:>3}  {:<6}  {}  {:>6}  {:>12}  {}",
+                position.position,
+                position.symbol,
+                role,
+                position.weight,
+                position.contribution,
+                position.meaning.as_deref().unwrap_or(""),
+            );
+            table.push_str(line.trim_end());
+            table.push('\n');
+        }
+        table.push_str(&format!("Sum: {}, expected check digit: {}\n", self.sum, self.expected_check_digit));
+        table
+    }
+}
@@ -0,0 +1,70 @@
+use crate::{ChecksumStatus, VIN};
+
+impl VIN {
+    /// Names of the fields returned by [`to_ordered_map`](#method.to_ordered_map), in their order,
+    /// which is kept stable (new fields are only appended), e.g. to be used as the CSV header
+    pub const FIELDS: [&'static str; 8] = [
+        "vin",
+        "wmi",
+        "manufacturer",
+        "country",
+        "region",
+        "valid_checksum",
+        "checksum_status",
+        "years",
+    ];
+
+    /// Returns fields of the VIN as strings in the order of [`FIELDS`](#associatedconstant.FIELDS),
+    /// unknown values are empty and the model years are separated by `;`
+    ///
+    /// # Examples
+    /// ```
+    /// let fields = vin::get_info("1M8GDM9AXKP042788").unwrap().to_ordered_map();
+    /// assert_eq!(fields[0], ("vin", "1M8GDM9AXKP042788".to_string()));
+    /// assert_eq!(fields[6], ("checksum_status", "valid".to_string()));
+    /// assert_eq!(fields[7], ("years", "1989;2019".to_string()));
+    /// ```
+    pub fn to_ordered_map(&self) -> Vec<(&'static str, String)> {
+        let years: Vec<String> = self.years().candidates.iter().map(u32::to_string).collect();
+        let values = [
+            self.as_str().to_string(),
+            self.wmi().to_string(),
+            self.manufacturer.clone().unwrap_or_default(),
+            self.country.clone().unwrap_or_default(),
+            self.region.clone().unwrap_or_default(),
+            self.valid_checksum.is_valid().to_string(),
+            match self.valid_checksum {
+                ChecksumStatus::Valid => "valid",
+                ChecksumStatus::Invalid(_) => "invalid",
+                _ => "not_applicable",
+            }.to_string(),
+            years.join(";"),
+        ];
+        VIN::FIELDS.iter().cloned().zip(values.iter().cloned()).collect()
+    }
+
+    /// Returns fields of the VIN (see [`to_ordered_map`](#method.to_ordered_map)) as a CSV
+    /// record without the line terminator, fields are quoted if needed
+    ///
+    /// # Examples
+    /// ```
+    /// let record = vin::get_info("WP0ZZZ99ZTS392124").unwrap().to_csv_record();
+    /// assert!(record.starts_with("WP0ZZZ99ZTS392124,WP0,Porsche car,Germany/West Germany,Europe,false,"));
+    /// ```
+    pub fn to_csv_record(&self) -> String {
+        self.to_ordered_map()
+            .iter()
+            .map(|(_, value)| csv_field(value))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+/// Encloses the field in double quotes if it contains commas, quotes or line breaks
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,79 @@
+//! Extraction of VINs from arbitrary text (emails, documents converted to text, etc.).
+//!
+//! # Examples
+//! ```
+//! let text = "Claim #42: vehicle 1M8GDM9AXKP042788 was damaged, see also WP0ZZZ99ZTS392124.";
+//! let found = vin::extract::find_all(text);
+//!
+//! assert_eq!(found.len(), 2);
+//! assert_eq!(found[0].vin, "1M8GDM9AXKP042788");
+//! assert_eq!(&text[found[0].start..found[0].end], "1M8GDM9AXKP042788");
+//! assert!(found[0].confidence > found[1].confidence);  // the second one has invalid checksum
+//! ```
+use crate::dicts::get_manufacturer;
+use crate::{check_validity, verify_checksum};
+
+/// Holds VIN found in the text
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedVin {
+    /// Found VIN (uppercased)
+    pub vin: String,
+
+    /// Byte offset of the VIN start in the text
+    pub start: usize,
+
+    /// Byte offset of the VIN end in the text (exclusive)
+    pub end: usize,
+
+    /// Whether checksum of the VIN is valid
+    pub valid_checksum: bool,
+
+    /// Confidence that the found sequence is a real VIN (0.5 - 1.0): structurally valid sequences
+    /// get 0.5, valid checksum adds 0.3, known manufacturer adds 0.2
+    pub confidence: f32,
+}
+
+/// Returns all structurally valid VINs found in the text, in order of appearance.
+///
+/// Only standalone sequences of 17 alphanumeric characters are considered, i.e. VINs glued
+/// to other letters or digits are not found.
+pub fn find_all(text: &str) -> Vec<ExtractedVin> {
+    let mut result = vec![];
+    let mut start = None;
+
+    for (i, ch) in text.char_indices().chain(std::iter::once((text.len(), ' '))) {
+        match (ch.is_ascii_alphanumeric(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(from)) => {
+                // non-ASCII letters glue to the sequence, making it not a VIN
+                if !ch.is_alphanumeric() {
+                    result.extend(candidate(text, from, i));
+                }
+                start = None;
+            }
+            _ => (),
+        }
+    }
+
+    result
+}
+
+fn candidate(text: &str, start: usize, end: usize) -> Option<ExtractedVin> {
+    if end - start != 17 || text[..start].chars().next_back().map_or(false, char::is_alphanumeric) {
+        return None;
+    }
+
+    let vin = text[start..end].to_uppercase();
+    check_validity(&vin).ok()?;
+
+    let valid_checksum = verify_checksum(&vin).is_ok();
+    let mut confidence = 0.5;
+    if valid_checksum {
+        confidence += 0.3;
+    }
+    if get_manufacturer(&vin[..3]).is_some() {
+        confidence += 0.2;
+    }
+
+    Some(ExtractedVin { vin, start, end, valid_checksum, confidence })
+}
@@ -0,0 +1,194 @@
+use std::fmt;
+
+use crate::{ChecksumStatus, ValidationReport, ValidationWarning, VINError};
+
+/// Describes how serious the finding is
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Severity {
+    /// VIN is malformed or must not be accepted
+    Error,
+
+    /// VIN is valid, but suspicious
+    Warning,
+
+    /// Informational note about the VIN
+    Info,
+}
+
+/// Machine-readable code of the finding
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FindingCode {
+    /// Length of the VIN is not 17 (`E_LENGTH`)
+    Length,
+
+    /// VIN contains invalid characters (`E_CHARS`)
+    Characters,
+
+    /// VIN contains letters I, O or Q rejected by the strict parser (`E_LETTERS`)
+    ForbiddenLetters,
+
+    /// Checksum is invalid in the region mandating the check digit (`E_CHECKSUM`)
+    Checksum,
+
+    /// Serial number of North American VIN is not numeric (`E_SERIAL` if rejected by
+    /// the validation profile, `W_SERIAL` otherwise)
+    NonNumericSerial,
+
+    /// Manufacturer is unknown (`W_UNKNOWN_WMI`)
+    UnknownWmi,
+
+    /// VIN is listed in the watchlist (`W_WATCHLISTED`)
+    Watchlisted,
+
+    /// Serial number consists of identical symbols (`W_IDENTICAL_SERIAL`)
+    IdenticalSerial,
+
+    /// Serial number consists of sequential digits (`W_SEQUENTIAL_SERIAL`)
+    SequentialSerial,
+
+    /// Checksum is invalid, but the region does not mandate the check digit (`I_CHECKSUM_NOT_APPLICABLE`)
+    ChecksumNotApplicable,
+
+    /// Violation of the custom rule (see [`rules`](rules/index.html)) with the identifier of the rule
+    Rule(&'static str),
+}
+
+/// Holds a single result of [`VinParser::validate_full`](struct.VinParser.html#method.validate_full)
+///
+/// # Examples
+/// ```
+/// use vin::{FindingCode, Severity};
+///
+/// let result = vin::VinParser::new().validate_full("1M8GDM9AXKP042789");
+/// let finding = &result.findings[0];
+/// assert_eq!((finding.severity, finding.code), (Severity::Error, FindingCode::Checksum));
+/// assert_eq!(finding.id(), "E_CHECKSUM");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    /// Severity of the finding
+    pub severity: Severity,
+
+    /// Machine-readable code of the finding
+    pub code: FindingCode,
+
+    /// Human-readable description of the finding (in English)
+    pub message: String,
+
+    // arguments of the localized message
+    #[cfg_attr(not(feature = "i18n-messages"), allow(dead_code))]
+    pub(in crate) args: Vec<(&'static str, String)>,
+}
+
+impl Finding {
+    fn new(severity: Severity, code: FindingCode, message: &str) -> Finding {
+        Finding { severity, code, message: message.to_string(), args: vec![] }
+    }
+
+    /// Creates finding of the custom rule with the given identifier, which should follow
+    /// the built-in ones (e.g. `E_MODEL_YEAR`)
+    pub fn rule(severity: Severity, id: &'static str, message: &str) -> Finding {
+        Finding::new(severity, FindingCode::Rule(id), message)
+    }
+
+    /// Returns stable identifier of the finding combining its severity and code, e.g. `W_UNKNOWN_WMI`
+    pub fn id(&self) -> &'static str {
+        match (self.severity, self.code) {
+            (_, FindingCode::Length) => "E_LENGTH",
+            (_, FindingCode::Characters) => "E_CHARS",
+            (_, FindingCode::ForbiddenLetters) => "E_LETTERS",
+            (_, FindingCode::Checksum) => "E_CHECKSUM",
+            (Severity::Error, FindingCode::NonNumericSerial) => "E_SERIAL",
+            (_, FindingCode::NonNumericSerial) => "W_SERIAL",
+            (_, FindingCode::UnknownWmi) => "W_UNKNOWN_WMI",
+            (_, FindingCode::Watchlisted) => "W_WATCHLISTED",
+            (_, FindingCode::IdenticalSerial) => "W_IDENTICAL_SERIAL",
+            (_, FindingCode::SequentialSerial) => "W_SEQUENTIAL_SERIAL",
+            (_, FindingCode::ChecksumNotApplicable) => "I_CHECKSUM_NOT_APPLICABLE",
+            (_, FindingCode::Rule(id)) => id,
+        }
+    }
+
+    /// Returns finding describing the error of VIN parsing
+    pub(in crate) fn from_error(err: &VINError) -> Finding {
+        let code = match err {
+            VINError::IncorrectLength => FindingCode::Length,
+            VINError::InvalidCharacters(_) => FindingCode::Characters,
+            VINError::ChecksumError(_) => FindingCode::Checksum,
+            VINError::NonNumericSerial(_) => FindingCode::NonNumericSerial,
+            VINError::ForbiddenLetters(_) => FindingCode::ForbiddenLetters,
+        };
+        Finding { severity: Severity::Error, code, message: err.english(), args: error_args(err) }
+    }
+
+    /// Returns findings describing the decoded VIN and results of optional checks
+    pub(in crate) fn from_report(report: &ValidationReport) -> Vec<Finding> {
+        let mut result = vec![];
+        match report.vin.valid_checksum {
+            ChecksumStatus::Invalid(info) => result.push(Finding::from_error(&VINError::ChecksumError(info))),
+            ChecksumStatus::NotApplicable => result.push(Finding::new(
+                Severity::Info,
+                FindingCode::ChecksumNotApplicable,
+                "Invalid checksum symbol on 9th place, but the check digit is not mandatory in the region.",
+            )),
+            _ => {}
+        }
+        if report.vin.manufacturer.is_none() {
+            result.push(Finding::new(Severity::Warning, FindingCode::UnknownWmi, "Unknown manufacturer."));
+        }
+        if report.watchlisted == Some(true) {
+            result.push(Finding::new(Severity::Warning, FindingCode::Watchlisted, "VIN is listed in the watchlist."));
+        }
+        if report.numeric_serial == Some(false) {
+            result.push(Finding::new(
+                Severity::Warning,
+                FindingCode::NonNumericSerial,
+                "Serial number of North American VIN is not numeric.",
+            ));
+        }
+        for warning in &report.warnings {
+            result.push(match warning {
+                ValidationWarning::IdenticalSerial =>
+                    Finding::new(Severity::Warning, FindingCode::IdenticalSerial, "Serial number consists of identical symbols."),
+                ValidationWarning::SequentialSerial =>
+                    Finding::new(Severity::Warning, FindingCode::SequentialSerial, "Serial number consists of sequential digits."),
+            });
+        }
+        result.extend(report.rule_findings.iter().cloned());
+        result
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        #[cfg(feature = "i18n-messages")]
+        if let Some(message) = crate::messages::render_global(&(self.id(), self.args.clone())) {
+            return write!(f, "{}: {}", self.id(), message);
+        }
+        write!(f, "{}: {}", self.id(), self.message)
+    }
+}
+
+#[cfg(feature = "i18n-messages")]
+fn error_args(err: &VINError) -> Vec<(&'static str, String)> { crate::messages::error_message(err).1 }
+
+#[cfg(not(feature = "i18n-messages"))]
+fn error_args(_: &VINError) -> Vec<(&'static str, String)> { vec![] }
+
+/// Holds results of [`VinParser::validate_full`](struct.VinParser.html#method.validate_full)
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct FullValidation {
+    /// Decoded VIN with results of optional checks, `None` if the VIN is rejected
+    pub report: Option<ValidationReport>,
+
+    /// Findings, from the most to the least severe
+    pub findings: Vec<Finding>,
+}
+
+impl FullValidation {
+    /// Returns whether there are no findings with [`Severity::Error`]
+    pub fn is_valid(&self) -> bool { self.findings.iter().all(|x| x.severity != Severity::Error) }
+}
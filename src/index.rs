@@ -0,0 +1,144 @@
+//! Memory-efficient sets of VINs for deduplication.
+//!
+//! Every VIN is packed into 11 bytes (17 base-33 digits take 86 bits). For really large jobs
+//! the set can work in the approximate mode: a bloom filter takes only a few bits per VIN,
+//! but may report some VINs as present when they are not.
+//!
+//! # Examples
+//! ```
+//! use vin::index::VinSet;
+//!
+//! let mut set = VinSet::new();
+//! assert!(set.insert("1M8GDM9AXKP042788").unwrap());
+//! assert!(!set.insert("1m8gdm9axkp042788").unwrap());  // duplicate
+//! assert!(set.contains("1M8GDM9AXKP042788"));
+//! assert!(!set.contains("WP0ZZZ99ZTS392124"));
+//!
+//! let mut approximate = VinSet::bloom(1_000_000, 0.001);
+//! approximate.insert("1M8GDM9AXKP042788").unwrap();
+//! assert!(approximate.contains("1M8GDM9AXKP042788"));
+//! ```
+use std::collections::HashSet;
+
+use crate::compact::{pack, to_bytes};
+use crate::parse::validated;
+use crate::VINError;
+
+/// Set of VINs, exact or approximate (see [module documentation](index.html))
+#[derive(Debug, Clone)]
+pub struct VinSet {
+    storage: Storage,
+    len: usize,
+}
+
+#[derive(Debug, Clone)]
+enum Storage {
+    Exact(HashSet<[u8; 11]>),
+    Bloom(Bloom),
+}
+
+impl Default for VinSet {
+    fn default() -> Self { VinSet { storage: Storage::Exact(HashSet::new()), len: 0 } }
+}
+
+impl VinSet {
+    /// Creates an empty exact set
+    pub fn new() -> VinSet { VinSet::default() }
+
+    /// Creates an empty approximate set sized for the expected count of VINs and the given
+    /// probability of false positives
+    pub fn bloom(expected: usize, false_positive_rate: f64) -> VinSet {
+        VinSet { storage: Storage::Bloom(Bloom::new(expected, false_positive_rate)), len: 0 }
+    }
+
+    /// Returns whether the set is approximate (bloom filter)
+    pub fn is_approximate(&self) -> bool { matches!(self.storage, Storage::Bloom(_)) }
+
+    /// Adds valid VIN to the set, returns whether it was not present before.
+    ///
+    /// Approximate set may falsely consider new VIN as already present.
+    pub fn insert(&mut self, vin: &str) -> Result<bool, VINError> {
+        let packed = packed(vin)?;
+        let inserted = match &mut self.storage {
+            Storage::Exact(set) => set.insert(to_bytes(packed)),
+            Storage::Bloom(bloom) => bloom.insert(packed),
+        };
+        if inserted {
+            self.len += 1;
+        }
+        Ok(inserted)
+    }
+
+    /// Returns whether the VIN is present in the set (invalid VINs are never present).
+    ///
+    /// Approximate set may return `true` for VINs which were never inserted.
+    pub fn contains(&self, vin: &str) -> bool {
+        let packed = match packed(vin) {
+            Ok(packed) => packed,
+            Err(_) => return false,
+        };
+        match &self.storage {
+            Storage::Exact(set) => set.contains(&to_bytes(packed)),
+            Storage::Bloom(bloom) => bloom.contains(packed),
+        }
+    }
+
+    /// Returns count of VINs in the set (count of successful insertions for approximate set)
+    pub fn len(&self) -> usize { self.len }
+
+    /// Returns whether the set is empty
+    pub fn is_empty(&self) -> bool { self.len == 0 }
+}
+
+fn packed(vin: &str) -> Result<u128, VINError> {
+    Ok(pack(validated(vin)?.as_bytes()))
+}
+
+#[derive(Debug, Clone)]
+struct Bloom {
+    bits: Vec<u64>,
+    bit_count: u64,
+    hash_count: u32,
+}
+
+impl Bloom {
+    fn new(expected: usize, false_positive_rate: f64) -> Bloom {
+        let expected = expected.max(1) as f64;
+        let rate = false_positive_rate.clamp(f64::MIN_POSITIVE, 0.5);
+        let ln2 = std::f64::consts::LN_2;
+
+        let bit_count = (-expected * rate.ln() / (ln2 * ln2)).ceil().max(64.0) as u64;
+        let hash_count = ((bit_count as f64 / expected) * ln2).round().clamp(1.0, 32.0) as u32;
+        Bloom { bits: vec![0; ((bit_count + 63) / 64) as usize], bit_count, hash_count }
+    }
+
+    fn positions(&self, packed: u128) -> impl Iterator<Item = u64> + '_ {
+        let first = mix(packed as u64 ^ mix((packed >> 64) as u64));
+        let second = mix(first ^ 0x9e37_79b9_7f4a_7c15) | 1;
+        (0..u64::from(self.hash_count))
+            .map(move |i| first.wrapping_add(i.wrapping_mul(second)) % self.bit_count)
+    }
+
+    fn insert(&mut self, packed: u128) -> bool {
+        let positions: Vec<u64> = self.positions(packed).collect();
+        let mut inserted = false;
+        for position in positions {
+            let (word, bit) = ((position / 64) as usize, 1u64 << (position % 64));
+            inserted |= self.bits[word] & bit == 0;
+            self.bits[word] |= bit;
+        }
+        inserted
+    }
+
+    fn contains(&self, packed: u128) -> bool {
+        self.positions(packed)
+            .all(|position| self.bits[(position / 64) as usize] & (1 << (position % 64)) != 0)
+    }
+}
+
+/// SplitMix64 finalizer
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
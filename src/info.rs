@@ -0,0 +1,79 @@
+use std::borrow::Cow;
+use std::convert::TryInto;
+
+use crate::dicts::{check_digit_mandatory, lookup_longest_names, region_name};
+use crate::parse::{ascii_bytes, check_digit_result, observe_checksum, scan, uppercase};
+use crate::{ChecksumStatus, VINError, YearWindow, VIN};
+
+/// Holds information about the vehicle borrowing the input and the built-in dictionary, so
+/// decoding of already canonical (uppercase) VINs does not allocate
+///
+/// Unlike [`get_info`](fn.get_info.html), the built-in dictionary and the standard validation
+/// profile are always used and the input is not normalized.
+///
+/// # Examples
+/// ```
+/// use vin::VinInfo;
+///
+/// let info = VinInfo::parse("WP0ZZZ99ZTS392124").unwrap();
+/// assert!(info.is_borrowed());
+/// assert_eq!(info.manufacturer, Some("Porsche car"));
+/// assert_eq!(info.region, Some("Europe"));
+///
+/// let info = VinInfo::parse("wp0zzz99zts392124").unwrap();
+/// assert!(!info.is_borrowed());
+/// assert_eq!(info.into_owned().as_str(), "WP0ZZZ99ZTS392124");
+/// ```
+#[derive(Debug, Clone)]
+pub struct VinInfo<'a> {
+    // Provided VIN number (validated, uppercased only if needed)
+    vin: Cow<'a, str>,
+
+    /// Country of the manufacturer, if known
+    pub country: Option<&'static str>,
+
+    /// Name of the manufacturer, if known
+    pub manufacturer: Option<&'static str>,
+
+    /// Region of the manufacturer, if known
+    pub region: Option<&'static str>,
+
+    /// Whether checksum of the VIN is valid, invalid or not applicable in the region
+    pub valid_checksum: ChecksumStatus,
+}
+
+impl<'a> VinInfo<'a> {
+    /// Returns basic information about manufacturer of the vehicle
+    pub fn parse(vin: &'a str) -> Result<VinInfo<'a>, VINError> {
+        let vin = uppercase(vin);
+        let bytes = ascii_bytes(&vin)?;
+        let contributions = scan(bytes)?;
+        let valid_checksum = match observe_checksum(&vin, || check_digit_result(bytes, contributions)) {
+            Err(VINError::ChecksumError(info)) if check_digit_mandatory(&vin[..1]) => ChecksumStatus::Invalid(info),
+            Err(_) => ChecksumStatus::NotApplicable,
+            Ok(()) => ChecksumStatus::Valid,
+        };
+        let (country, manufacturer) = lookup_longest_names(&vin).unwrap_or_default();
+        let region = region_name(&vin[..1]);
+        Ok(VinInfo { vin, country, manufacturer, region, valid_checksum })
+    }
+
+    /// Returns VIN as string
+    pub fn as_str(&self) -> &str { &self.vin }
+
+    /// Returns whether the VIN borrows the input
+    pub fn is_borrowed(&self) -> bool { matches!(self.vin, Cow::Borrowed(_)) }
+
+    /// Converts into the owned [`VIN`](struct.VIN.html)
+    pub fn into_owned(self) -> VIN {
+        VIN {
+            // validated VIN consists of 17 ASCII characters
+            vin: self.vin.as_bytes().try_into().unwrap_or([b'0'; 17]),
+            country: self.country.map(str::to_string),
+            manufacturer: self.manufacturer.map(str::to_string),
+            region: self.region.map(str::to_string),
+            valid_checksum: self.valid_checksum,
+            year_window: YearWindow::default(),
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use crate::{ChecksumStatus, VinInfo, YearWindow, VIN};
+
+/// Pool of manufacturer, country and region names shared by the decoded VINs, so storing
+/// millions of them keeps a single copy of every name
+///
+/// # Examples
+/// ```
+/// use std::sync::Arc;
+/// use vin::{Interner, VinInfo};
+///
+/// let interner = Interner::new();
+/// let first = interner.intern_vin(&vin::get_info("WP0ZZZ99ZTS392124").unwrap());
+/// let second = interner.intern_info(&VinInfo::parse("WP0ZZZ99ZTS392125").unwrap());
+/// assert_eq!(first.manufacturer.as_deref(), Some("Porsche car"));
+/// assert!(Arc::ptr_eq(first.manufacturer.as_ref().unwrap(), second.manufacturer.as_ref().unwrap()));
+/// assert_eq!(interner.len(), 3);
+/// ```
+#[derive(Debug, Default)]
+pub struct Interner {
+    names: Mutex<HashSet<Arc<str>>>,
+}
+
+impl Interner {
+    /// Creates empty pool
+    pub fn new() -> Interner { Interner::default() }
+
+    /// Returns shared copy of the name, allocated only on the first occurrence
+    pub fn intern(&self, name: &str) -> Arc<str> {
+        let mut names = match self.names.lock() {
+            Ok(names) => names,
+            Err(_) => return Arc::from(name),
+        };
+        if let Some(shared) = names.get(name) {
+            return shared.clone();
+        }
+        let shared: Arc<str> = Arc::from(name);
+        names.insert(shared.clone());
+        shared
+    }
+
+    /// Returns count of distinct names in the pool
+    pub fn len(&self) -> usize { self.names.lock().map_or(0, |x| x.len()) }
+
+    /// Returns whether the pool is empty
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Removes names, which are not used by any decoded VIN anymore
+    pub fn shrink(&self) {
+        if let Ok(mut names) = self.names.lock() {
+            names.retain(|x| Arc::strong_count(x) > 1);
+        }
+    }
+
+    /// Returns decoded VIN with the shared names
+    pub fn intern_vin(&self, vin: &VIN) -> InternedVin {
+        InternedVin {
+            vin: vin.vin,
+            country: vin.country.as_deref().map(|x| self.intern(x)),
+            manufacturer: vin.manufacturer.as_deref().map(|x| self.intern(x)),
+            region: vin.region.as_deref().map(|x| self.intern(x)),
+            valid_checksum: vin.valid_checksum,
+            year_window: vin.year_window,
+        }
+    }
+
+    /// Returns decoded VIN with the shared names without allocating for the known names
+    pub fn intern_info(&self, info: &VinInfo) -> InternedVin {
+        let mut vin = [b'0'; 17];
+        // validated VIN consists of 17 ASCII characters
+        vin.copy_from_slice(info.as_str().as_bytes());
+        InternedVin {
+            vin,
+            country: info.country.map(|x| self.intern(x)),
+            manufacturer: info.manufacturer.map(|x| self.intern(x)),
+            region: info.region.map(|x| self.intern(x)),
+            valid_checksum: info.valid_checksum,
+            year_window: YearWindow::default(),
+        }
+    }
+}
+
+/// Holds parsed information about the vehicle with the names shared through [`Interner`]
+#[derive(Clone)]
+pub struct InternedVin {
+    // Copy of provided VIN number (validated and uppercased)
+    vin: [u8; 17],
+
+    /// Country of the manufacturer, if known
+    pub country: Option<Arc<str>>,
+
+    /// Name of the manufacturer, if known
+    pub manufacturer: Option<Arc<str>>,
+
+    /// Region of the manufacturer, if known
+    pub region: Option<Arc<str>>,
+
+    /// Whether checksum of the VIN is valid, invalid or not applicable in the region
+    pub valid_checksum: ChecksumStatus,
+
+    // Window of model years of the parser which decoded the VIN
+    year_window: YearWindow,
+}
+
+impl InternedVin {
+    /// Returns VIN as bytes (always 17 uppercase ASCII characters)
+    pub fn as_bytes(&self) -> &[u8; 17] { &self.vin }
+
+    /// Returns VIN as string
+    pub fn as_str(&self) -> &str {
+        // VIN is validated on construction to contain only ASCII characters
+        std::str::from_utf8(&self.vin).unwrap_or("")
+    }
+
+    /// Converts into the [`VIN`](struct.VIN.html) owning copies of the names
+    pub fn to_vin(&self) -> VIN {
+        VIN {
+            vin: self.vin,
+            country: self.country.as_deref().map(str::to_string),
+            manufacturer: self.manufacturer.as_deref().map(str::to_string),
+            region: self.region.as_deref().map(str::to_string),
+            valid_checksum: self.valid_checksum,
+            year_window: self.year_window,
+        }
+    }
+}
+
+impl fmt::Debug for InternedVin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("InternedVin")
+            .field("vin", &self.as_str())
+            .field("country", &self.country)
+            .field("manufacturer", &self.manufacturer)
+            .field("region", &self.region)
+            .field("valid_checksum", &self.valid_checksum)
+            .finish()
+    }
+}
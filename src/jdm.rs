@@ -0,0 +1,77 @@
+//! Detection of Japanese domestic market frame numbers.
+//!
+//! Vehicles built for the Japanese market are identified by a frame number (車台番号)
+//! instead of the VIN: the model code followed by a dash and the serial number, e.g. `ZN6-012345`.
+//! Imported vehicles often carry only the frame number, so it has to be handled separately.
+//!
+//! # Examples
+//! ```
+//! use vin::jdm::{detect, Identifier};
+//!
+//! if let Identifier::FrameNumber(frame) = detect("zn6-012345").unwrap() {
+//!     assert_eq!(frame.model_code, "ZN6");
+//!     assert_eq!(frame.serial, "012345");
+//! } else {
+//!     unreachable!();
+//! }
+//! assert!(matches!(detect("WP0ZZZ99ZTS392124"), Ok(Identifier::Vin(_))));
+//! assert!(detect("ZN6012345").is_err());
+//! ```
+use std::fmt;
+
+use crate::{get_info, VINError, VIN};
+
+/// Holds the Japanese domestic frame number
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FrameNumber {
+    /// Model code (uppercased), e.g. `ZN6` or `JZA80`
+    pub model_code: String,
+
+    /// Serial number, e.g. `012345`
+    pub serial: String,
+}
+
+impl fmt::Display for FrameNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}", self.model_code, self.serial)
+    }
+}
+
+/// Describes the detected kind of the vehicle identifier
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum Identifier {
+    /// Valid VIN
+    Vin(VIN),
+
+    /// Japanese domestic frame number
+    FrameNumber(FrameNumber),
+}
+
+/// Returns the decoded VIN or the frame number, if the input has incorrect length for the VIN
+/// but looks like the frame number. Other errors of the VIN validation are returned as is.
+pub fn detect(input: &str) -> Result<Identifier, VINError> {
+    match get_info(input) {
+        Ok(vin) => Ok(Identifier::Vin(vin)),
+        Err(VINError::IncorrectLength) =>
+            parse_frame_number(input).map(Identifier::FrameNumber).ok_or(VINError::IncorrectLength),
+        Err(err) => Err(err),
+    }
+}
+
+/// Returns the frame number if the input consists of the model code (2-7 alphanumeric symbols
+/// starting with a letter), dash and 5-7 digits of the serial number
+fn parse_frame_number(input: &str) -> Option<FrameNumber> {
+    let (model_code, serial) = input.trim().split_once('-')?;
+
+    let valid_model = (2..=7).contains(&model_code.len())
+        && model_code.starts_with(|x: char| x.is_ascii_alphabetic())
+        && model_code.chars().all(|x| x.is_ascii_alphanumeric());
+    let valid_serial = (5..=7).contains(&serial.len()) && serial.chars().all(|x| x.is_ascii_digit());
+
+    if valid_model && valid_serial {
+        Some(FrameNumber { model_code: model_code.to_ascii_uppercase(), serial: serial.to_owned() })
+    } else {
+        None
+    }
+}
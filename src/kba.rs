@@ -0,0 +1,37 @@
+//! Mapping of VINs to German KBA (Kraftfahrt-Bundesamt) type codes.
+//!
+//! German registration documents and insurers identify vehicles by HSN (manufacturer key,
+//! 4 digits) and TSN (type key, 3 characters). HSN is derived from the WMI, TSN is known only
+//! for the VIN prefixes listed in `data/kba.csv`.
+//!
+//! # Examples
+//! ```
+//! let result = vin::get_info("WP0ZZZ99ZTS392124").unwrap();
+//! let codes = result.kba_codes().unwrap();
+//! assert_eq!(codes.hsn, "0583");
+//! assert_eq!(codes.tsn, None);
+//! ```
+use crate::dicts::KBA_TABLE;
+use crate::VIN;
+
+/// Holds KBA type codes of the vehicle
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct KbaCodes {
+    /// Manufacturer key (Herstellerschlüsselnummer)
+    pub hsn: &'static str,
+
+    /// Type key (Typschlüsselnummer), if it can be derived from the VIN
+    pub tsn: Option<&'static str>,
+}
+
+impl VIN {
+    /// Returns KBA codes of the vehicle, if the manufacturer is known to the KBA table
+    pub fn kba_codes(&self) -> Option<KbaCodes> {
+        let vin = self.as_str();
+        KBA_TABLE
+            .iter()
+            .filter(|(prefix, _, _)| vin.starts_with(prefix))
+            .max_by_key(|(prefix, _, _)| prefix.len())
+            .map(|(_, hsn, tsn)| KbaCodes { hsn, tsn: if tsn.is_empty() { None } else { Some(tsn) } })
+    }
+}
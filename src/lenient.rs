@@ -0,0 +1,56 @@
+use crate::{config, VINError, VinParser, VIN};
+
+/// Holds VIN decoded by [`VinParser::parse_lenient`] together with the stripped formatting
+///
+/// # Examples
+/// ```
+/// let result = vin::VinParser::new().parse_lenient(" WP0 ZZZ 99Z TS 392124\n").unwrap();
+/// assert_eq!(result.vin.as_str(), "WP0ZZZ99ZTS392124");
+/// assert_eq!(result.stripped[..2], [(0, ' '), (4, ' ')]);
+/// assert!(result.is_formatted());
+/// ```
+#[derive(Debug, Clone)]
+pub struct LenientVin {
+    /// Decoded VIN
+    pub vin: VIN,
+
+    /// Stripped characters with their positions (0-based, in characters) in the input
+    pub stripped: Vec<(usize, char)>,
+}
+
+impl LenientVin {
+    /// Returns whether the input contained any formatting
+    pub fn is_formatted(&self) -> bool { !self.stripped.is_empty() }
+}
+
+/// Returns whether the character is formatting tolerated by the lenient parsing
+fn is_formatting(ch: char) -> bool { ch.is_whitespace() || ch == '-' || ch == '.' }
+
+impl VinParser {
+    /// Parses VIN as [`parse`](#method.parse) does, after removing whitespaces, hyphens and dots
+    /// anywhere in the input (e.g. `WP0-ZZZ-99Z-TS-392124` copied from a document)
+    pub fn parse_lenient(&self, vin: &str) -> Result<LenientVin, VINError> {
+        let mut stripped = vec![];
+        let mut cleaned = String::with_capacity(vin.len());
+        for (position, ch) in vin.chars().enumerate() {
+            match is_formatting(ch) {
+                true => stripped.push((position, ch)),
+                false => cleaned.push(ch),
+            }
+        }
+        Ok(LenientVin { vin: self.parse(&cleaned)?, stripped })
+    }
+}
+
+/// Parses VIN with the parser set by [`config::configure`](crate::config::configure), tolerating
+/// formatting of the input (see [`VinParser::parse_lenient`])
+///
+/// # Examples
+/// ```
+/// let result = vin::parse_lenient("1M8.GDM9A.XKP.042788").unwrap();
+/// assert_eq!(result.vin.as_str(), "1M8GDM9AXKP042788");
+/// assert_eq!(result.stripped.len(), 3);
+/// ```
+pub fn parse_lenient(vin: &str) -> Result<LenientVin, VINError> {
+    config::parser().parse_lenient(vin)
+}
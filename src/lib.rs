@@ -22,23 +22,120 @@
 //! // Get VIN information
 //! let vin_number = "wp0zzz998ts392124";
 //! let result = vin::get_info(vin_number).unwrap();
-//! assert_eq!(result.vin, vin_number.to_uppercase());
-//! assert_eq!(result.country, "Germany/West Germany");
-//! assert_eq!(result.manufacturer, "Porsche car");
-//! assert_eq!(result.region, "Europe");
-//! assert!(result.valid_checksum.is_ok());
+//! assert_eq!(result.as_str(), vin_number.to_uppercase());
+//! assert_eq!(result.country.unwrap(), "Germany/West Germany");
+//! assert_eq!(result.manufacturer.unwrap(), "Porsche car");
+//! assert_eq!(result.region.unwrap(), "Europe");
+//! assert!(result.valid_checksum.is_valid());
 //! ```
-#[macro_use]
-extern crate lazy_static;
-
-use std::collections::HashSet;
+//!
+//! # Thread safety
+//! All public types are `Send` and `Sync`, so parsers (including their caches, sources, watchlists
+//! and rules) may be shared between threads, e.g. in `Arc`. Built-in dictionaries are initialized
+//! once on the first use, concurrent first uses wait for the single initialization.
+//!
+//! # Minimum supported Rust version
+//! The crate with default features builds on Rust 1.65 (`rust-version` in `Cargo.toml`), std
+//! features of newer toolchains are used only when the compiler supports them. Optional features
+//! integrating other crates follow the minimum versions of those crates. Raising the minimum
+//! version is considered a breaking change.
 use std::fmt;
-use std::time::SystemTime;
-
-use crate::VINError::{ChecksumError, IncorrectLength, InvalidCharacters};
-use crate::dicts::{get_region, get_country, get_manufacturer};
 
-mod dicts;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+mod assemble;
+pub mod barcode;
+pub mod batch;
+pub mod body;
+mod cache;
+mod cached;
+mod compact;
+pub mod config;
+mod country;
+pub mod coverage;
+pub mod data;
+pub mod dicts;
+mod diff;
+pub mod engine;
+pub mod ev;
+pub mod explain;
+mod export;
+pub mod extract;
+mod finding;
+pub mod index;
+mod info;
+mod intern;
+pub mod jdm;
+#[cfg(feature = "kba")]
+pub mod kba;
+mod lenient;
+mod manufacturer;
+mod masking;
+pub mod market;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+#[cfg(feature = "i18n-messages")]
+pub mod messages;
+#[cfg(any(feature = "metrics", feature = "tracing"))]
+mod observe;
+pub mod ocr;
+pub mod parse;
+mod parser;
+pub mod partial;
+pub mod patterns;
+pub mod pickup;
+#[cfg(feature = "polars")]
+pub mod polars;
+#[cfg(feature = "proto")]
+pub mod proto;
+pub mod recalls;
+pub mod registry;
+pub mod report;
+#[cfg(feature = "json")]
+pub mod response;
+pub mod restraint;
+pub mod rules;
+pub mod source;
+#[cfg(any(feature = "diesel", feature = "sqlx"))]
+mod sql;
+#[cfg(feature = "serde")]
+mod serialize;
+#[cfg(feature = "service")]
+pub mod service;
+mod squish;
+pub mod stats;
+mod sync;
+pub mod tables;
+pub mod testdata;
+#[cfg(feature = "proptest")]
+pub mod testing;
+#[cfg(feature = "validator")]
+pub mod validation;
+pub mod watchlist;
+mod years;
+
+// Free functions of 1.x API, kept at the crate root for compatibility
+#[doc(inline)]
+pub use crate::parse::{
+    check_validity, compute_check_digit, detect_transposition, get_info, transposition_candidates, verify_checksum,
+};
+
+pub use crate::assemble::{VinComponents, VinParts};
+pub use crate::cached::CachedParser;
+pub use crate::config::{configure, GlobalConfig};
+pub use crate::country::{all_regions, countries_in, wmis_in_country, Country, Region};
+pub use crate::diff::{diff, Attribute, AttributeDiff, SymbolDiff, VinDiff};
+pub use crate::finding::{Finding, FindingCode, FullValidation, Severity};
+pub use crate::info::VinInfo;
+pub use crate::intern::{InternedVin, Interner};
+pub use crate::lenient::{parse_lenient, LenientVin};
+pub use crate::manufacturer::{
+    brand_aliases, canonical_brand, group_of, search_manufacturers, wmis_for_manufacturer, Manufacturer,
+};
+pub use crate::masking::{mask, MaskPolicy};
+pub use crate::parser::{ValidationReport, ValidationWarning, VinParser};
+pub use crate::squish::SquishVin;
+pub use crate::years::{year_codes, Clock, DecadeHint, ModelYearEstimate, SystemClock, YearHints, YearMethod, YearPolicy, YearWindow};
 
 
 /// Provides information about invalid checksum calculation from the VIN
@@ -49,64 +146,301 @@ pub struct ChecksumErrorInfo {
 
     /// Received symbol at the 9-th place
     pub received: char,
+
+    /// Computed weighted sum of the VIN symbols
+    pub sum: u32,
+
+    /// Contributions of every position into the weighted sum (value of symbol multiplied by the
+    /// weight of the position, the 9-th position is always 0)
+    pub contributions: [u32; 17],
+}
+
+impl ChecksumErrorInfo {
+    /// Returns positions (0-based) and replacement symbols, any of which would make the checksum
+    /// valid if the other symbols are correct
+    ///
+    /// # Examples
+    /// ```
+    /// let info = match vin::verify_checksum("1M8GDM9AXKP042789") {
+    ///     Err(vin::VINError::ChecksumError(info)) => info,
+    ///     _ => unreachable!(),
+    /// };
+    /// let candidates = info.candidates();
+    /// assert!(candidates.contains(&(16, vec!['8', 'H', 'Y'])));
+    /// assert!(candidates.contains(&(8, vec![info.expected])));
+    /// ```
+    pub fn candidates(&self) -> Vec<(usize, Vec<char>)> {
+        let mut result = vec![];
+        let target = match self.received {
+            'X' => Some(10),
+            x => x.to_digit(10),
+        };
+
+        for (i, weight) in dicts::WEIGHTS.iter().enumerate() {
+            if i == 8 {
+                result.push((8, vec![self.expected]));
+                continue;
+            }
+
+            let target = match target {
+                Some(target) => target,
+                None => continue,
+            };
+            let current = self.contributions[i] / weight;
+            let rest = self.sum - self.contributions[i];
+            let mut chars: Vec<char> = tables::CHAR_VALUES
+                .iter()
+                .filter(|(_, value)| *value != current && (rest + *value * weight) % 11 == target)
+                .map(|(ch, _)| *ch)
+                .collect();
+            if !chars.is_empty() {
+                chars.sort_unstable();
+                result.push((i, chars));
+            }
+        }
+
+        result
+    }
+
+    /// Returns position (0-based) of the symbol which is most likely wrong.
+    ///
+    /// The check digit itself is blamed if it can not be a check digit at all, otherwise
+    /// the only position other than the check digit, which single change satisfies the checksum.
+    /// `None` is returned if several positions are equally likely.
+    ///
+    /// # Examples
+    /// ```
+    /// let info = match vin::verify_checksum("WP0ZZZ99ZTS392124") {
+    ///     Err(vin::VINError::ChecksumError(info)) => info,
+    ///     _ => unreachable!(),
+    /// };
+    /// assert_eq!(info.most_likely_wrong_position(), Some(8));
+    /// ```
+    pub fn most_likely_wrong_position(&self) -> Option<usize> {
+        if self.received != 'X' && !self.received.is_ascii_digit() {
+            return Some(8);
+        }
+
+        let positions: Vec<usize> = self.candidates()
+            .into_iter()
+            .map(|(i, _)| i)
+            .filter(|i| *i != 8)
+            .collect();
+        match positions.as_slice() {
+            [position] => Some(*position),
+            _ => None,
+        }
+    }
+}
+
+/// Describes result of the checksum validation of the decoded VIN
+///
+/// # Examples
+/// ```
+/// use vin::ChecksumStatus;
+///
+/// assert!(vin::get_info("1M8GDM9AXKP042788").unwrap().valid_checksum.is_valid());
+/// assert!(vin::get_info("1M8GDM9AXKP042789").unwrap().valid_checksum.is_invalid());
+///
+/// // check digit is not mandatory in Europe
+/// let result = vin::get_info("WP0ZZZ99ZTS392124").unwrap();
+/// assert!(matches!(result.valid_checksum, ChecksumStatus::NotApplicable));
+/// ```
+#[derive(Debug, Copy, Clone)]
+#[non_exhaustive]
+pub enum ChecksumStatus {
+    /// Checksum is valid
+    Valid,
+
+    /// Checksum is invalid in the region mandating the check digit
+    Invalid(ChecksumErrorInfo),
+
+    /// Checksum is invalid, but the region does not mandate the check digit
+    NotApplicable,
+}
+
+impl ChecksumStatus {
+    /// Returns whether checksum is valid
+    pub fn is_valid(&self) -> bool { matches!(self, ChecksumStatus::Valid) }
+
+    /// Returns whether checksum is invalid in the region mandating the check digit
+    pub fn is_invalid(&self) -> bool { matches!(self, ChecksumStatus::Invalid(_)) }
+
+    /// Returns information about invalid checksum in the region mandating the check digit
+    pub fn error(&self) -> Option<ChecksumErrorInfo> {
+        match self {
+            ChecksumStatus::Invalid(info) => Some(*info),
+            _ => None,
+        }
+    }
+}
+
+/// Describes invalid character of the VIN reported by [`VINError::InvalidCharacters`]
+///
+/// # Examples
+/// ```
+/// let chars = match vin::check_validity("wp0zzz99zts39212o") {
+///     Err(vin::VINError::InvalidCharacters(chars)) => chars,
+///     _ => unreachable!(),
+/// };
+/// assert_eq!(chars, [vin::InvalidChar { position: 16, character: 'O', confusable: true }]);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct InvalidChar {
+    /// Position (0-based) of the character in the checked string
+    pub position: usize,
+
+    /// The character (uppercased)
+    pub character: char,
+
+    /// Whether the character is one of I, O and Q, which are excluded from VINs as easily
+    /// confused with 1 and 0
+    pub confusable: bool,
+}
+
+impl InvalidChar {
+    pub(in crate) fn new(position: usize, character: char) -> InvalidChar {
+        InvalidChar { position, character, confusable: matches!(character, 'I' | 'O' | 'Q') }
+    }
+
+    /// Returns digit similar to the confusable letter (`1` for I, `0` for O and Q)
+    pub fn suggestion(&self) -> Option<char> {
+        match self.character {
+            'I' => Some('1'),
+            'O' | 'Q' => Some('0'),
+            _ => None,
+        }
+    }
+
+    /// Returns invalid characters of the uppercased string, skipping the given positions
+    pub(in crate) fn find(value: &str, skip: &[usize]) -> Vec<InvalidChar> {
+        value
+            .chars()
+            .enumerate()
+            .filter(|(i, x)| !skip.contains(i) && !tables::is_allowed(*x))
+            .map(|(position, character)| InvalidChar::new(position, character))
+            .collect()
+    }
 }
 
 /// Provides possible errors during VIN parsing
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum VINError {
     /// Provided number length != 17
     IncorrectLength,
 
-    /// Provided number contains invalid characters
-    InvalidCharacters(HashSet<char>),
+    /// Provided number contains invalid characters (in the order of their positions)
+    InvalidCharacters(Vec<InvalidChar>),
 
     /// Provided number did not pass checksum validation (notice, that only North American VINs
     /// must pass this validation, for others it is not obligatory)
     ChecksumError(ChecksumErrorInfo),
+
+    /// Serial number of North American VIN contains non-numeric symbol (contains its 1-based
+    /// position), rejected by [`ValidationProfile::NorthAmerica`](config/enum.ValidationProfile.html#variant.NorthAmerica)
+    NonNumericSerial(usize),
+
+    /// Provided number contains only letters I, O and Q among invalid characters, reported instead
+    /// of [`InvalidCharacters`](#variant.InvalidCharacters) by the parser with
+    /// [`VinParser::with_forbidden_letters`](struct.VinParser.html#method.with_forbidden_letters)
+    ForbiddenLetters(Vec<InvalidChar>),
 }
 
 impl fmt::Display for VINError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        #[cfg(feature = "i18n-messages")]
+        if let Some(message) = messages::render_global(&messages::error_message(self)) {
+            return write!(f, "{}", message);
+        }
+        write!(f, "{}", self.english())
+    }
+}
+
+impl VINError {
+    /// Returns English message, even if the process-wide message catalog is set
+    pub(in crate) fn english(&self) -> String {
         match self {
             VINError::IncorrectLength =>
-                write!(f, "Incorrect length of given string, 17 chars expected."),
-            VINError::InvalidCharacters(chars) =>
-                write!(f, "Invalid characters received in given string: {:?}.", chars),
+                "Incorrect length of given string, 17 chars expected.".to_string(),
+            VINError::InvalidCharacters(chars) => {
+                let chars: Vec<String> = chars
+                    .iter()
+                    .map(|x| format!("{} (position {})", x.character, x.position + 1))
+                    .collect();
+                format!("Invalid characters received in given string: {}.", chars.join(", "))
+            }
             VINError::ChecksumError(err) =>
-                write!(f, "Invalid checksum symbol on 9th place, {} expected, {} received.", err.expected, err.received),
+                format!("Invalid checksum symbol on 9th place, {} expected, {} received.", err.expected, err.received),
+            VINError::NonNumericSerial(position) =>
+                format!("Non-numeric symbol of the serial number on {}th place.", position),
+            VINError::ForbiddenLetters(letters) => {
+                let letters: Vec<String> = letters
+                    .iter()
+                    .map(|x| format!("{} (position {}, did you mean {}?)", x.character, x.position + 1, x.suggestion().unwrap_or('0')))
+                    .collect();
+                format!("Letters I, O and Q are not allowed in VIN: {}.", letters.join(", "))
+            }
         }
     }
 }
 
+impl std::error::Error for VINError {}
+
 /// Holds parsed information about the vehicle
-#[derive(Debug, Clone)]
+#[derive(Clone)]
+#[cfg_attr(feature = "diesel", derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow))]
+#[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::Text))]
 pub struct VIN {
-    /// Copy of provided VIN number
-    pub vin: String,
+    // Copy of provided VIN number (validated and uppercased)
+    vin: [u8; 17],
 
-    /// Country of the manufacturer
-    pub country: String,
+    /// Country of the manufacturer, if known (see also [`country`](#method.country) for
+    /// the structured country of the built-in dictionary)
+    pub country: Option<String>,
 
-    /// Name of the manufacturer
-    pub manufacturer: String,
+    /// Name of the manufacturer, if known
+    pub manufacturer: Option<String>,
 
-    /// Region of the manufacturer
-    pub region: String,
+    /// Region of the manufacturer, if known
+    pub region: Option<String>,
 
-    /// Whether checksum of the VIN is valid
-    pub valid_checksum: Result<(), ChecksumErrorInfo>,
+    /// Whether checksum of the VIN is valid, invalid or not applicable in the region
+    pub valid_checksum: ChecksumStatus,
+
+    // Window of model years of the parser which decoded the VIN
+    year_window: YearWindow,
 }
 
 
 impl VIN {
+    /// Returns VIN as bytes (always 17 uppercase ASCII characters)
+    pub fn as_bytes(&self) -> &[u8; 17] { &self.vin }
+
+    /// Returns VIN as string
+    ///
+    /// # Examples
+    /// ```
+    /// let result = vin::get_info("wp0zzz99zts392124").unwrap();
+    /// assert_eq!(result.as_ascii_str(), "WP0ZZZ99ZTS392124");
+    /// assert_eq!(result.as_bytes()[..3], *b"WP0");
+    /// ```
+    pub fn as_ascii_str(&self) -> &str {
+        // VIN is validated on construction to contain only ASCII characters
+        std::str::from_utf8(&self.vin).unwrap_or("")
+    }
+
+    /// Returns VIN as string (same as [`as_ascii_str`](#method.as_ascii_str))
+    pub fn as_str(&self) -> &str { self.as_ascii_str() }
+
     /// Returns WMI part of VIN
-    pub fn wmi(&self) -> &str { &self.vin[..3] }
+    pub fn wmi(&self) -> &str { &self.as_ascii_str()[..3] }
 
     /// Returns VDS part of VIN
-    pub fn vds(&self) -> &str { &self.vin[3..9] }
+    pub fn vds(&self) -> &str { &self.as_ascii_str()[3..9] }
 
     /// Returns VIS part of VIN
-    pub fn vis(&self) -> &str { &self.vin[9..] }
+    pub fn vis(&self) -> &str { &self.as_ascii_str()[9..] }
 
     /// Returns whether manufacturer is small and does not have its own ID in VIN
     pub fn small_manufacturer(&self) -> bool { &self.wmi()[2..] == "9" }
@@ -117,138 +451,60 @@ impl VIN {
     /// Returns country VIN code
     pub fn country_code(&self) -> &str { &self.wmi()[1..] }
 
-    /// Returns possible years of assembling
-    pub fn years(&self) -> Vec<u32> {
-        let letters = "ABCDEFGHJKLMNPRSTVWXY123456789";
-        let year_letter = &self.vis().chars().nth(0).unwrap();
-
-        let mut year: u32 = 1979;
-        let cur_year = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .unwrap()
-            .as_secs_f64();
-        let cur_year = cur_year / 3600.0 / 24.0 / 365.25 + 1970.0;  // get year
-        let cur_year = (cur_year.round() + 2.0) as u32;             // add 2 years in advance
-
-        let mut result = vec![];
-        for letter in letters.chars().cycle() {
-            year += 1;
-
-            if letter == *year_letter {
-                result.push(year);
-            }
-
-            if year == cur_year { break; }
+    /// Returns check digit (the 9-th symbol)
+    ///
+    /// # Examples
+    /// ```
+    /// let result = vin::get_info("1M8GDM9AXKP042788").unwrap();
+    /// assert_eq!(result.check_digit(), 'X');
+    /// assert!(result.is_checksum_applicable());
+    /// assert!(!vin::get_info("WP0ZZZ99ZTS392124").unwrap().is_checksum_applicable());
+    /// ```
+    pub fn check_digit(&self) -> char { self.vin[8] as char }
+
+    /// Returns whether the region of the VIN mandates the check digit
+    pub fn is_checksum_applicable(&self) -> bool { dicts::check_digit_mandatory(self.region_code()) }
+
+    /// Returns whether the serial number (13-17-th symbols, or 15-17-th ones for small
+    /// manufacturers) is numeric, as required for North American VINs, `None` for other regions
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(vin::get_info("1M8GDM9AXKP042788").unwrap().has_numeric_serial(), Some(true));
+    /// assert_eq!(vin::get_info("1M8GDM9AXKP04Z788").unwrap().has_numeric_serial(), Some(false));
+    /// assert_eq!(vin::get_info("WP0ZZZ99ZTS392124").unwrap().has_numeric_serial(), None);
+    /// ```
+    pub fn has_numeric_serial(&self) -> Option<bool> {
+        match self.vin[0] {
+            b'1'..=b'5' => Some(parse::non_numeric_serial(&self.vin).is_none()),
+            _ => None,
         }
-
-        result
     }
 }
 
-
-/// Validates Vehicle Identification Number without computing the checksum
-/// (check used symbols and length of the number)
-///
-/// # Examples
-/// ```
-/// let vin_number = "WP0ZZZ99ZTS392124";
-/// assert!(vin::check_validity(vin_number).is_ok());
-///
-/// let vin_number = "W$0ZZZ99ZTS392124";  // notice $ sign on 2-nd place
-/// assert!(vin::check_validity(vin_number).is_err())
-/// ```
-pub fn check_validity(vin: &str) -> Result<(), VINError> {
-    let vin = vin.to_uppercase();
-
-    // check length
-    if vin.chars().count() != 17 {
-        return Err(IncorrectLength);
-    }
-
-    // check alphabet
-    let used_chars: HashSet<char> = vin.chars().collect();
-    let odd_chars: HashSet<char> = used_chars.difference(&dicts::ALLOWED_CHARS).cloned().collect();
-    if odd_chars.len() > 0 {
-        return Err(InvalidCharacters(odd_chars));
+impl fmt::Debug for VIN {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("VIN")
+            .field("vin", &self.as_ascii_str())
+            .field("country", &self.country)
+            .field("manufacturer", &self.manufacturer)
+            .field("region", &self.region)
+            .field("valid_checksum", &self.valid_checksum)
+            .finish()
     }
-
-    Ok(())
 }
 
 
-/// Validates Vehicle Identification Number AND validates the checksum
+/// Returns version of the embedded dataset of regions, countries and manufacturers
 ///
 /// # Examples
 /// ```
-/// let vin_number = "1M8GDM9AXKP042788";
-/// assert!(vin::verify_checksum(vin_number).is_ok());
-///
-/// let vin_number = "WP0ZZZ99ZTS392124";
-/// assert!(match vin::verify_checksum(vin_number) {
-///     Err(vin::VINError::ChecksumError(vin::ChecksumErrorInfo {
-///         expected: '8',
-///         received: 'Z',
-///     })) => true,
-///     _ => false,
-/// })
+/// let version = vin::data_version();
+/// assert_eq!(version.date.len(), "YYYY-MM-DD".len());
 /// ```
-pub fn verify_checksum(vin: &str) -> Result<(), VINError> {
-    let vin = vin.to_uppercase();
-    check_validity(&vin)?;
-
-    // verify checksum
-    let checksum: u32 = vin
-        .chars()
-        .map(|x| dicts::VALUE_MAP.get(&x).unwrap())
-        .zip(dicts::WEIGHTS.iter())
-        .map(|(l, r)| l * r)
-        .sum();
-
-
-    let checknumber = match checksum % 11 {
-        10 => 'X',
-        i => std::char::from_digit(i, 10).unwrap()
-    };
-
-    let pr_number = vin.chars().nth(8).unwrap();
-    if pr_number == checknumber {
-        Ok(())
-    } else {
-        Err(ChecksumError(ChecksumErrorInfo {
-            expected: checknumber,
-            received: pr_number,
-        }))
+pub fn data_version() -> dicts::DataVersion {
+    dicts::DataVersion {
+        version: dicts::DATA_VERSION,
+        date: dicts::DATA_DATE,
     }
 }
-
-
-/// Return basic information about manufacturer of the vehicle
-///
-/// # Examples
-/// ```
-/// let vin_number = "wp0zzz998ts392124";
-/// let result = vin::get_info(vin_number).unwrap();
-/// assert_eq!(result.vin, vin_number.to_uppercase());
-/// assert_eq!(result.country, "Germany/West Germany");
-/// assert_eq!(result.manufacturer, "Porsche car");
-/// assert_eq!(result.region, "Europe");
-/// assert!(result.valid_checksum.is_ok())
-/// ```
-pub fn get_info(vin: &str) -> Result<VIN, VINError> {
-    let vin = vin.to_uppercase();
-    check_validity(&vin)?;
-
-    return Ok(VIN {
-        vin: vin.clone(),
-        country: get_country(&vin[..2]),
-        manufacturer: get_manufacturer(&vin[..3]),
-        region: get_region(&vin[..1]),
-        valid_checksum: match verify_checksum(&vin) {
-            Ok(()) => Ok(()),
-            Err(VINError::ChecksumError(x)) => Err(x),
-            _ => Ok(())     // unreachable
-        }
-    })
-}
-
-
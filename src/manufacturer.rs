@@ -0,0 +1,133 @@
+use std::fmt;
+
+use crate::dicts::{get_group, get_manufacturer, manufacturer_rows, manufacturers, BRAND_TABLE};
+use crate::VIN;
+
+/// Holds manufacturer of the vehicle identified by the WMI
+///
+/// # Examples
+/// ```
+/// let result = vin::get_info("WAUZZZ8V5KA123456").unwrap();
+/// let manufacturer = result.manufacturer_info().unwrap();
+/// assert_eq!(manufacturer.name(), "Audi");
+/// assert_eq!(manufacturer.parent_group(), Some("Volkswagen Group"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Manufacturer {
+    wmi: String,
+    name: String,
+}
+
+impl Manufacturer {
+    /// Returns manufacturer with the given WMI known to the built-in dictionary
+    pub fn from_wmi(wmi: &str) -> Option<Manufacturer> {
+        let wmi = wmi.to_uppercase();
+        if wmi.len() != 3 || !wmi.is_ascii() {
+            return None;
+        }
+        get_manufacturer(&wmi).map(|name| Manufacturer { wmi, name })
+    }
+
+    /// Returns WMI of the manufacturer
+    pub fn wmi(&self) -> &str { &self.wmi }
+
+    /// Returns name of the manufacturer
+    pub fn name(&self) -> &str { &self.name }
+
+    /// Returns canonical brand (e.g. `Porsche` for `Porsche car`), or the name itself if it has no aliases
+    pub fn canonical_brand(&self) -> &str { canonical_brand(&self.name) }
+
+    /// Returns parent group (e.g. `Stellantis` for Ram), if known
+    pub fn parent_group(&self) -> Option<&'static str> { get_group(&self.wmi) }
+}
+
+impl fmt::Display for Manufacturer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// Returns parent group of the manufacturer with the given WMI, if known
+///
+/// # Examples
+/// ```
+/// assert_eq!(vin::group_of("1C6"), Some("Stellantis"));
+/// assert_eq!(vin::group_of("wp0"), Some("Volkswagen Group"));
+/// assert_eq!(vin::group_of("1G9"), None);
+/// ```
+pub fn group_of(wmi: &str) -> Option<&'static str> {
+    let wmi = wmi.to_uppercase();
+    if wmi.len() != 3 || !wmi.is_ascii() {
+        return None;
+    }
+    get_group(&wmi)
+}
+
+/// Returns canonical brand for the variation of the manufacturer name (matched
+/// case-insensitively), or the name itself if it is not known to the alias table
+///
+/// # Examples
+/// ```
+/// assert_eq!(vin::canonical_brand("Dr. Ing. h.c. F. Porsche"), "Porsche");
+/// assert_eq!(vin::canonical_brand("PORSCHE AG"), "Porsche");
+/// assert_eq!(vin::canonical_brand("Porsche"), "Porsche");
+/// ```
+pub fn canonical_brand(name: &str) -> &str {
+    let lowercase = name.to_lowercase();
+    BRAND_TABLE
+        .iter()
+        .find(|(alias, _)| alias.to_lowercase() == lowercase)
+        .map_or(name, |(_, brand)| brand)
+}
+
+/// Returns the alias table as pairs of the name variation and the canonical brand
+pub fn brand_aliases() -> &'static [(&'static str, &'static str)] { BRAND_TABLE }
+
+/// Returns WMIs (or their first 2 characters for groups of WMIs) of the manufacturer,
+/// matching either its name or its canonical brand case-insensitively
+///
+/// # Examples
+/// ```
+/// assert_eq!(vin::wmis_for_manufacturer("Porsche"), ["WP0", "WP1"]);
+/// assert_eq!(vin::wmis_for_manufacturer("porsche suv"), ["WP1"]);
+/// assert!(vin::wmis_for_manufacturer("Unknown").is_empty());
+/// ```
+pub fn wmis_for_manufacturer(name: &str) -> Vec<&'static str> {
+    let lowercase = name.to_lowercase();
+    let mut result: Vec<&'static str> = manufacturer_rows()
+        // extended codes of small manufacturers are not WMIs
+        .filter(|(code, _)| code.len() <= 3)
+        // later entries override earlier ones
+        .filter(|(code, manufacturer)| manufacturers().get(*code).map_or(false, |x| x == manufacturer))
+        .filter(|(_, manufacturer)| {
+            manufacturer.to_lowercase() == lowercase || canonical_brand(manufacturer).to_lowercase() == lowercase
+        })
+        .map(|(code, _)| *code)
+        .collect();
+    result.sort_unstable();
+    result.dedup();
+    result
+}
+
+/// Returns names of the manufacturers known to the built-in dictionary containing the query
+/// (case-insensitively), in alphabetical order
+///
+/// # Examples
+/// ```
+/// assert_eq!(vin::search_manufacturers("porsche"), ["Porsche SUV", "Porsche car"]);
+/// ```
+pub fn search_manufacturers(query: &str) -> Vec<&'static str> {
+    let lowercase = query.to_lowercase();
+    let mut result: Vec<&'static str> = manufacturer_rows()
+        .map(|(_, manufacturer)| *manufacturer)
+        .filter(|manufacturer| manufacturer.to_lowercase().contains(&lowercase))
+        .collect();
+    result.sort_unstable();
+    result.dedup();
+    result
+}
+
+impl VIN {
+    /// Returns manufacturer of the vehicle, if it is known to the built-in dictionary
+    pub fn manufacturer_info(&self) -> Option<Manufacturer> { Manufacturer::from_wmi(self.wmi()) }
+}
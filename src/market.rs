@@ -0,0 +1,4 @@
+//! Conventions of particular national markets.
+pub mod india;
+pub mod korea;
+pub mod uk;
@@ -0,0 +1,77 @@
+//! Conventions of VINs assigned in India (WMIs `MA` to `ME`).
+//!
+//! Indian regulations require the month and year of manufacture to be marked on the vehicle,
+//! so some manufacturers encode the month in the VIS using letters `A` (January) to `M`
+//! (December, `I` is skipped). Positions of the month are listed in `data/india.csv`.
+//!
+//! # Examples
+//! ```
+//! use vin::market::india::{self, Segment};
+//!
+//! let result = vin::get_info("ME3U3S5C1KC123456").unwrap();
+//! let manufacturer = india::manufacturer(&result).unwrap();
+//! assert_eq!(manufacturer.name, "Royal Enfield");
+//! assert_eq!(manufacturer.segment, Segment::TwoWheeler);
+//! assert_eq!(india::manufacture_month(&result), Some(3));
+//! ```
+use crate::dicts::INDIA_TABLE;
+use crate::VIN;
+
+/// Letters encoding months of manufacture, starting from January
+const MONTH_LETTERS: &str = "ABCDEFGHJKLM";
+
+/// Describes kind of vehicles produced by the manufacturer
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Segment {
+    /// Passenger cars and utility vehicles
+    Car,
+
+    /// Motorcycles and scooters
+    TwoWheeler,
+
+    /// Trucks and buses
+    Commercial,
+}
+
+/// Holds information about the Indian manufacturer
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct IndianManufacturer {
+    /// Name of the manufacturer
+    pub name: &'static str,
+
+    /// Kind of produced vehicles
+    pub segment: Segment,
+
+    /// Position (1-based) of the month of manufacture, if the manufacturer encodes it
+    pub month_position: Option<usize>,
+}
+
+/// Returns whether the WMI is assigned in India
+pub fn is_indian(vin: &VIN) -> bool {
+    let code = vin.as_bytes();
+    code[0] == b'M' && (b'A'..=b'E').contains(&code[1])
+}
+
+/// Returns the Indian manufacturer of the vehicle, if it is known to the table
+pub fn manufacturer(vin: &VIN) -> Option<IndianManufacturer> {
+    INDIA_TABLE
+        .iter()
+        .find(|(wmi, _, _, _)| *wmi == vin.wmi())
+        .map(|(_, name, segment, month_position)| IndianManufacturer {
+            name,
+            segment: match *segment {
+                "two-wheeler" => Segment::TwoWheeler,
+                "commercial" => Segment::Commercial,
+                _ => Segment::Car,
+            },
+            month_position: month_position.parse().ok(),
+        })
+}
+
+/// Returns the month of manufacture (1-12), if the manufacturer encodes it in the VIS
+pub fn manufacture_month(vin: &VIN) -> Option<u32> {
+    let position = manufacturer(vin)?.month_position?;
+    let letter = *vin.as_bytes().get(position.checked_sub(1)?)? as char;
+    MONTH_LETTERS.find(letter).map(|x| x as u32 + 1)
+}
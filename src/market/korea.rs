@@ -0,0 +1,53 @@
+//! Decoding of VDS and VIS of Hyundai, Kia and Genesis vehicles built in South Korea.
+//!
+//! Korean manufacturers encode the assembly plant in the 11-th position and, for some model
+//! lines, the engine family in the 8-th one. Known codes are listed in `data/korea.csv`.
+//!
+//! # Examples
+//! ```
+//! use vin::market::korea;
+//!
+//! let result = vin::get_info("KMHDH4AE1DU123456").unwrap();
+//! let details = korea::decode(&result).unwrap();
+//! assert_eq!(details.plant, Some("Ulsan"));
+//! assert_eq!(details.engine, Some("1.8L Nu"));
+//! ```
+use crate::dicts::KOREA_TABLE;
+use crate::VIN;
+
+/// WMIs of Hyundai Motor Group vehicles built in South Korea
+const WMIS: [&str; 8] = ["KMH", "KMF", "KMJ", "KMT", "KNA", "KNC", "KND", "KNE"];
+
+/// Holds manufacturer-specific information decoded from the VIN
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct KoreanDetails {
+    /// Assembly plant, if known
+    pub plant: Option<&'static str>,
+
+    /// Engine family, if known
+    pub engine: Option<&'static str>,
+}
+
+/// Returns whether the vehicle is built by Hyundai, Kia or Genesis in South Korea
+pub fn is_covered(vin: &VIN) -> bool { WMIS.contains(&vin.wmi()) }
+
+/// Returns decoded plant and engine family, or `None` if the manufacturer is not covered
+pub fn decode(vin: &VIN) -> Option<KoreanDetails> {
+    if !is_covered(vin) {
+        return None;
+    }
+    Some(KoreanDetails { plant: lookup(vin, "plant"), engine: lookup(vin, "engine") })
+}
+
+fn lookup(vin: &VIN, kind: &str) -> Option<&'static str> {
+    let code = vin.as_str();
+    KOREA_TABLE
+        .iter()
+        .filter(|(prefix, position, ch, x, _)| {
+            *x == kind
+                && code.starts_with(prefix)
+                && position.parse().ok().and_then(|x: usize| code.get(x.checked_sub(1)?..x)) == Some(*ch)
+        })
+        .max_by_key(|(prefix, _, _, _, _)| prefix.len())
+        .map(|(_, _, _, _, value)| *value)
+}
@@ -0,0 +1,86 @@
+//! Conventions of VINs assigned in the United Kingdom (WMIs `SA` to `SM`).
+//!
+//! Manufacturers producing less than 1000 vehicles per year (kit cars, specialist sports cars)
+//! share the `SA9` WMI and are identified by 12-14 positions of the VIN, so the serial number
+//! takes only 15-17 positions.
+//!
+//! # Examples
+//! ```
+//! use vin::market::uk;
+//!
+//! let result = vin::get_info("SCCPC11125HA12345").unwrap();
+//! assert!(uk::is_uk(&result));
+//! assert_eq!(result.manufacturer.as_deref(), Some("Lotus Cars"));
+//! assert!(uk::check(&result).is_empty());
+//!
+//! let small = vin::get_info("SA9AB123XK1MCA1B3").unwrap();
+//! assert_eq!(uk::small_manufacturer_code(&small), Some("MCA"));
+//! assert_eq!(uk::check(&small), vec![uk::UkIssue::SmallManufacturerSerial]);
+//! ```
+use std::fmt;
+
+use crate::dicts::get_manufacturer;
+use crate::years::YEAR_LETTERS;
+use crate::VIN;
+
+/// Describes deviations of the VIN from UK conventions
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum UkIssue {
+    /// WMI is in the UK range, but is not known to the built-in dictionary
+    UnknownManufacturer,
+
+    /// Serial number of the small manufacturer (15-17 positions) is not numeric
+    SmallManufacturerSerial,
+
+    /// 10-th position does not encode the model year (common for VINs issued before 1980s)
+    NoModelYear(char),
+}
+
+impl fmt::Display for UkIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UkIssue::UnknownManufacturer =>
+                write!(f, "Manufacturer is not known for the UK WMI."),
+            UkIssue::SmallManufacturerSerial =>
+                write!(f, "Serial number of the small manufacturer must be numeric."),
+            UkIssue::NoModelYear(ch) =>
+                write!(f, "Symbol {:?} does not encode the model year.", ch),
+        }
+    }
+}
+
+/// Returns whether the WMI is assigned in the United Kingdom
+pub fn is_uk(vin: &VIN) -> bool {
+    let code = vin.as_bytes();
+    code[0] == b'S' && (b'A'..=b'M').contains(&code[1])
+}
+
+/// Returns manufacturer code (12-14 positions) of the small UK manufacturer
+pub fn small_manufacturer_code(vin: &VIN) -> Option<&str> {
+    if is_uk(vin) && vin.small_manufacturer() {
+        Some(&vin.as_str()[11..14])
+    } else {
+        None
+    }
+}
+
+/// Returns deviations of UK VIN from the conventions (empty for non-UK VINs)
+pub fn check(vin: &VIN) -> Vec<UkIssue> {
+    let mut issues = vec![];
+    if !is_uk(vin) {
+        return issues;
+    }
+
+    if get_manufacturer(vin.wmi()).is_none() {
+        issues.push(UkIssue::UnknownManufacturer);
+    }
+    if small_manufacturer_code(vin).is_some() && !vin.as_bytes()[14..].iter().all(u8::is_ascii_digit) {
+        issues.push(UkIssue::SmallManufacturerSerial);
+    }
+    let year = vin.as_bytes()[9] as char;
+    if !YEAR_LETTERS.contains(year) {
+        issues.push(UkIssue::NoModelYear(year));
+    }
+    issues
+}
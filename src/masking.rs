@@ -0,0 +1,76 @@
+use crate::parse::validated;
+use crate::{VINError, VIN};
+
+/// Symbol used to replace redacted characters
+const MASK_CHAR: char = '*';
+
+/// Describes which parts of the VIN are kept in the masked output
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MaskPolicy {
+    /// Keep only WMI (1-3 positions)
+    KeepWmi,
+
+    /// Keep WMI and the model year symbol (10-th position)
+    KeepWmiAndYear,
+
+    /// Keep WMI, VDS and the check digit (1-9 positions), redact the whole VIS
+    KeepDescriptor,
+
+    /// Keep WMI, VDS and the check digit (1-9 positions) and given count of trailing symbols
+    KeepDescriptorAndLast(usize),
+
+    /// Keep symbols at the positions marked with `true`
+    Custom([bool; 17]),
+}
+
+impl Default for MaskPolicy {
+    fn default() -> Self { MaskPolicy::KeepDescriptorAndLast(4) }
+}
+
+impl MaskPolicy {
+    fn keeps(&self, position: usize) -> bool {
+        match self {
+            MaskPolicy::KeepWmi => position < 3,
+            MaskPolicy::KeepWmiAndYear => position < 3 || position == 9,
+            MaskPolicy::KeepDescriptor => position < 9,
+            MaskPolicy::KeepDescriptorAndLast(n) => position < 9 || *n >= 17 - position,
+            MaskPolicy::Custom(keep) => keep[position],
+        }
+    }
+}
+
+/// Redacts the VIN according to the given policy, so that it can be safely logged
+///
+/// # Examples
+/// ```
+/// use vin::MaskPolicy;
+///
+/// let vin_number = "wp0zzz99zts392124";
+/// assert_eq!(vin::mask(vin_number, MaskPolicy::default()).unwrap(), "WP0ZZZ99Z****2124");
+/// assert_eq!(vin::mask(vin_number, MaskPolicy::KeepWmiAndYear).unwrap(), "WP0******T*******");
+/// ```
+pub fn mask(vin: &str, policy: MaskPolicy) -> Result<String, VINError> {
+    Ok(mask_unchecked(&validated(vin)?, policy))
+}
+
+fn mask_unchecked(vin: &str, policy: MaskPolicy) -> String {
+    vin.chars()
+        .enumerate()
+        .map(|(i, x)| if policy.keeps(i) { x } else { MASK_CHAR })
+        .collect()
+}
+
+impl VIN {
+    /// Returns VIN redacted with the default policy (WMI, VDS and the last 4 symbols are kept)
+    ///
+    /// # Examples
+    /// ```
+    /// let result = vin::get_info("WP0ZZZ99ZTS392124").unwrap();
+    /// assert_eq!(result.masked(), "WP0ZZZ99Z****2124");
+    /// ```
+    pub fn masked(&self) -> String { self.masked_with(MaskPolicy::default()) }
+
+    /// Returns VIN redacted with the given policy
+    pub fn masked_with(&self, policy: MaskPolicy) -> String { mask_unchecked(self.as_str(), policy) }
+}
@@ -0,0 +1,118 @@
+//! Localization of error messages and findings.
+//!
+//! Messages are rendered from templates of the [`MessageCatalog`] by the identifier of
+//! the message (`E_LENGTH`, `E_CHARS`, `E_LETTERS`, `E_CHECKSUM`, `E_SERIAL` for errors and
+//! [`Finding::id`](../struct.Finding.html#method.id) for findings). Templates may contain
+//! placeholders `{chars}`, `{positions}` (1-based), `{expected}`, `{received}` and `{position}`.
+//! English messages are used for identifiers unknown to the catalog.
+//!
+//! The catalog may be passed explicitly, or set process-wide with [`set_catalog`], so `Display`
+//! implementations of [`VINError`](../enum.VINError.html) and [`Finding`](../struct.Finding.html)
+//! use it as well.
+//!
+//! # Examples
+//! ```
+//! use vin::messages::Catalog;
+//!
+//! let german = Catalog::new()
+//!     .with("E_LENGTH", "Falsche Länge, 17 Zeichen erwartet.")
+//!     .with("E_CHECKSUM", "Ungültige Prüfziffer, {expected} erwartet, {received} erhalten.");
+//!
+//! let err = vin::verify_checksum("1M8GDM9AXKP042789").unwrap_err();
+//! assert_eq!(err.localized(&german), "Ungültige Prüfziffer, 1 erwartet, X erhalten.");
+//! ```
+use std::collections::HashMap;
+
+use crate::sync::OnceLock;
+use crate::{Finding, InvalidChar, VINError};
+
+/// Source of message templates by the identifier of the message
+pub trait MessageCatalog: Send + Sync {
+    /// Returns template of the message with the given identifier, `None` to use English message
+    fn template(&self, id: &str) -> Option<&str>;
+}
+
+/// Catalog of message templates kept in memory
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    templates: HashMap<String, String>,
+}
+
+impl Catalog {
+    /// Creates empty catalog
+    pub fn new() -> Catalog { Catalog::default() }
+
+    /// Adds the template of the message with the given identifier
+    pub fn with(mut self, id: &str, template: &str) -> Catalog {
+        self.templates.insert(id.to_string(), template.to_string());
+        self
+    }
+}
+
+impl MessageCatalog for Catalog {
+    fn template(&self, id: &str) -> Option<&str> { self.templates.get(id).map(String::as_str) }
+}
+
+static CATALOG: OnceLock<Box<dyn MessageCatalog>> = OnceLock::new();
+
+/// Sets process-wide catalog used by `Display` implementations, returns the given catalog back
+/// if it was already set
+pub fn set_catalog<C: MessageCatalog + 'static>(catalog: C) -> Result<(), C> {
+    let mut catalog = Some(catalog);
+    CATALOG.get_or_init(|| Box::new(catalog.take().unwrap()));
+    match catalog {
+        Some(catalog) => Err(catalog),
+        None => Ok(()),
+    }
+}
+
+/// Identifier and arguments of the message
+pub(in crate) type Message = (&'static str, Vec<(&'static str, String)>);
+
+/// Returns identifier and arguments of the error message
+pub(in crate) fn error_message(err: &VINError) -> Message {
+    match err {
+        VINError::IncorrectLength => ("E_LENGTH", vec![]),
+        VINError::InvalidCharacters(invalid) => ("E_CHARS", char_args(invalid)),
+        VINError::ForbiddenLetters(letters) => ("E_LETTERS", char_args(letters)),
+        VINError::ChecksumError(info) =>
+            ("E_CHECKSUM", vec![("expected", info.expected.to_string()), ("received", info.received.to_string())]),
+        VINError::NonNumericSerial(position) => ("E_SERIAL", vec![("position", position.to_string())]),
+    }
+}
+
+/// Returns `chars` (sorted, without duplicates) and `positions` (1-based) arguments
+fn char_args(invalid: &[InvalidChar]) -> Vec<(&'static str, String)> {
+    let mut chars: Vec<char> = invalid.iter().map(|x| x.character).collect();
+    chars.sort_unstable();
+    chars.dedup();
+    let positions: Vec<String> = invalid.iter().map(|x| (x.position + 1).to_string()).collect();
+    vec![("chars", chars.into_iter().collect()), ("positions", positions.join(", "))]
+}
+
+/// Returns message rendered with the catalog, if it has the template
+pub(in crate) fn render(catalog: &dyn MessageCatalog, (id, args): &Message) -> Option<String> {
+    let template = catalog.template(id)?;
+    Some(args.iter().fold(template.to_string(), |message, (name, value)| {
+        message.replace(&format!("{{{}}}", name), value)
+    }))
+}
+
+/// Returns message rendered with the process-wide catalog, if it is set and has the template
+pub(in crate) fn render_global(message: &Message) -> Option<String> {
+    render(CATALOG.get()?.as_ref(), message)
+}
+
+impl VINError {
+    /// Returns message rendered with the catalog, or English message if the catalog has no template
+    pub fn localized(&self, catalog: &dyn MessageCatalog) -> String {
+        render(catalog, &error_message(self)).unwrap_or_else(|| self.english())
+    }
+}
+
+impl Finding {
+    /// Returns message rendered with the catalog, or English message if the catalog has no template
+    pub fn localized(&self, catalog: &dyn MessageCatalog) -> String {
+        render(catalog, &(self.id(), self.args.clone())).unwrap_or_else(|| self.message.clone())
+    }
+}
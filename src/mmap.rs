@@ -0,0 +1,208 @@
+//! Memory-mapped external dataset of manufacturers, for datasets too large to be kept in memory.
+//!
+//! The dataset is packed from CSV by the `vin-pack` binary (see [`pack_csv`]) and plugged into
+//! [`VinParser`](../struct.VinParser.html) as [`MmapSource`]. Its keys are prefixes (3-10 symbols)
+//! of the squish VIN (1-8 and 10-11 positions of the VIN), the longest matching prefix is used.
+//!
+//! # Layout
+//!
+//! All numbers are little-endian.
+//!
+//! | Offset | Size | Content |
+//! |--------|------|---------|
+//! | 0 | 8 | magic `VINWMI01` |
+//! | 8 | 4 | count of records (`u32`) |
+//! | 12 | 4 | reserved, zero |
+//! | 16 | 24 × count | records sorted by the key bytes |
+//! | 16 + 24 × count | rest | UTF-8 strings referenced by records |
+//!
+//! Every record consists of the key (10 uppercase ASCII bytes padded with zeros), offset (`u32`,
+//! from the start of strings) and length (`u16`) of the manufacturer name, the same for
+//! the country name (zero length if unknown) and 2 reserved zero bytes.
+//!
+//! # Examples
+//! ```
+//! use vin::mmap::{pack_csv, MmapSource};
+//! use vin::VinParser;
+//!
+//! let path = std::env::temp_dir().join("vin_mmap_doc.bin");
+//! let csv = "key,manufacturer,country\nXTA,Lada,Russia\nXTA21099,Lada 21099,Russia\n";
+//! pack_csv(csv.as_bytes(), std::fs::File::create(&path).unwrap()).unwrap();
+//!
+//! let parser = VinParser::with_source(MmapSource::open(&path).unwrap());
+//! assert_eq!(parser.parse("XTA21099043576182").unwrap().manufacturer.unwrap(), "Lada 21099");
+//! assert_eq!(parser.parse("XTA21083043576182").unwrap().manufacturer.unwrap(), "Lada");
+//! ```
+use std::convert::TryInto;
+use std::fs::File;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use crate::dicts::get_region;
+use crate::source::{WmiRecord, WmiSource};
+use crate::squish::squish_unchecked;
+use crate::tables::is_allowed;
+
+const MAGIC: &[u8; 8] = b"VINWMI01";
+const HEADER_LEN: usize = 16;
+const RECORD_LEN: usize = 24;
+const KEY_LEN: usize = 10;
+
+/// Source of manufacturers information backed by the memory-mapped file
+#[derive(Debug)]
+pub struct MmapSource {
+    map: Mmap,
+    count: usize,
+}
+
+impl MmapSource {
+    /// Maps the packed dataset, checking its header and size
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<MmapSource> {
+        let file = File::open(path)?;
+        // the file is expected not to be modified while mapped, as with any packed dataset
+        let map = unsafe { Mmap::map(&file)? };
+        if map.len() < HEADER_LEN || &map[..8] != MAGIC {
+            return Err(invalid_data("not a packed VIN dataset"));
+        }
+        let count = u32::from_le_bytes(map[8..12].try_into().unwrap()) as usize;
+        if map.len() < HEADER_LEN + count * RECORD_LEN {
+            return Err(invalid_data("truncated packed VIN dataset"));
+        }
+        Ok(MmapSource { map, count })
+    }
+
+    /// Returns count of records
+    pub fn len(&self) -> usize { self.count }
+
+    /// Returns whether the dataset has no records
+    pub fn is_empty(&self) -> bool { self.count == 0 }
+
+    fn record(&self, index: usize) -> &[u8] {
+        let start = HEADER_LEN + index * RECORD_LEN;
+        &self.map[start..start + RECORD_LEN]
+    }
+
+    fn string(&self, field: &[u8]) -> Option<String> {
+        let offset = u32::from_le_bytes(field[..4].try_into().unwrap()) as usize;
+        let len = u16::from_le_bytes(field[4..6].try_into().unwrap()) as usize;
+        let start = HEADER_LEN + self.count * RECORD_LEN + offset;
+        match len {
+            0 => None,
+            _ => self.map.get(start..start + len).map(|x| String::from_utf8_lossy(x).into_owned()),
+        }
+    }
+
+    /// Returns record with exactly the given key
+    fn find(&self, key: &str) -> Option<WmiRecord> {
+        let key = padded(key)?;
+        let (mut low, mut high) = (0, self.count);
+        while low < high {
+            let middle = (low + high) / 2;
+            let record = self.record(middle);
+            match record[..KEY_LEN].cmp(&key[..]) {
+                std::cmp::Ordering::Less => low = middle + 1,
+                std::cmp::Ordering::Greater => high = middle,
+                std::cmp::Ordering::Equal => return Some(WmiRecord {
+                    manufacturer: self.string(&record[10..16]).unwrap_or_default(),
+                    country: self.string(&record[16..22]),
+                    region: get_region(std::str::from_utf8(&key[..1]).unwrap_or_default()),
+                }),
+            }
+        }
+        None
+    }
+}
+
+impl WmiSource for MmapSource {
+    fn lookup(&self, wmi: &str) -> Option<WmiRecord> { self.find(wmi) }
+
+    fn lookup_vin(&self, vin: &str) -> Option<WmiRecord> {
+        let squish = squish_unchecked(vin);
+        (3..=KEY_LEN).rev().find_map(|len| self.find(&squish[..len]))
+    }
+}
+
+/// Returns the key padded with zeros, or `None` if it is not 3-10 allowed symbols
+fn padded(key: &str) -> Option<[u8; KEY_LEN]> {
+    if !(3..=KEY_LEN).contains(&key.len()) || !key.chars().all(is_allowed) {
+        return None;
+    }
+    let mut result = [0; KEY_LEN];
+    result[..key.len()].copy_from_slice(key.as_bytes());
+    Some(result)
+}
+
+fn invalid_data(message: &str) -> io::Error { io::Error::new(io::ErrorKind::InvalidData, message) }
+
+/// Packs the dataset from CSV with `key,manufacturer,country` header (fields containing commas
+/// must be enclosed in double quotes, the country may be empty), returns count of records
+pub fn pack_csv<R: BufRead, W: Write>(reader: R, mut writer: W) -> io::Result<usize> {
+    let mut records = vec![];
+    for (number, line) in reader.lines().enumerate().skip(1) {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let error = |message: &str| invalid_data(&format!("line {}: {}", number + 1, message));
+        let fields = split_csv(&line);
+        if fields.len() != 3 {
+            return Err(error("3 fields expected"));
+        }
+        let key = padded(&fields[0].to_uppercase()).ok_or_else(|| error("key of 3-10 VIN symbols expected"))?;
+        if fields[1].is_empty() {
+            return Err(error("manufacturer expected"));
+        }
+        records.push((key, fields[1].clone(), fields[2].clone()));
+    }
+    records.sort();
+    if let Some(pair) = records.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+        return Err(invalid_data(&format!("duplicate key {}", String::from_utf8_lossy(&pair[0].0))));
+    }
+
+    let mut strings: Vec<u8> = vec![];
+    let mut reference = |value: &str| -> io::Result<[u8; 6]> {
+        let len: u16 = value.len().try_into().map_err(|_| invalid_data("too long name"))?;
+        let offset: u32 = strings.len().try_into().map_err(|_| invalid_data("too large dataset"))?;
+        strings.extend_from_slice(value.as_bytes());
+        let mut result = [0; 6];
+        result[..4].copy_from_slice(&offset.to_le_bytes());
+        result[4..].copy_from_slice(&len.to_le_bytes());
+        Ok(result)
+    };
+
+    let count: u32 = records.len().try_into().map_err(|_| invalid_data("too large dataset"))?;
+    let mut packed = Vec::with_capacity(HEADER_LEN + records.len() * RECORD_LEN);
+    packed.extend_from_slice(MAGIC);
+    packed.extend_from_slice(&count.to_le_bytes());
+    packed.extend_from_slice(&[0; 4]);
+    for (key, manufacturer, country) in &records {
+        packed.extend_from_slice(key);
+        packed.extend_from_slice(&reference(manufacturer)?);
+        packed.extend_from_slice(&reference(country)?);
+        packed.extend_from_slice(&[0; 2]);
+    }
+    writer.write_all(&packed)?;
+    writer.write_all(&strings)?;
+    Ok(records.len())
+}
+
+/// Splits CSV line into fields, which may be enclosed in double quotes
+fn split_csv(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(x) = chars.next() {
+        match x {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().unwrap().push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            _ => fields.last_mut().unwrap().push(x),
+        }
+    }
+    fields
+}
@@ -0,0 +1,42 @@
+//! Instrumentation of the main entry points.
+use crate::{VINError, VIN};
+
+/// Returns WMI of the raw (not validated) VIN, so that it can be attached to spans
+#[cfg(feature = "tracing")]
+pub(in crate) fn wmi(vin: &str) -> &str { vin.get(..3).unwrap_or_default() }
+
+/// Name of the counter of [`parse_outcome`] values
+#[cfg(feature = "metrics")]
+pub(in crate) const PARSED_COUNTER: &str = "vin_parsed_total";
+
+/// Name of the counter of [`checksum_outcome`] values
+#[cfg(feature = "metrics")]
+pub(in crate) const CHECKSUM_COUNTER: &str = "vin_checksum_verified_total";
+
+/// Returns outcome of the parsing
+pub(in crate) fn parse_outcome(result: &Result<VIN, VINError>) -> &'static str {
+    match result {
+        Ok(vin) if vin.valid_checksum.is_invalid() => "checksum_fail",
+        Ok(vin) if vin.manufacturer.is_none() => "unknown_wmi",
+        Ok(_) => "valid",
+        Err(err) => error_outcome(err),
+    }
+}
+
+/// Returns outcome of the checksum verification
+pub(in crate) fn checksum_outcome(result: &Result<(), VINError>) -> &'static str {
+    match result {
+        Ok(()) => "valid",
+        Err(err) => error_outcome(err),
+    }
+}
+
+fn error_outcome(err: &VINError) -> &'static str {
+    match err {
+        VINError::IncorrectLength => "invalid_length",
+        VINError::InvalidCharacters(_) => "invalid_chars",
+        VINError::ChecksumError(_) => "checksum_fail",
+        VINError::NonNumericSerial(_) => "non_numeric_serial",
+        VINError::ForbiddenLetters(_) => "forbidden_letters",
+    }
+}
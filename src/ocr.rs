@@ -0,0 +1,158 @@
+//! Decoding of VINs recognized by OCR engines.
+//!
+//! OCR engine provides a list of possible characters with probabilities for every position.
+//! The decoder searches for the most probable combinations which are structurally valid
+//! and pass the checksum validation.
+//!
+//! # Examples
+//! ```
+//! use vin::ocr::decode_candidates;
+//!
+//! let mut chars: Vec<Vec<(char, f32)>> = "1M8GDM9AXKP042788".chars().map(|x| vec![(x, 0.9)]).collect();
+//! chars[15] = vec![('B', 0.6), ('8', 0.4)];  // "8" misread as "B"
+//!
+//! let candidates = decode_candidates(&chars);
+//! assert_eq!(candidates[0].vin, "1M8GDM9AXKP042788");
+//! ```
+use std::collections::HashMap;
+
+use crate::dicts;
+use crate::tables::{char_value, is_allowed};
+use crate::years::YEAR_LETTERS;
+
+/// Maximum count of candidates returned by [`decode_candidates`]
+pub const MAX_CANDIDATES: usize = 10;
+
+/// Holds decoded VIN with its probability
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoredVin {
+    /// Decoded VIN
+    pub vin: String,
+
+    /// Probability of the VIN (product of probabilities of its characters)
+    pub score: f32,
+}
+
+#[derive(Debug, Clone)]
+struct Partial {
+    log_score: f64,
+    chars: Vec<char>,
+}
+
+/// Returns the most probable VINs (up to [`MAX_CANDIDATES`]), best first
+pub fn decode_candidates(chars: &[Vec<(char, f32)>]) -> Vec<ScoredVin> {
+    decode_candidates_with_limit(chars, MAX_CANDIDATES)
+}
+
+/// Returns up to `limit` the most probable VINs, best first.
+///
+/// Characters which can never appear in VIN are mapped to the similar ones
+/// (`O` and `Q` to `0`, `I` to `1`), other invalid characters are ignored.
+/// Empty list is returned if there are not exactly 17 positions.
+pub fn decode_candidates_with_limit(chars: &[Vec<(char, f32)>], limit: usize) -> Vec<ScoredVin> {
+    if chars.len() != 17 || limit == 0 {
+        return vec![];
+    }
+
+    let options: Vec<Vec<(char, f64)>> = chars
+        .iter()
+        .enumerate()
+        .map(|(i, x)| normalize(i, x))
+        .collect();
+
+    // best partial combinations by the weighted sum modulo 11
+    let mut states: Vec<Vec<Partial>> = vec![vec![]; 11];
+    states[0].push(Partial { log_score: 0.0, chars: vec![] });
+
+    for (i, position) in options.iter().enumerate() {
+        if i == 8 {
+            // check digit does not affect the sum, it's chosen in the end
+            for state in states.iter_mut() {
+                for partial in state.iter_mut() {
+                    partial.chars.push('_');
+                }
+            }
+            continue;
+        }
+
+        let weight = dicts::WEIGHTS[i];
+        let mut next: Vec<Vec<Partial>> = vec![vec![]; 11];
+        for (remainder, state) in states.iter().enumerate() {
+            for partial in state {
+                for (ch, log_p) in position {
+                    let value = char_value(*ch).unwrap_or(0);
+                    let mut chars = partial.chars.clone();
+                    chars.push(*ch);
+                    next[(remainder + (value * weight) as usize) % 11].push(Partial {
+                        log_score: partial.log_score + log_p,
+                        chars,
+                    });
+                }
+            }
+        }
+
+        for state in next.iter_mut() {
+            keep_best(state, limit);
+        }
+        states = next;
+    }
+
+    let mut result: Vec<Partial> = vec![];
+    for (ch, log_p) in &options[8] {
+        let remainder = match ch {
+            'X' => 10,
+            x => match x.to_digit(10) {
+                Some(x) => x as usize,
+                None => continue,
+            },
+        };
+        for partial in &states[remainder] {
+            let mut chars = partial.chars.clone();
+            chars[8] = *ch;
+            result.push(Partial { log_score: partial.log_score + log_p, chars });
+        }
+    }
+    keep_best(&mut result, limit);
+
+    result
+        .into_iter()
+        .map(|x| ScoredVin {
+            vin: x.chars.into_iter().collect(),
+            score: x.log_score.exp() as f32,
+        })
+        .collect()
+}
+
+/// Maps characters to the allowed ones, merges duplicates and converts probabilities to logarithms
+fn normalize(position: usize, chars: &[(char, f32)]) -> Vec<(char, f64)> {
+    let mut merged: HashMap<char, f64> = HashMap::new();
+
+    for (ch, p) in chars {
+        let ch = match ch.to_ascii_uppercase() {
+            'O' | 'Q' => '0',
+            'I' => '1',
+            x => x,
+        };
+        if *p <= 0.0 || !is_allowed(ch) {
+            continue;
+        }
+        if position == 9 && !YEAR_LETTERS.contains(ch) {
+            continue;
+        }
+        *merged.entry(ch).or_insert(0.0) += f64::from(*p);
+    }
+
+    let mut result: Vec<(char, f64)> = merged.into_iter().map(|(ch, p)| (ch, p.ln())).collect();
+    result.sort_by_key(|x| x.0);
+    result
+}
+
+fn keep_best(partials: &mut Vec<Partial>, limit: usize) {
+    partials.sort_by(|a, b| {
+        b.log_score
+            .partial_cmp(&a.log_score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.chars.cmp(&b.chars))
+    });
+    partials.truncate(limit);
+}
@@ -0,0 +1,323 @@
+//! Validation and decoding of VINs.
+//!
+//! Functions of this module are also available at the crate root for compatibility with 1.x.
+use std::borrow::Cow;
+use std::convert::TryInto;
+
+#[cfg(any(feature = "metrics", feature = "tracing"))]
+use crate::observe;
+use crate::tables::char_value;
+use crate::VINError::{self, ChecksumError, IncorrectLength, InvalidCharacters};
+use crate::{config, dicts, ChecksumErrorInfo, InvalidChar, VIN};
+
+mod kernel;
+
+
+/// Validates Vehicle Identification Number without computing the checksum
+/// (check used symbols and length of the number), the input is normalized as configured
+/// (see [`config`](config/index.html))
+///
+/// # Examples
+/// ```
+/// let vin_number = "WP0ZZZ99ZTS392124";
+/// assert!(vin::check_validity(vin_number).is_ok());
+///
+/// let vin_number = "W$0ZZZ99ZTS392124";  // notice $ sign on 2-nd place
+/// assert!(vin::check_validity(vin_number).is_err())
+/// ```
+pub fn check_validity(vin: &str) -> Result<(), VINError> {
+    check_uppercase(&uppercase(&config::normalization().apply(vin)))
+}
+
+/// Returns the normalized uppercase VIN if it is valid (see [`check_validity`])
+pub(in crate) fn validated(vin: &str) -> Result<String, VINError> {
+    let vin = uppercase(&config::normalization().apply(vin)).into_owned();
+    check_uppercase(&vin)?;
+    Ok(vin)
+}
+
+/// Returns the VIN in uppercase, borrowing the input if it is already uppercase ASCII
+pub(in crate) fn uppercase(vin: &str) -> Cow<'_, str> {
+    match vin.bytes().any(|x| x.is_ascii_lowercase() || !x.is_ascii()) {
+        true => Cow::Owned(vin.to_uppercase()),
+        false => Cow::Borrowed(vin),
+    }
+}
+
+/// Validates length and alphabet of the uppercase VIN
+pub(in crate) fn check_uppercase(vin: &str) -> Result<(), VINError> {
+    // check length
+    if vin.chars().count() != 17 {
+        return Err(IncorrectLength);
+    }
+
+    // check alphabet, the set of odd symbols is collected only if there are any
+    if vin.bytes().all(|x| dicts::BYTE_VALUES[x as usize] != u8::MAX) {
+        return Ok(());
+    }
+    Err(InvalidCharacters(InvalidChar::find(vin, &[])))
+}
+
+
+/// Computes the check digit (symbol at the 9-th place) of the given VIN.
+///
+/// The 9-th symbol itself is not taken into account, so it may be blank, a placeholder
+/// or simply wrong - which allows to stamp the correct digit into a freshly generated number.
+///
+/// # Examples
+/// ```
+/// assert_eq!(vin::compute_check_digit("1M8GDM9A_KP042788").unwrap(), 'X');
+/// assert_eq!(vin::compute_check_digit("WP0ZZZ99ZTS392124").unwrap(), '8');
+/// ```
+pub fn compute_check_digit(vin: &str) -> Result<char, VINError> {
+    let vin = uppercase(vin);
+
+    // check length
+    if vin.chars().count() != 17 {
+        return Err(IncorrectLength);
+    }
+
+    // check alphabet of every position but the 9-th
+    let odd_chars = InvalidChar::find(&vin, &[8]);
+    if !odd_chars.is_empty() {
+        return Err(InvalidCharacters(odd_chars));
+    }
+
+    let checksum: u32 = contributions(&vin).iter().sum();
+    Ok(check_symbol(checksum))
+}
+
+/// Returns value of every symbol multiplied by the weight of its position
+fn contributions(vin: &str) -> [u32; 17] {
+    let mut result = [0; 17];
+    for ((l, r), x) in vin.chars().zip(dicts::WEIGHTS.iter()).zip(result.iter_mut()) {
+        *x = char_value(l).unwrap_or(0) * r;
+    }
+    result
+}
+
+fn check_symbol(checksum: u32) -> char {
+    match checksum % 11 {
+        10 => 'X',
+        i => std::char::from_digit(i, 10).unwrap()
+    }
+}
+
+
+/// Validates Vehicle Identification Number AND validates the checksum
+///
+/// Input is normalized as set by [`config::configure`](crate::config::configure).
+///
+/// # Examples
+/// ```
+/// let vin_number = "1M8GDM9AXKP042788";
+/// assert!(vin::verify_checksum(vin_number).is_ok());
+///
+/// let vin_number = "WP0ZZZ99ZTS392124";
+/// assert!(match vin::verify_checksum(vin_number) {
+///     Err(vin::VINError::ChecksumError(vin::ChecksumErrorInfo {
+///         expected: '8',
+///         received: 'Z',
+///         ..
+///     })) => true,
+///     _ => false,
+/// })
+/// ```
+pub fn verify_checksum(vin: &str) -> Result<(), VINError> {
+    verify_normalized(&config::normalization().apply(vin))
+}
+
+/// Validates the checksum of already normalized VIN, instrumented as [`verify_checksum`]
+pub(in crate) fn verify_normalized(vin: &str) -> Result<(), VINError> {
+    observe_checksum(vin, || verify_checksum_unobserved(vin))
+}
+
+/// Instruments the checksum verification of the VIN
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(in crate) fn observe_checksum<F>(vin: &str, verify: F) -> Result<(), VINError>
+where
+    F: FnOnce() -> Result<(), VINError>,
+{
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("verify_checksum", wmi = observe::wmi(vin)).entered();
+
+    let result = verify();
+
+    #[cfg(feature = "tracing")]
+    tracing::debug!(outcome = observe::checksum_outcome(&result), "checksum verified");
+    #[cfg(feature = "metrics")]
+    metrics::counter!(observe::CHECKSUM_COUNTER, "outcome" => observe::checksum_outcome(&result)).increment(1);
+    result
+}
+
+fn verify_checksum_unobserved(vin: &str) -> Result<(), VINError> {
+    let vin = uppercase(vin);
+    let bytes = ascii_bytes(&vin)?;
+    let contributions = scan(bytes)?;
+    check_digit_result(bytes, contributions)
+}
+
+/// Returns bytes of the uppercase VIN if it consists of 17 ASCII symbols
+pub(in crate) fn ascii_bytes(vin: &str) -> Result<&[u8; 17], VINError> {
+    match vin.as_bytes().try_into() {
+        Ok(bytes) if vin.is_ascii() => Ok(bytes),
+        // the error is described by the regular validation
+        _ => Err(check_uppercase(vin).err().unwrap_or(IncorrectLength)),
+    }
+}
+
+/// Validates alphabet of the uppercase VIN and computes contributions of its symbols into
+/// the checksum in a single pass
+pub(in crate) fn scan(vin: &[u8; 17]) -> Result<[u32; 17], VINError> {
+    let mut contributions = [0; 17];
+    let mut valid = true;
+    for ((byte, weight), x) in vin.iter().zip(dicts::WEIGHTS.iter()).zip(contributions.iter_mut()) {
+        let value = dicts::BYTE_VALUES[*byte as usize];
+        valid &= value != u8::MAX;
+        *x = u32::from(value) * weight;
+    }
+    match valid {
+        true => Ok(contributions),
+        false => Err(InvalidCharacters(InvalidChar::find(&vin.iter().map(|x| *x as char).collect::<String>(), &[]))),
+    }
+}
+
+/// Compares the check digit of the VIN with the one computed from the contributions
+pub(in crate) fn check_digit_result(vin: &[u8; 17], contributions: [u32; 17]) -> Result<(), VINError> {
+    let sum = contributions.iter().sum();
+    let checknumber = check_symbol(sum);
+
+    let pr_number = vin[8] as char;
+    if pr_number == checknumber {
+        Ok(())
+    } else {
+        Err(ChecksumError(ChecksumErrorInfo {
+            expected: checknumber,
+            received: pr_number,
+            sum,
+            contributions,
+        }))
+    }
+}
+
+/// Validates checksums of many VINs at once, returning the same results as
+/// [`verify_checksum`](fn.verify_checksum.html) in the order of the input
+///
+/// Uppercase VINs are verified as is, on x86_64 with SSE2 instructions transliterating and
+/// weighting 16 symbols at once. Other VINs are normalized and verified one by one. Unlike
+/// `verify_checksum`, the batch is not instrumented.
+///
+/// # Examples
+/// ```
+/// let results = vin::parse::verify_checksum_batch(&["1M8GDM9AXKP042788", "1m8gdm9axkp042788", "WP0ZZZ99ZTS392124"]);
+/// assert!(results[0].is_ok() && results[1].is_ok());
+/// assert!(matches!(results[2], Err(vin::VINError::ChecksumError(_))));
+/// ```
+pub fn verify_checksum_batch(vins: &[&str]) -> Vec<Result<(), VINError>> {
+    vins.iter()
+        .map(|vin| match vin.as_bytes().try_into() {
+            Ok(bytes) if kernel::weighted_sum(bytes).map_or(false, |sum| bytes[8] as char == check_symbol(sum)) =>
+                Ok(()),
+            // errors are rare, so they are described by the regular verification
+            _ => verify_checksum_unobserved(&config::normalization().apply(vin)),
+        })
+        .collect()
+}
+
+/// Returns 1-based position of the first non-numeric symbol of the serial number (13-17-th
+/// symbols, or 15-17-th ones for small manufacturers)
+pub(in crate) fn non_numeric_serial(vin: &[u8; 17]) -> Option<usize> {
+    let start = if vin[2] == b'9' { 14 } else { 12 };
+    (start..17).find(|i| !vin[*i].is_ascii_digit()).map(|i| i + 1)
+}
+
+/// Returns all swaps of adjacent symbols (0-based positions in the normalized VIN), which turn
+/// the VIN with invalid checksum into a valid one. If some of the swaps result in WMI known to
+/// the dictionary, the others are discarded.
+///
+/// Returns an empty list if the VIN is malformed or its checksum is already valid.
+pub fn transposition_candidates(vin: &str) -> Vec<(usize, usize)> {
+    let normalized = config::normalization().apply(vin);
+    let vin = uppercase(&normalized);
+    let bytes = match ascii_bytes(&vin) {
+        Ok(bytes) => *bytes,
+        Err(_) => return vec![],
+    };
+    match verify_normalized(&vin) {
+        Err(ChecksumError(_)) => (),
+        _ => return vec![],
+    }
+
+    let mut result = vec![];
+    for i in 0..bytes.len() - 1 {
+        if bytes[i] == bytes[i + 1] {
+            continue;
+        }
+
+        let mut swapped = bytes;
+        swapped.swap(i, i + 1);
+        // the bytes are ASCII, so they stay valid UTF-8 after the swap
+        let swapped = std::str::from_utf8(&swapped).expect("VIN consists of ASCII symbols");
+
+        if verify_checksum_unobserved(swapped).is_ok() {
+            result.push(((i, i + 1), dicts::get_manufacturer(&swapped[..3]).is_some()));
+        }
+    }
+
+    // swaps resulting in unknown WMI are considered only if there are no better options
+    let known_wmi = result.iter().any(|(_, known)| *known);
+    result
+        .into_iter()
+        .filter(|(_, known)| *known || !known_wmi)
+        .map(|(swap, _)| swap)
+        .collect()
+}
+
+
+/// Detects the swap of adjacent symbols (the most common manual data entry error), which would
+/// make the checksum valid. Returns 0-based positions of the swapped symbols, or `None` if
+/// there are no or several possible swaps (see [`transposition_candidates`](fn.transposition_candidates.html)).
+///
+/// # Examples
+/// ```
+/// assert_eq!(vin::detect_transposition("1M8GDMA9XKP042788"), Some((6, 7)));
+/// assert_eq!(vin::detect_transposition("1M8GDM9AXKP042788"), None);  // valid VIN
+/// ```
+pub fn detect_transposition(vin: &str) -> Option<(usize, usize)> {
+    match transposition_candidates(vin).as_slice() {
+        [swap] => Some(*swap),
+        _ => None,
+    }
+}
+
+
+/// Return basic information about manufacturer of the vehicle
+///
+/// Uses source, validation profile and normalization set by [`config::configure`](crate::config::configure).
+///
+/// # Examples
+/// ```
+/// let vin_number = "wp0zzz998ts392124";
+/// let result = vin::get_info(vin_number).unwrap();
+/// assert_eq!(result.as_str(), vin_number.to_uppercase());
+/// assert_eq!(result.country.unwrap(), "Germany/West Germany");
+/// assert_eq!(result.manufacturer.unwrap(), "Porsche car");
+/// assert_eq!(result.region.unwrap(), "Europe");
+/// assert!(result.valid_checksum.is_valid())
+/// ```
+pub fn get_info(vin: &str) -> Result<VIN, VINError> {
+    config::parser().parse(vin)
+}
+
+/// Parses VIN given as 17 uppercase ASCII bytes with the parser set by
+/// [`config::configure`](crate::config::configure), skipping the normalization
+/// (see [`VinParser::parse_unchecked_ascii`](crate::VinParser::parse_unchecked_ascii))
+///
+/// # Examples
+/// ```
+/// let result = vin::parse::parse_unchecked_ascii(b"1M8GDM9AXKP042788").unwrap();
+/// assert!(result.valid_checksum.is_valid());
+/// ```
+pub fn parse_unchecked_ascii(vin: &[u8; 17]) -> Result<VIN, VINError> {
+    config::parser().parse_unchecked_ascii(vin)
+}
@@ -0,0 +1,84 @@
+//! Weighted sum of VIN symbols used by the batch checksum verification.
+//!
+//! On x86_64 the first 16 symbols are transliterated and weighted at once with SSE2
+//! instructions (available on every x86_64 CPU), other targets use the scalar code.
+use crate::dicts::BYTE_VALUES;
+use crate::tables::WEIGHTS;
+
+/// Returns the weighted sum of symbols of the uppercase VIN, `None` if some of them are not allowed
+#[cfg(all(target_arch = "x86_64", target_feature = "sse2"))]
+pub(in crate) fn weighted_sum(vin: &[u8; 17]) -> Option<u32> {
+    // SAFETY: SSE2 is enabled for the target, only the first 16 bytes of the VIN are loaded
+    let head = unsafe { sse2::weighted_sum(vin) }?;
+    Some(head + scalar_sum(&vin[16..], &WEIGHTS[16..])?)
+}
+
+/// Returns the weighted sum of symbols of the uppercase VIN, `None` if some of them are not allowed
+#[cfg(not(all(target_arch = "x86_64", target_feature = "sse2")))]
+pub(in crate) fn weighted_sum(vin: &[u8; 17]) -> Option<u32> { scalar_sum(vin, &WEIGHTS) }
+
+fn scalar_sum(symbols: &[u8], weights: &[u32]) -> Option<u32> {
+    symbols.iter().zip(weights).try_fold(0, |sum, (symbol, weight)| match BYTE_VALUES[*symbol as usize] {
+        u8::MAX => None,
+        value => Some(sum + u32::from(value) * weight),
+    })
+}
+
+#[cfg(all(target_arch = "x86_64", target_feature = "sse2"))]
+mod sse2 {
+    use std::arch::x86_64::*;
+
+    use crate::tables::WEIGHTS;
+
+    /// Weights of the first 16 positions, as 16-bit lanes
+    const HEAD_WEIGHTS: [i16; 16] = head_weights();
+
+    const fn head_weights() -> [i16; 16] {
+        let mut result = [0; 16];
+        let mut i = 0;
+        while i < 16 {
+            result[i] = WEIGHTS[i] as i16;
+            i += 1;
+        }
+        result
+    }
+
+    /// Returns the weighted sum of the first 16 symbols of the uppercase VIN, `None` if some
+    /// of them are not allowed
+    pub(super) unsafe fn weighted_sum(vin: &[u8; 17]) -> Option<u32> {
+        let symbols = _mm_loadu_si128(vin.as_ptr() as *const __m128i);
+        // comparisons are signed, so non-ASCII bytes never fall into the ranges
+        let between = |from: u8, to: u8| _mm_and_si128(
+            _mm_cmpgt_epi8(symbols, _mm_set1_epi8(from as i8 - 1)),
+            _mm_cmplt_epi8(symbols, _mm_set1_epi8(to as i8 + 1)),
+        );
+        let equal = |symbol: u8| _mm_cmpeq_epi8(symbols, _mm_set1_epi8(symbol as i8));
+
+        // ranges of allowed symbols (I, O and Q are excluded) and offsets of their values
+        let ranges = [
+            (between(b'0', b'9'), b'0'),
+            (between(b'A', b'H'), b'A' - 1),
+            (_mm_andnot_si128(_mm_or_si128(equal(b'O'), equal(b'Q')), between(b'J', b'R')), b'J' - 1),
+            (between(b'S', b'Z'), b'S' - 2),
+        ];
+        let (mut allowed, mut values) = (_mm_setzero_si128(), _mm_setzero_si128());
+        for (mask, offset) in ranges.iter() {
+            allowed = _mm_or_si128(allowed, *mask);
+            values = _mm_or_si128(values, _mm_and_si128(*mask, _mm_sub_epi8(symbols, _mm_set1_epi8(*offset as i8))));
+        }
+        if _mm_movemask_epi8(allowed) != 0xffff {
+            return None;
+        }
+
+        // values are widened to 16 bits, multiplied by the weights and summed pairwise into 32 bits
+        let zero = _mm_setzero_si128();
+        let weights = HEAD_WEIGHTS.as_ptr() as *const __m128i;
+        let sums = _mm_add_epi32(
+            _mm_madd_epi16(_mm_unpacklo_epi8(values, zero), _mm_loadu_si128(weights)),
+            _mm_madd_epi16(_mm_unpackhi_epi8(values, zero), _mm_loadu_si128(weights.add(1))),
+        );
+        let mut lanes = [0i32; 4];
+        _mm_storeu_si128(lanes.as_mut_ptr() as *mut __m128i, sums);
+        Some(lanes.iter().sum::<i32>() as u32)
+    }
+}
@@ -0,0 +1,434 @@
+use std::fmt;
+use std::sync::Mutex;
+
+use crate::cache::LruCache;
+use crate::config::{Normalization, ValidationProfile};
+use crate::dicts::{check_digit_mandatory, get_region};
+#[cfg(any(feature = "metrics", feature = "tracing"))]
+use crate::observe;
+use crate::rules::{AllowedWmis, DecodeContext, DeniedWmis, Rule};
+use crate::source::{BuiltinSource, WmiSource};
+use crate::watchlist::Watchlist;
+use crate::parse::{ascii_bytes, check_digit_result, non_numeric_serial, observe_checksum, scan, uppercase};
+use crate::{ChecksumStatus, Clock, Finding, FullValidation, SystemClock, VINError, YearPolicy, YearWindow, VIN};
+
+/// Parser of VINs backed by the configurable source of manufacturers information
+///
+/// # Examples
+/// ```
+/// let parser = vin::VinParser::new();
+/// let result = parser.parse("wp0zzz998ts392124").unwrap();
+/// assert_eq!(result.manufacturer.unwrap(), "Porsche car");
+/// ```
+pub struct VinParser {
+    source: Box<dyn WmiSource>,
+    cache: Option<Mutex<LruCache<String, Decoded>>>,
+    profile: ValidationProfile,
+    normalization: Normalization,
+    watchlist: Option<Box<dyn Watchlist>>,
+    serial_heuristics: bool,
+    forbidden_letters: bool,
+    rules: Vec<Box<dyn Rule>>,
+    clock: Box<dyn Clock>,
+    year_policy: YearPolicy,
+}
+
+/// Describes suspicious, but valid VIN reported by [`VinParser::validate`](struct.VinParser.html#method.validate)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ValidationWarning {
+    /// Serial number (the last 6 symbols) consists of identical symbols, e.g. `000000`
+    IdenticalSerial,
+
+    /// Serial number (the last 6 symbols) consists of sequential digits, e.g. `123456` or `654321`
+    SequentialSerial,
+}
+
+/// Holds the decoded VIN together with results of optional checks
+/// (see [`VinParser::validate`](struct.VinParser.html#method.validate))
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ValidationReport {
+    /// Decoded VIN
+    pub vin: VIN,
+
+    /// Whether the VIN is listed in the watchlist, `None` if the parser has no watchlist
+    pub watchlisted: Option<bool>,
+
+    /// Whether the serial number of North American VIN is numeric, `None` for other regions
+    /// (see [`VIN::has_numeric_serial`](struct.VIN.html#method.has_numeric_serial))
+    pub numeric_serial: Option<bool>,
+
+    /// Warnings about suspicious VIN (e.g. placeholder serial number)
+    pub warnings: Vec<ValidationWarning>,
+
+    /// Findings of the custom rules (see [`VinParser::with_rule`](struct.VinParser.html#method.with_rule))
+    pub rule_findings: Vec<Finding>,
+}
+
+/// Manufacturer, country and region
+type Decoded = (Option<String>, Option<String>, Option<String>);
+
+impl VinParser {
+    /// Creates parser using built-in dictionary
+    pub fn new() -> VinParser { VinParser::with_source(BuiltinSource) }
+
+    /// Creates parser using the given source of manufacturers information
+    pub fn with_source<S: WmiSource + 'static>(source: S) -> VinParser {
+        VinParser {
+            source: Box::new(source),
+            cache: None,
+            profile: ValidationProfile::default(),
+            normalization: Normalization::default(),
+            watchlist: None,
+            serial_heuristics: true,
+            forbidden_letters: false,
+            rules: vec![],
+            clock: Box::new(SystemClock),
+            year_policy: YearPolicy::default(),
+        }
+    }
+
+    /// Sets validation profile
+    ///
+    /// # Examples
+    /// ```
+    /// use vin::config::ValidationProfile;
+    ///
+    /// let parser = vin::VinParser::new().with_profile(ValidationProfile::RequireChecksum);
+    /// assert!(parser.parse("WP0ZZZ99ZTS392124").is_err());
+    /// ```
+    pub fn with_profile(mut self, profile: ValidationProfile) -> VinParser {
+        self.profile = profile;
+        self
+    }
+
+    /// Sets normalization of the input
+    pub fn with_normalization(mut self, normalization: Normalization) -> VinParser {
+        self.normalization = normalization;
+        self
+    }
+
+    /// Sets watchlist checked by [`validate`](#method.validate)
+    pub fn with_watchlist<W: Watchlist + 'static>(mut self, watchlist: W) -> VinParser {
+        self.watchlist = Some(Box::new(watchlist));
+        self
+    }
+
+    /// Enables or disables warnings about placeholder serial numbers (enabled by default)
+    ///
+    /// # Examples
+    /// ```
+    /// use vin::{ValidationWarning, VinParser};
+    ///
+    /// let report = VinParser::new().validate("WP0ZZZ99ZTS000000").unwrap();
+    /// assert_eq!(report.warnings, [ValidationWarning::IdenticalSerial]);
+    /// let report = VinParser::new().with_serial_heuristics(false).validate("WP0ZZZ99ZTS000000").unwrap();
+    /// assert!(report.warnings.is_empty());
+    /// ```
+    pub fn with_serial_heuristics(mut self, enabled: bool) -> VinParser {
+        self.serial_heuristics = enabled;
+        self
+    }
+
+    /// Enables reporting of VINs, which contain only letters I, O and Q among invalid characters,
+    /// with [`VINError::ForbiddenLetters`](enum.VINError.html#variant.ForbiddenLetters) instead of
+    /// [`VINError::InvalidCharacters`](enum.VINError.html#variant.InvalidCharacters), e.g. to suggest
+    /// digits 1 and 0 to the user (disabled by default)
+    ///
+    /// # Examples
+    /// ```
+    /// use vin::{VINError, VinParser};
+    ///
+    /// let parser = VinParser::new().with_forbidden_letters(true);
+    /// assert!(matches!(parser.parse("1M8GDM9AXKP04278O"), Err(VINError::ForbiddenLetters(_))));
+    /// assert!(matches!(parser.parse("1M8GDM9AXKP0427$O"), Err(VINError::InvalidCharacters(_))));
+    /// ```
+    pub fn with_forbidden_letters(mut self, enabled: bool) -> VinParser {
+        self.forbidden_letters = enabled;
+        self
+    }
+
+    /// Sets source of the current year bounding the model years of the decoded VINs
+    /// (the system clock by default, see [`VIN::year_window`](struct.VIN.html#method.year_window))
+    ///
+    /// # Examples
+    /// ```
+    /// let parser = vin::VinParser::new().with_clock(|| 2020);
+    /// assert_eq!(parser.year_window().range(), 1980..=2022);
+    /// ```
+    pub fn with_clock<C: Clock + 'static>(mut self, clock: C) -> VinParser {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    /// Sets how many model years in advance of the current year are plausible for the decoded
+    /// VINs (2 by default, see [`VIN::year_window`](struct.VIN.html#method.year_window))
+    pub fn with_year_policy(mut self, policy: YearPolicy) -> VinParser {
+        self.year_policy = policy;
+        self
+    }
+
+    /// Adds custom rule checked by [`validate`](#method.validate) (see [`rules`](rules/index.html)),
+    /// rules are checked in the order of addition
+    pub fn with_rule<R: Rule + 'static>(mut self, rule: R) -> VinParser {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Adds rule reporting VINs with WMIs other than the given ones (case-insensitive) with
+    /// `E_WMI_NOT_ALLOWED` finding (see [`AllowedWmis`](rules/struct.AllowedWmis.html))
+    ///
+    /// # Examples
+    /// ```
+    /// let parser = vin::VinParser::new().allow_wmis(["1M8", "WP0"]).deny_wmis(["WP0"]);
+    /// assert!(parser.validate_full("1M8GDM9AXKP042788").is_valid());
+    /// assert_eq!(parser.validate_full("WP0ZZZ99ZTS392124").findings[0].id(), "E_WMI_DENIED");
+    /// assert_eq!(parser.validate_full("WP1ZZZ99ZTS392124").findings[0].id(), "E_WMI_NOT_ALLOWED");
+    /// ```
+    pub fn allow_wmis<I, S>(self, wmis: I) -> VinParser
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.with_rule(AllowedWmis::new(wmis))
+    }
+
+    /// Adds rule reporting VINs with the given WMIs (case-insensitive) with `E_WMI_DENIED` finding
+    /// (see [`DeniedWmis`](rules/struct.DeniedWmis.html))
+    pub fn deny_wmis<I, S>(self, wmis: I) -> VinParser
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.with_rule(DeniedWmis::new(wmis))
+    }
+
+    /// Returns validation profile
+    pub fn profile(&self) -> ValidationProfile { self.profile }
+
+    /// Returns normalization of the input
+    pub fn normalization(&self) -> Normalization { self.normalization }
+
+    /// Returns year policy
+    pub fn year_policy(&self) -> YearPolicy { self.year_policy }
+
+    /// Returns window of model years as of the current year of the clock, bounded by the year policy
+    pub fn year_window(&self) -> YearWindow { self.year_policy.window(self.clock.current_year()) }
+
+    /// Enables caching of decoded information for up to `capacity` squish VINs
+    /// (see [`SquishVin`](struct.SquishVin.html)), the least recently used ones are evicted.
+    ///
+    /// Useful for expensive sources and workloads with many VINs of the same models.
+    ///
+    /// # Examples
+    /// ```
+    /// let parser = vin::VinParser::new().with_cache(10_000);
+    /// assert_eq!(parser.parse("wp0zzz998ts392124").unwrap().manufacturer.unwrap(), "Porsche car");
+    /// assert_eq!(parser.cache_len(), 1);
+    /// ```
+    pub fn with_cache(mut self, capacity: usize) -> VinParser {
+        self.cache = Some(Mutex::new(LruCache::new(capacity)));
+        self
+    }
+
+    /// Returns count of cached squish VINs
+    pub fn cache_len(&self) -> usize {
+        self.cache.as_ref().and_then(|x| x.lock().ok()).map_or(0, |x| x.len())
+    }
+
+    /// Removes all cached information
+    pub fn clear_cache(&self) {
+        if let Some(mut cache) = self.cache.as_ref().and_then(|x| x.lock().ok()) {
+            cache.clear();
+        }
+    }
+
+    /// Returns the source of manufacturers information
+    pub fn source(&self) -> &dyn WmiSource { self.source.as_ref() }
+
+    /// Returns basic information about manufacturer of the vehicle
+    /// (see [`get_info`](fn.get_info.html))
+    pub fn parse(&self, vin: &str) -> Result<VIN, VINError> {
+        observe_parse(vin, || {
+            let normalized = self.normalization.apply(vin);
+            let vin = uppercase(&normalized);
+            self.parse_ascii(ascii_bytes(&vin)?)
+        })
+    }
+
+    /// Parses VIN given as 17 uppercase ASCII bytes, skipping the normalization (lowercase
+    /// symbols are reported as invalid). The alphabet and the checksum are validated in
+    /// a single pass over the symbols.
+    ///
+    /// # Examples
+    /// ```
+    /// let parser = vin::VinParser::new();
+    /// assert_eq!(parser.parse_unchecked_ascii(b"WP0ZZZ99ZTS392124").unwrap().manufacturer.unwrap(), "Porsche car");
+    /// assert!(parser.parse_unchecked_ascii(b"wp0zzz99zts392124").is_err());
+    /// ```
+    pub fn parse_unchecked_ascii(&self, vin: &[u8; 17]) -> Result<VIN, VINError> {
+        observe_parse(std::str::from_utf8(vin).unwrap_or_default(), || self.parse_ascii(vin))
+    }
+
+    /// Returns decoded VIN together with results of optional checks (e.g. watchlist matching)
+    pub fn validate(&self, input: &str) -> Result<ValidationReport, VINError> {
+        let vin = self.parse(input)?;
+        let watchlisted = self.watchlist.as_ref().map(|x| x.contains(&vin));
+        let numeric_serial = vin.has_numeric_serial();
+        let warnings = match self.serial_heuristics {
+            true => serial_warning(&vin.as_bytes()[11..]).into_iter().collect(),
+            false => vec![],
+        };
+        let ctx = DecodeContext { input, profile: self.profile, watchlisted, year_window: self.year_window() };
+        let rule_findings = self.rules.iter().filter_map(|x| x.check(&vin, &ctx)).collect();
+        Ok(ValidationReport { vin, watchlisted, numeric_serial, warnings, rule_findings })
+    }
+
+    /// Returns decoded VIN together with all findings (errors, warnings and notes) carrying
+    /// machine-readable codes
+    ///
+    /// # Examples
+    /// ```
+    /// let result = vin::VinParser::new().validate_full("AP0ZZZ99ZTS392124");
+    /// assert!(result.is_valid());
+    /// let ids: Vec<&str> = result.findings.iter().map(|x| x.id()).collect();
+    /// assert_eq!(ids, ["W_UNKNOWN_WMI", "I_CHECKSUM_NOT_APPLICABLE"]);
+    /// ```
+    pub fn validate_full(&self, vin: &str) -> FullValidation {
+        match self.validate(vin) {
+            Ok(report) => {
+                let mut findings = Finding::from_report(&report);
+                findings.sort_by_key(|x| x.severity);
+                FullValidation { report: Some(report), findings }
+            }
+            Err(err) => FullValidation { report: None, findings: vec![Finding::from_error(&err)] },
+        }
+    }
+
+    /// Returns information about the VIN consisting of allowed symbols (e.g. generated or
+    /// restored from the storage) without validating it against the profile
+    pub(in crate) fn restore(&self, bytes: [u8; 17]) -> VIN {
+        // allowed symbols are ASCII
+        let vin = std::str::from_utf8(&bytes).unwrap_or_default();
+        let (manufacturer, country, region) = self.decode(vin);
+
+        let valid_checksum = match scan(&bytes).and_then(|x| check_digit_result(&bytes, x)) {
+            Ok(()) => ChecksumStatus::Valid,
+            Err(VINError::ChecksumError(info)) if check_digit_mandatory(&vin[..1]) => ChecksumStatus::Invalid(info),
+            Err(_) => ChecksumStatus::NotApplicable,
+        };
+
+        VIN { vin: bytes, country, manufacturer, region, valid_checksum, year_window: self.year_window() }
+    }
+
+    fn parse_ascii(&self, bytes: &[u8; 17]) -> Result<VIN, VINError> {
+        let contributions = scan(bytes).map_err(|err| self.tailor(err))?;
+        // validated VIN consists of 17 ASCII characters
+        let vin = std::str::from_utf8(bytes).unwrap_or_default();
+
+        let (manufacturer, country, region) = self.decode(vin);
+
+        let valid_checksum = match observe_checksum(vin, || check_digit_result(bytes, contributions)) {
+            Err(VINError::ChecksumError(info)) if self.profile.requires_checksum(vin) =>
+                return Err(VINError::ChecksumError(info)),
+            Err(VINError::ChecksumError(info)) if check_digit_mandatory(&vin[..1]) => ChecksumStatus::Invalid(info),
+            Err(VINError::ChecksumError(_)) => ChecksumStatus::NotApplicable,
+            _ => ChecksumStatus::Valid,
+        };
+        if self.profile.requires_numeric_serial(vin) {
+            if let Some(position) = non_numeric_serial(bytes) {
+                return Err(VINError::NonNumericSerial(position));
+            }
+        }
+
+        Ok(VIN { vin: *bytes, country, manufacturer, region, valid_checksum, year_window: self.year_window() })
+    }
+
+    /// Reports confusable letters with the dedicated error, if enabled
+    fn tailor(&self, err: VINError) -> VINError {
+        match err {
+            VINError::InvalidCharacters(chars) if self.forbidden_letters && chars.iter().all(|x| x.confusable) =>
+                VINError::ForbiddenLetters(chars),
+            err => err,
+        }
+    }
+
+    /// Returns decoded information about the valid uppercased VIN, consulting the cache first
+    fn decode(&self, vin: &str) -> Decoded {
+        let cache = match &self.cache {
+            Some(cache) => cache,
+            None => return self.lookup(vin),
+        };
+
+        // small manufacturers are identified by the 12-14-th characters as well
+        let squish = match &vin[2..3] {
+            "9" => format!("{}{}{}", &vin[..8], &vin[9..11], &vin[11..14]),
+            _ => format!("{}{}", &vin[..8], &vin[9..11]),
+        };
+        if let Some(decoded) = cache.lock().ok().and_then(|mut x| x.get(&squish)) {
+            return decoded;
+        }
+        let decoded = self.lookup(vin);
+        if let Ok(mut cache) = cache.lock() {
+            cache.insert(squish, decoded.clone());
+        }
+        decoded
+    }
+
+    fn lookup(&self, vin: &str) -> Decoded {
+        match self.source.lookup_vin(vin) {
+            Some(record) => (Some(record.manufacturer), record.country, record.region),
+            None => (None, self.source.lookup_country(&vin[..2]), get_region(&vin[..1])),
+        }
+    }
+}
+
+/// Instruments the parsing of the VIN
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+fn observe_parse<F>(vin: &str, parse: F) -> Result<VIN, VINError>
+where
+    F: FnOnce() -> Result<VIN, VINError>,
+{
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("parse", wmi = observe::wmi(vin)).entered();
+
+    let result = parse();
+
+    #[cfg(feature = "tracing")]
+    tracing::debug!(outcome = observe::parse_outcome(&result), "VIN parsed");
+    #[cfg(feature = "metrics")]
+    metrics::counter!(observe::PARSED_COUNTER, "outcome" => observe::parse_outcome(&result)).increment(1);
+    result
+}
+
+/// Returns warning about placeholder serial number
+fn serial_warning(serial: &[u8]) -> Option<ValidationWarning> {
+    let steps: Vec<i16> = serial.windows(2).map(|x| i16::from(x[1]) - i16::from(x[0])).collect();
+    if steps.iter().all(|x| *x == 0) {
+        Some(ValidationWarning::IdenticalSerial)
+    } else if serial.iter().all(u8::is_ascii_digit) && (steps.iter().all(|x| *x == 1) || steps.iter().all(|x| *x == -1)) {
+        Some(ValidationWarning::SequentialSerial)
+    } else {
+        None
+    }
+}
+
+impl Default for VinParser {
+    fn default() -> Self { VinParser::new() }
+}
+
+impl fmt::Debug for VinParser {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("VinParser")
+            .field("profile", &self.profile)
+            .field("normalization", &self.normalization)
+            .field("cached", &self.cache_len())
+            .field("watchlist", &self.watchlist.is_some())
+            .field("serial_heuristics", &self.serial_heuristics)
+            .field("forbidden_letters", &self.forbidden_letters)
+            .field("rules", &self.rules.len())
+            .field("year_policy", &self.year_policy)
+            .finish()
+    }
+}
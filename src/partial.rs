@@ -0,0 +1,111 @@
+//! Utilities for partial VINs.
+//!
+//! Police reports, auction sheets and similar documents frequently contain only the last
+//! 6-8 characters of the VIN. This module allows to match such suffixes against full numbers.
+//!
+//! # Examples
+//! ```
+//! use vin::partial::{PartialIndex, PartialVin};
+//!
+//! let partial = PartialVin::parse("...042788").unwrap();
+//! assert!(partial.matches("1M8GDM9AXKP042788"));
+//!
+//! let mut index = PartialIndex::new();
+//! index.insert("1M8GDM9AXKP042788").unwrap();
+//! index.insert("WP0ZZZ99ZTS392124").unwrap();
+//! assert_eq!(index.find(&partial), vec!["1M8GDM9AXKP042788"]);
+//! ```
+use std::collections::BTreeMap;
+
+use crate::parse::validated;
+use crate::VINError::{self, IncorrectLength, InvalidCharacters};
+use crate::InvalidChar;
+
+/// Characters that may be used to denote the omitted part of the VIN
+const ELLIPSIS: &[char] = &['.', '…', '*'];
+
+/// Holds trailing part of the VIN
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PartialVin {
+    suffix: String,
+}
+
+impl PartialVin {
+    /// Parses partial VIN, ignoring leading ellipsis (`...`, `…` or `*`) and whitespaces
+    ///
+    /// # Examples
+    /// ```
+    /// let partial = vin::partial::PartialVin::parse("…kp042788").unwrap();
+    /// assert_eq!(partial.as_str(), "KP042788");
+    /// ```
+    pub fn parse(partial: &str) -> Result<PartialVin, VINError> {
+        let suffix = partial
+            .trim()
+            .trim_start_matches(ELLIPSIS)
+            .trim_start()
+            .to_uppercase();
+
+        // check length
+        let length = suffix.chars().count();
+        if length == 0 || length > 17 {
+            return Err(IncorrectLength);
+        }
+
+        // check alphabet
+        let odd_chars = InvalidChar::find(&suffix, &[]);
+        if !odd_chars.is_empty() {
+            return Err(InvalidCharacters(odd_chars));
+        }
+
+        Ok(PartialVin { suffix })
+    }
+
+    /// Returns known trailing part of the VIN
+    pub fn as_str(&self) -> &str { &self.suffix }
+
+    /// Returns count of known characters
+    pub fn len(&self) -> usize { self.suffix.len() }
+
+    /// Returns whether no characters are known (never true for parsed partial VIN)
+    pub fn is_empty(&self) -> bool { self.suffix.is_empty() }
+
+    /// Returns whether the given full VIN ends with this partial VIN
+    pub fn matches(&self, vin: &str) -> bool {
+        vin.to_uppercase().ends_with(&self.suffix)
+    }
+}
+
+/// Index of full VINs allowing to efficiently find all numbers ending with the given suffix
+#[derive(Debug, Clone, Default)]
+pub struct PartialIndex {
+    // reversed VIN -> VIN, so that suffix search becomes a prefix range scan
+    vins: BTreeMap<String, String>,
+}
+
+impl PartialIndex {
+    /// Creates an empty index
+    pub fn new() -> PartialIndex { PartialIndex::default() }
+
+    /// Adds valid VIN to the index
+    pub fn insert(&mut self, vin: &str) -> Result<(), VINError> {
+        let vin = validated(vin)?;
+        self.vins.insert(vin.chars().rev().collect(), vin);
+        Ok(())
+    }
+
+    /// Returns count of indexed VINs
+    pub fn len(&self) -> usize { self.vins.len() }
+
+    /// Returns whether the index is empty
+    pub fn is_empty(&self) -> bool { self.vins.is_empty() }
+
+    /// Returns all indexed VINs matching the given partial VIN
+    pub fn find(&self, partial: &PartialVin) -> Vec<&str> {
+        let key: String = partial.suffix.chars().rev().collect();
+        self.vins
+            .range(key.clone()..)
+            .take_while(|(reversed, _)| reversed.starts_with(&key))
+            .map(|(_, vin)| vin.as_str())
+            .collect()
+    }
+}
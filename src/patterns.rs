@@ -0,0 +1,334 @@
+//! Matching of VINs against pattern rules (e.g. for recall and warranty applicability checks).
+//!
+//! Pattern is matched position by position against the beginning of the VIN:
+//! * an allowed VIN character matches itself;
+//! * `*` or `?` matches any character;
+//! * `(A|B|C)` matches any of the listed characters.
+//!
+//! Pattern may be shorter than 17 characters, in which case the rest of the VIN is not checked.
+//!
+//! # Examples
+//! ```
+//! use vin::patterns::{PatternRule, PatternSet};
+//!
+//! let mut set = PatternSet::new();
+//! set.add(PatternRule::new("20V-123", "1M8*DM(8|9)A").unwrap());
+//! set.add(PatternRule::new("21V-456", "WP0").unwrap().with_years(2000..=2010));
+//!
+//! let result = vin::get_info("1M8GDM9AXKP042788").unwrap();
+//! let ids: Vec<&str> = set.find(&result).iter().map(|x| x.id.as_str()).collect();
+//! assert_eq!(ids, vec!["20V-123"]);
+//! ```
+use std::fmt;
+use std::io::{self, BufRead};
+use std::ops::RangeInclusive;
+
+use crate::tables::{is_allowed, ALLOWED_CHARS};
+use crate::VIN;
+
+/// Provides possible errors during pattern parsing
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum PatternError {
+    /// Pattern is empty or describes more than 17 positions
+    IncorrectLength,
+
+    /// Pattern contains character which can not be used in VIN
+    InvalidCharacter(char),
+
+    /// Alternatives group is not closed, empty or has no alternatives separated by `|`
+    InvalidGroup,
+
+    /// Year range can not be parsed
+    InvalidYears(String),
+
+    /// Line of the rules file can not be parsed (contains number of the line)
+    InvalidLine(usize),
+
+    /// Rules can not be read
+    Io(io::Error),
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PatternError::IncorrectLength =>
+                write!(f, "Incorrect length of given pattern, 1 to 17 positions expected."),
+            PatternError::InvalidCharacter(ch) =>
+                write!(f, "Invalid character received in given pattern: {:?}.", ch),
+            PatternError::InvalidGroup =>
+                write!(f, "Alternatives group is empty, not closed or has no alternatives."),
+            PatternError::InvalidYears(years) =>
+                write!(f, "Invalid year range: {:?}, YYYY-YYYY expected.", years),
+            PatternError::InvalidLine(line) =>
+                write!(f, "Invalid rule at line {}.", line),
+            PatternError::Io(err) =>
+                write!(f, "Rules can not be read: {}.", err),
+        }
+    }
+}
+
+impl From<io::Error> for PatternError {
+    fn from(err: io::Error) -> Self { PatternError::Io(err) }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Matcher {
+    Any,
+    OneOf(Vec<char>),
+}
+
+impl Matcher {
+    fn matches(&self, ch: char) -> bool {
+        match self {
+            Matcher::Any => true,
+            Matcher::OneOf(chars) => chars.contains(&ch),
+        }
+    }
+
+    fn char_set(&self) -> CharSet {
+        match self {
+            Matcher::Any => ANY_CHAR,
+            Matcher::OneOf(chars) => chars.iter().fold(0, |set, x| set | char_bit(*x)),
+        }
+    }
+}
+
+/// Set of characters matched at the position, bit `i` stands for `ALLOWED_CHARS[i]`
+type CharSet = u64;
+
+/// Set matching any character, including the ones not allowed in VIN
+const ANY_CHAR: CharSet = CharSet::MAX;
+
+/// Returns set of the single character, characters not allowed in VIN are matched only by `*`
+fn char_bit(ch: char) -> CharSet {
+    match ALLOWED_CHARS.iter().position(|x| *x == ch) {
+        Some(i) => 1 << i,
+        None => 1 << 63,
+    }
+}
+
+/// Holds parsed wildcard pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    source: String,
+    matchers: Vec<Matcher>,
+}
+
+impl Pattern {
+    /// Parses the pattern (see [module documentation](index.html) for syntax)
+    pub fn parse(pattern: &str) -> Result<Pattern, PatternError> {
+        let source = pattern.trim().to_uppercase();
+        let mut matchers = vec![];
+        let mut chars = source.chars();
+
+        while let Some(ch) = chars.next() {
+            let matcher = match ch {
+                '*' | '?' => Matcher::Any,
+                '(' => {
+                    let mut group = vec![];
+                    let mut alternatives = false;
+                    loop {
+                        match chars.next() {
+                            Some(')') => break,
+                            Some('|') => alternatives = true,
+                            Some(x) if is_allowed(x) => group.push(x),
+                            Some(x) => return Err(PatternError::InvalidCharacter(x)),
+                            None => return Err(PatternError::InvalidGroup),
+                        }
+                    }
+                    if group.is_empty() || !alternatives {
+                        return Err(PatternError::InvalidGroup);
+                    }
+                    Matcher::OneOf(group)
+                }
+                x if is_allowed(x) => Matcher::OneOf(vec![x]),
+                x => return Err(PatternError::InvalidCharacter(x)),
+            };
+            matchers.push(matcher);
+        }
+
+        if matchers.is_empty() || matchers.len() > 17 {
+            return Err(PatternError::IncorrectLength);
+        }
+
+        Ok(Pattern { source, matchers })
+    }
+
+    /// Returns the pattern as it was provided (uppercased)
+    pub fn as_str(&self) -> &str { &self.source }
+
+    /// Returns whether the given VIN matches the pattern
+    pub fn matches(&self, vin: &str) -> bool {
+        let vin = vin.to_uppercase();
+        vin.chars().count() == 17 && self.matchers
+            .iter()
+            .zip(vin.chars())
+            .all(|(m, ch)| m.matches(ch))
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+/// Holds pattern with its identifier and optional model years limitation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternRule {
+    /// Identifier of the rule (e.g. recall campaign number)
+    pub id: String,
+
+    /// Pattern to match
+    pub pattern: Pattern,
+
+    /// Model years the rule is applicable to (any years, if absent)
+    pub years: Option<RangeInclusive<u32>>,
+}
+
+impl PatternRule {
+    /// Creates rule applicable to any model year
+    pub fn new(id: &str, pattern: &str) -> Result<PatternRule, PatternError> {
+        Ok(PatternRule {
+            id: id.to_string(),
+            pattern: Pattern::parse(pattern)?,
+            years: None,
+        })
+    }
+
+    /// Limits the rule to the given model years
+    pub fn with_years(mut self, years: RangeInclusive<u32>) -> PatternRule {
+        self.years = Some(years);
+        self
+    }
+
+    /// Returns whether the decoded VIN matches the rule (at least one of possible model years
+    /// must fall into the rule years)
+    pub fn matches(&self, vin: &VIN) -> bool {
+        self.pattern.matches(vin.as_str()) && self.years_match(vin)
+    }
+
+    fn years_match(&self, vin: &VIN) -> bool {
+        match &self.years {
+            Some(years) => !vin.years_in(years.clone()).is_empty(),
+            None => true,
+        }
+    }
+}
+
+// every rule is a single path of the tree, patterns sharing the leading positions share nodes
+#[derive(Debug, Clone, Default)]
+struct Node {
+    children: Vec<(CharSet, Node)>,
+    rules: Vec<usize>,
+}
+
+impl Node {
+    fn insert(&mut self, matchers: &[Matcher], rule: usize) {
+        match matchers.split_first() {
+            None => self.rules.push(rule),
+            Some((matcher, rest)) => self.child(matcher.char_set()).insert(rest, rule),
+        }
+    }
+
+    fn child(&mut self, set: CharSet) -> &mut Node {
+        let i = match self.children.iter().position(|(x, _)| *x == set) {
+            Some(i) => i,
+            None => {
+                self.children.push((set, Node::default()));
+                self.children.len() - 1
+            }
+        };
+        &mut self.children[i].1
+    }
+
+    fn collect(&self, vin: &[CharSet], result: &mut Vec<usize>) {
+        result.extend(&self.rules);
+        if let Some((ch, rest)) = vin.split_first() {
+            for (set, node) in &self.children {
+                if set & ch != 0 {
+                    node.collect(rest, result);
+                }
+            }
+        }
+    }
+}
+
+/// Set of pattern rules indexed for fast matching of a VIN against thousands of patterns
+#[derive(Debug, Clone, Default)]
+pub struct PatternSet {
+    rules: Vec<PatternRule>,
+    root: Node,
+}
+
+impl PatternSet {
+    /// Creates an empty set
+    pub fn new() -> PatternSet { PatternSet::default() }
+
+    /// Loads rules from the reader.
+    ///
+    /// Every non-empty line, not starting with `#`, must contain whitespace separated identifier,
+    /// pattern and optional years range, e.g. `20V-123 1FT*W2(A|B) 2017-2019`.
+    pub fn load<R: BufRead>(reader: R) -> Result<PatternSet, PatternError> {
+        let mut set = PatternSet::new();
+
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            let rule = match parts.as_slice() {
+                [id, pattern] => PatternRule::new(id, pattern)?,
+                [id, pattern, years] => PatternRule::new(id, pattern)?.with_years(parse_years(years)?),
+                _ => return Err(PatternError::InvalidLine(i + 1)),
+            };
+            set.add(rule);
+        }
+
+        Ok(set)
+    }
+
+    /// Adds the rule to the set
+    pub fn add(&mut self, rule: PatternRule) {
+        self.root.insert(&rule.pattern.matchers, self.rules.len());
+        self.rules.push(rule);
+    }
+
+    /// Returns all rules of the set
+    pub fn rules(&self) -> &[PatternRule] { &self.rules }
+
+    /// Returns count of rules in the set
+    pub fn len(&self) -> usize { self.rules.len() }
+
+    /// Returns whether the set is empty
+    pub fn is_empty(&self) -> bool { self.rules.is_empty() }
+
+    /// Returns rules matching the decoded VIN, in the order they were added
+    pub fn find(&self, vin: &VIN) -> Vec<&PatternRule> {
+        let chars: Vec<CharSet> = vin.as_str().chars().map(char_bit).collect();
+        let mut ids = vec![];
+        self.root.collect(&chars, &mut ids);
+        ids.sort_unstable();
+        ids.dedup();
+
+        ids.into_iter()
+            .map(|i| &self.rules[i])
+            .filter(|x| x.years_match(vin))
+            .collect()
+    }
+}
+
+fn parse_years(years: &str) -> Result<RangeInclusive<u32>, PatternError> {
+    let invalid = || PatternError::InvalidYears(years.to_string());
+    let mut parts = years.splitn(2, '-');
+    let from = parts.next().and_then(|x| x.parse().ok()).ok_or_else(invalid)?;
+    let to = match parts.next() {
+        Some(x) => x.parse().map_err(|_| invalid())?,
+        None => from,
+    };
+    Ok(from..=to)
+}
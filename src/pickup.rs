@@ -0,0 +1,75 @@
+//! Decoding of series, cab and driveline of North American pickups.
+//!
+//! Ford encodes cab, series and driveline in 5-7 positions of F-series VINs, GM uses
+//! the 5-th position for the driveline (`C` for 4x2 and `K` for 4x4). Covered VIN prefixes
+//! are listed in `data/pickups.csv`.
+//!
+//! # Examples
+//! ```
+//! use vin::pickup::{Cab, Drive};
+//!
+//! let info = vin::get_info("1FTFW1ET5DFC10312").unwrap().pickup_info().unwrap();
+//! assert_eq!(info.model, Some("F-150"));
+//! assert_eq!(info.cab, Some(Cab::Crew));
+//! assert_eq!(info.drive, Some(Drive::FourWheel));
+//! ```
+use crate::dicts::{match_pattern, PICKUP_TABLE};
+use crate::VIN;
+
+/// Describes cab of the pickup
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Cab {
+    /// Regular cab with one row of seats
+    Regular,
+
+    /// Extended cab (Ford SuperCab, GM Double Cab, RAM Quad Cab)
+    Extended,
+
+    /// Crew cab with four full doors (Ford SuperCrew)
+    Crew,
+}
+
+/// Describes driveline of the pickup
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Drive {
+    /// Two-wheel drive (4x2)
+    TwoWheel,
+
+    /// Four-wheel drive (4x4)
+    FourWheel,
+}
+
+/// Holds decoded series, cab and driveline of the pickup
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct PickupInfo {
+    /// Model (series), if known
+    pub model: Option<&'static str>,
+
+    /// Cab, if known
+    pub cab: Option<Cab>,
+
+    /// Driveline, if known
+    pub drive: Option<Drive>,
+}
+
+impl VIN {
+    /// Returns decoded information about the pickup, or `None` if the VIN is not covered by the table
+    pub fn pickup_info(&self) -> Option<PickupInfo> {
+        match_pattern(PICKUP_TABLE, |x| x.0, self.as_bytes()).map(|(_, model, cab, drive)| PickupInfo {
+            model: if model.is_empty() { None } else { Some(model) },
+            cab: match *cab {
+                "regular" => Some(Cab::Regular),
+                "extended" => Some(Cab::Extended),
+                "crew" => Some(Cab::Crew),
+                _ => None,
+            },
+            drive: match *drive {
+                "4x2" => Some(Drive::TwoWheel),
+                "4x4" => Some(Drive::FourWheel),
+                _ => None,
+            },
+        })
+    }
+}
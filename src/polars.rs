@@ -0,0 +1,72 @@
+//! Decoding of Polars series.
+//!
+//! [`decode_series`] maps the string series of VINs to the struct series of decoded fields
+//! (the same fields as in [`arrow::DecodedBatch`](../arrow/struct.DecodedBatch.html)), so it can be
+//! used directly as the function of `map` expressions and unnested afterwards.
+//!
+//! # Examples
+//! ```
+//! use polars_core::prelude::*;
+//!
+//! let vins = Series::new("vin".into(), &[Some("wp0zzz998ts392124"), None]);
+//! let decoded = vin::polars::decode_series(&vins).unwrap();
+//!
+//! let fields = decoded.struct_().unwrap().fields_as_series();
+//! assert_eq!(fields[1].str().unwrap().get(0), Some("Porsche car"));
+//! assert_eq!(fields[0].bool().unwrap().get(1), Some(false));
+//! ```
+use polars_core::prelude::*;
+
+use crate::{get_info, ChecksumStatus};
+
+/// Decodes the string series of VINs into the struct series with the same name and fields
+/// `valid`, `manufacturer`, `country`, `region`, `model_year` and `checksum_ok`.
+///
+/// Rows with null or invalid VINs are null in all fields but `valid`. `model_year` is the most
+/// likely model year, it's null if the year is ambiguous.
+pub fn decode_series(vins: &Series) -> PolarsResult<Series> {
+    let vins = vins.str()?;
+    let len = vins.len();
+    let mut valid: Vec<bool> = Vec::with_capacity(len);
+    let mut manufacturer: Vec<Option<String>> = Vec::with_capacity(len);
+    let mut country: Vec<Option<String>> = Vec::with_capacity(len);
+    let mut region: Vec<Option<String>> = Vec::with_capacity(len);
+    let mut model_year: Vec<Option<u32>> = Vec::with_capacity(len);
+    let mut checksum_ok: Vec<Option<bool>> = Vec::with_capacity(len);
+
+    for chunk in vins.downcast_iter() {
+        for vin in chunk.iter() {
+            match vin.map(get_info) {
+                Some(Ok(vin)) => {
+                    valid.push(true);
+                    model_year.push(vin.years().best_guess);
+                    checksum_ok.push(match vin.valid_checksum {
+                        ChecksumStatus::NotApplicable => None,
+                        status => Some(status.is_valid()),
+                    });
+                    manufacturer.push(vin.manufacturer);
+                    country.push(vin.country);
+                    region.push(vin.region);
+                }
+                _ => {
+                    valid.push(false);
+                    manufacturer.push(None);
+                    country.push(None);
+                    region.push(None);
+                    model_year.push(None);
+                    checksum_ok.push(None);
+                }
+            }
+        }
+    }
+
+    let fields = [
+        Series::new("valid".into(), valid),
+        Series::new("manufacturer".into(), manufacturer),
+        Series::new("country".into(), country),
+        Series::new("region".into(), region),
+        Series::new("model_year".into(), model_year),
+        Series::new("checksum_ok".into(), checksum_ok),
+    ];
+    Ok(StructChunked::from_series(vins.name().clone(), len, fields.iter())?.into_series())
+}
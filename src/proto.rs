@@ -0,0 +1,93 @@
+//! Protobuf messages for exchanging decoding results between services.
+//!
+//! The messages are described by `proto/vin.proto` in the repository, which should be used
+//! to generate types for other languages. Rust types below mirror the schema, names and
+//! numbers of their fields are compared with it by the tests.
+//!
+//! # Examples
+//! ```
+//! use prost::Message;
+//! use vin::proto::DecodedVin;
+//!
+//! let decoded = DecodedVin::from(&vin::get_info("wp0zzz998ts392124").unwrap());
+//! let bytes = decoded.encode_to_vec();
+//!
+//! let received = DecodedVin::decode(bytes.as_slice()).unwrap();
+//! assert_eq!(received.manufacturer.as_deref(), Some("Porsche car"));
+//! assert!(received.checksum_error.is_none());
+//! ```
+use std::convert::TryFrom;
+
+use crate::{get_info, ChecksumStatus, VINError, VIN};
+
+/// Describes failed checksum validation (`vin.v1.ChecksumError`)
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct ChecksumError {
+    /// Expected check digit
+    #[prost(string, tag = "1")]
+    pub expected: String,
+
+    /// Check digit found in the VIN
+    #[prost(string, tag = "2")]
+    pub received: String,
+}
+
+/// Holds decoded information about the vehicle (`vin.v1.DecodedVin`)
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct DecodedVin {
+    /// Validated and uppercased VIN
+    #[prost(string, tag = "1")]
+    pub vin: String,
+
+    /// Name of the manufacturer, if known
+    #[prost(string, optional, tag = "2")]
+    pub manufacturer: Option<String>,
+
+    /// Country of the manufacturer, if known
+    #[prost(string, optional, tag = "3")]
+    pub country: Option<String>,
+
+    /// Region of the manufacturer, if known
+    #[prost(string, optional, tag = "4")]
+    pub region: Option<String>,
+
+    /// Absent if the checksum is valid or not applicable
+    #[prost(message, optional, tag = "5")]
+    pub checksum_error: Option<ChecksumError>,
+
+    /// Possible model years, in ascending order
+    #[prost(uint32, repeated, tag = "6")]
+    pub years: Vec<u32>,
+
+    /// Whether the checksum is invalid, but the region does not mandate the check digit
+    #[prost(bool, tag = "7")]
+    pub checksum_not_applicable: bool,
+}
+
+impl From<&VIN> for DecodedVin {
+    fn from(vin: &VIN) -> Self {
+        DecodedVin {
+            vin: vin.as_str().to_owned(),
+            manufacturer: vin.manufacturer.clone(),
+            country: vin.country.clone(),
+            region: vin.region.clone(),
+            checksum_not_applicable: matches!(vin.valid_checksum, ChecksumStatus::NotApplicable),
+            checksum_error: vin.valid_checksum.error().map(|err| ChecksumError {
+                expected: err.expected.to_string(),
+                received: err.received.to_string(),
+            }),
+            years: vin.years().candidates,
+        }
+    }
+}
+
+impl From<VIN> for DecodedVin {
+    fn from(vin: VIN) -> Self { DecodedVin::from(&vin) }
+}
+
+/// Decodes the received VIN again, ignoring other fields of the message
+impl TryFrom<&DecodedVin> for VIN {
+    type Error = VINError;
+
+    fn try_from(decoded: &DecodedVin) -> Result<Self, Self::Error> { get_info(&decoded.vin) }
+}
@@ -0,0 +1,182 @@
+//! Offline lookup of safety recalls.
+//!
+//! Recall campaigns are matched against the VIN by the squish VIN (see
+//! [`SquishVin`](../struct.SquishVin.html)) and the model year, so the serial number is ignored.
+//!
+//! Recalls can be loaded from tab-separated files with `CAMPNO`, squish VIN, model year,
+//! component and summary columns (see [`RecallDb::load`]), or from NHTSA `FLAT_RCL.txt`
+//! flat files (see [`RecallDb::load_nhtsa`]), which describe vehicles by make and model only,
+//! so the caller provides squish VINs of the models.
+//!
+//! # Examples
+//! ```
+//! use vin::recalls::RecallDb;
+//!
+//! let data = "20V123000\t1M8GDM9AKP\t2019\tAIR BAGS\tAir bag may not deploy\n";
+//! let db = RecallDb::load(data.as_bytes()).unwrap();
+//!
+//! let vin = vin::get_info("1M8GDM9AXKP042788").unwrap();
+//! let recalls = db.recalls_for(&vin);
+//! assert_eq!(recalls.len(), 1);
+//! assert_eq!(recalls[0].campaign, "20V123000");
+//! ```
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, BufRead};
+
+use crate::{SquishVin, VIN};
+
+/// Holds recall campaign affecting vehicles of one squish VIN and model year
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recall {
+    /// Campaign number, e.g. `20V123000`
+    pub campaign: String,
+
+    /// Squish VIN of affected vehicles
+    pub squish: SquishVin,
+
+    /// Model year of affected vehicles
+    pub model_year: u32,
+
+    /// Affected component
+    pub component: String,
+
+    /// Description of the defect
+    pub summary: String,
+}
+
+/// Provides possible errors during recalls loading
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum RecallError {
+    /// Recalls can not be read
+    Io(io::Error),
+
+    /// Line has unexpected number of fields or malformed model year (contains the line number)
+    InvalidLine(usize),
+
+    /// Squish VIN is malformed (contains the line number and the squish VIN)
+    InvalidSquish(usize, String),
+}
+
+impl fmt::Display for RecallError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RecallError::Io(err) =>
+                write!(f, "Recalls can not be read: {}.", err),
+            RecallError::InvalidLine(line) =>
+                write!(f, "Invalid recall at line {}.", line),
+            RecallError::InvalidSquish(line, squish) =>
+                write!(f, "Invalid squish VIN {:?} at line {}.", squish, line),
+        }
+    }
+}
+
+impl std::error::Error for RecallError {}
+
+impl From<io::Error> for RecallError {
+    fn from(err: io::Error) -> Self { RecallError::Io(err) }
+}
+
+/// Columns of NHTSA `FLAT_RCL.txt`: campaign, make, model, model year, component, defect
+const NHTSA_COLUMNS: [usize; 6] = [1, 2, 3, 4, 6, 19];
+
+/// Holds recalls indexed by squish VIN
+#[derive(Debug, Clone, Default)]
+pub struct RecallDb {
+    recalls: HashMap<SquishVin, Vec<Recall>>,
+    count: usize,
+}
+
+impl RecallDb {
+    /// Creates empty database
+    pub fn new() -> RecallDb { RecallDb::default() }
+
+    /// Loads recalls from tab-separated lines `campaign`, `squish VIN`, `model year`,
+    /// `component`, `summary`; empty lines are skipped
+    pub fn load<R: BufRead>(reader: R) -> Result<RecallDb, RecallError> {
+        let mut db = RecallDb::new();
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() != 5 {
+                return Err(RecallError::InvalidLine(i + 1));
+            }
+            let squish = SquishVin::parse(fields[1])
+                .map_err(|_| RecallError::InvalidSquish(i + 1, fields[1].to_owned()))?;
+            let model_year = fields[2].parse().map_err(|_| RecallError::InvalidLine(i + 1))?;
+
+            db.insert(Recall {
+                campaign: fields[0].to_owned(),
+                squish,
+                model_year,
+                component: fields[3].to_owned(),
+                summary: fields[4].to_owned(),
+            });
+        }
+        Ok(db)
+    }
+
+    /// Loads recalls from NHTSA `FLAT_RCL.txt`, `squishes` returns squish VINs of vehicles
+    /// by make, model and model year. Records with unknown model year (`9999`) are skipped.
+    pub fn load_nhtsa<R, F>(reader: R, mut squishes: F) -> Result<RecallDb, RecallError>
+    where
+        R: BufRead,
+        F: FnMut(&str, &str, u32) -> Vec<SquishVin>,
+    {
+        let mut db = RecallDb::new();
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() <= NHTSA_COLUMNS[5] {
+                return Err(RecallError::InvalidLine(i + 1));
+            }
+            let [campaign, make, model, year, component, summary] = NHTSA_COLUMNS.map(|x| fields[x].trim());
+            let model_year = match year.parse() {
+                Ok(9999) => continue,
+                Ok(year) => year,
+                Err(_) => return Err(RecallError::InvalidLine(i + 1)),
+            };
+
+            for squish in squishes(make, model, model_year) {
+                db.insert(Recall {
+                    campaign: campaign.to_owned(),
+                    squish,
+                    model_year,
+                    component: component.to_owned(),
+                    summary: summary.to_owned(),
+                });
+            }
+        }
+        Ok(db)
+    }
+
+    /// Adds the recall
+    pub fn insert(&mut self, recall: Recall) {
+        self.recalls.entry(recall.squish.clone()).or_default().push(recall);
+        self.count += 1;
+    }
+
+    /// Returns count of recalls
+    pub fn len(&self) -> usize { self.count }
+
+    /// Returns whether the database has no recalls
+    pub fn is_empty(&self) -> bool { self.count == 0 }
+
+    /// Returns recalls affecting the vehicle (matching its squish VIN and one of possible model years)
+    pub fn recalls_for(&self, vin: &VIN) -> Vec<&Recall> {
+        let years = vin.years().candidates;
+        self.recalls
+            .get(&SquishVin::from(vin))
+            .map(|recalls| recalls.iter().filter(|x| years.contains(&x.model_year)).collect())
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,227 @@
+//! Generation of sequential VINs for manufacturers.
+//!
+//! Given the assigned WMI and the vehicle descriptor, [`VinSequence`] issues VINs with
+//! consecutive serial numbers and correct check digits, restarts serials only when the model
+//! year changes and never issues the same VIN twice.
+//!
+//! Serial number takes 12-17 positions (numeric, 1 to 999999). Manufacturers producing less
+//! than 1000 vehicles per year (WMI ends with `9`) use 12-14 positions for the manufacturer
+//! code assigned by the national organization, so the serial number takes only 15-17 positions.
+//!
+//! # Examples
+//! ```
+//! use vin::registry::VinSequence;
+//!
+//! let mut sequence = VinSequence::new("1M8", "GDM9A", 'K', 'P').unwrap().starting_at(42788).unwrap();
+//! assert_eq!(sequence.next().unwrap().as_str(), "1M8GDM9AXKP042788");
+//! assert_eq!(sequence.next().unwrap().as_str(), "1M8GDM9A1KP042789");
+//!
+//! // small manufacturer
+//! let mut sequence = VinSequence::new("1G9", "AB123", 'L', 'A').unwrap()
+//!     .with_manufacturer_code("A42").unwrap();
+//! assert_eq!(sequence.next().unwrap().as_str(), "1G9AB1238LAA42001");
+//! ```
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::tables::is_allowed;
+use crate::years::YEAR_LETTERS;
+use crate::{compute_check_digit, config, VIN};
+
+/// Errors of the VIN generation
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RegistryError {
+    /// WMI is not 3 valid characters
+    InvalidWmi(String),
+
+    /// Vehicle descriptor (4-8 positions) is not 5 valid characters
+    InvalidDescriptor(String),
+
+    /// Symbol can not be used as model year
+    InvalidYear(char),
+
+    /// Symbol can not be used as plant code
+    InvalidPlant(char),
+
+    /// Manufacturer code is not 3 valid characters or is used with WMI of non-small manufacturer
+    InvalidManufacturerCode(String),
+
+    /// Serial number is out of the allowed range
+    InvalidSerial(u32),
+
+    /// All serial numbers of the model year are issued
+    SerialExhausted,
+
+    /// VIN was already issued in this run
+    Duplicate(String),
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RegistryError::InvalidWmi(wmi) =>
+                write!(f, "Invalid WMI: {:?}, 3 characters expected.", wmi),
+            RegistryError::InvalidDescriptor(vds) =>
+                write!(f, "Invalid vehicle descriptor: {:?}, 5 characters expected.", vds),
+            RegistryError::InvalidYear(ch) =>
+                write!(f, "Invalid model year symbol: {:?}.", ch),
+            RegistryError::InvalidPlant(ch) =>
+                write!(f, "Invalid plant symbol: {:?}.", ch),
+            RegistryError::InvalidManufacturerCode(code) =>
+                write!(f, "Invalid manufacturer code: {:?}.", code),
+            RegistryError::InvalidSerial(serial) =>
+                write!(f, "Serial number {} is out of the allowed range.", serial),
+            RegistryError::SerialExhausted =>
+                write!(f, "All serial numbers of the model year are issued."),
+            RegistryError::Duplicate(vin) =>
+                write!(f, "VIN {} was already issued.", vin),
+        }
+    }
+}
+
+/// Generator of sequential VINs
+#[derive(Debug, Clone)]
+pub struct VinSequence {
+    wmi: String,
+    descriptor: String,
+    year: char,
+    plant: char,
+    manufacturer_code: Option<String>,
+    next_serial: u32,
+    last_serials: HashMap<char, u32>,
+    issued: HashSet<String>,
+}
+
+impl VinSequence {
+    /// Creates the sequence starting from serial number 1
+    pub fn new(wmi: &str, descriptor: &str, year: char, plant: char) -> Result<VinSequence, RegistryError> {
+        let wmi = wmi.to_uppercase();
+        if !is_valid(&wmi, 3) {
+            return Err(RegistryError::InvalidWmi(wmi));
+        }
+        let descriptor = descriptor.to_uppercase();
+        if !is_valid(&descriptor, 5) {
+            return Err(RegistryError::InvalidDescriptor(descriptor));
+        }
+        let year = check_year(year)?;
+        let plant = plant.to_ascii_uppercase();
+        if !is_allowed(plant) {
+            return Err(RegistryError::InvalidPlant(plant));
+        }
+
+        Ok(VinSequence {
+            wmi,
+            descriptor,
+            year,
+            plant,
+            manufacturer_code: None,
+            next_serial: 1,
+            last_serials: HashMap::new(),
+            issued: HashSet::new(),
+        })
+    }
+
+    /// Sets the manufacturer code (12-14 positions) of the small manufacturer
+    pub fn with_manufacturer_code(mut self, code: &str) -> Result<VinSequence, RegistryError> {
+        let code = code.to_uppercase();
+        if !self.wmi.ends_with('9') || !is_valid(&code, 3) {
+            return Err(RegistryError::InvalidManufacturerCode(code));
+        }
+        self.manufacturer_code = Some(code);
+        Ok(self)
+    }
+
+    /// Sets the serial number of the next VIN
+    pub fn starting_at(mut self, serial: u32) -> Result<VinSequence, RegistryError> {
+        if serial == 0 || serial > self.max_serial() {
+            return Err(RegistryError::InvalidSerial(serial));
+        }
+        self.next_serial = serial;
+        Ok(self)
+    }
+
+    /// Returns the maximal serial number (999999, or 999 for small manufacturers)
+    pub fn max_serial(&self) -> u32 {
+        match self.manufacturer_code {
+            Some(_) => 999,
+            None => 999_999,
+        }
+    }
+
+    /// Returns the current model year symbol
+    pub fn model_year(&self) -> char { self.year }
+
+    /// Returns count of VINs issued (or registered) in this run
+    pub fn issued(&self) -> usize { self.issued.len() }
+
+    /// Issues the next VIN, skipping already issued ones.
+    ///
+    /// Serial numbers never roll over within the model year: when they are exhausted,
+    /// [`RegistryError::SerialExhausted`] is returned until the model year is changed.
+    /// The VIN is decoded by the configured parser, but isn't validated against its profile.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Result<VIN, RegistryError> {
+        while self.next_serial <= self.max_serial() {
+            let vin = self.build(self.next_serial);
+            self.next_serial += 1;
+            if self.issued.insert(String::from_utf8_lossy(&vin).into_owned()) {
+                let last = self.last_serials.entry(self.year).or_insert(0);
+                *last = (*last).max(self.next_serial - 1);
+                return Ok(config::parser().restore(vin));
+            }
+        }
+        Err(RegistryError::SerialExhausted)
+    }
+
+    /// Switches to another model year.
+    ///
+    /// Serial numbers restart from 1 for the new year; if the year was already used in this run,
+    /// they continue after the last issued one.
+    pub fn set_model_year(&mut self, year: char) -> Result<(), RegistryError> {
+        let year = check_year(year)?;
+        if year != self.year {
+            self.year = year;
+            self.next_serial = self.last_serials.get(&year).map_or(1, |x| x + 1);
+        }
+        Ok(())
+    }
+
+    /// Records VIN issued outside of the sequence, so that it's never issued again.
+    ///
+    /// Returns [`RegistryError::Duplicate`] if the VIN was already issued in this run.
+    pub fn register(&mut self, vin: &VIN) -> Result<(), RegistryError> {
+        if self.issued.insert(vin.as_str().to_owned()) {
+            Ok(())
+        } else {
+            Err(RegistryError::Duplicate(vin.as_str().to_owned()))
+        }
+    }
+
+    fn build(&self, serial: u32) -> [u8; 17] {
+        let vis = match &self.manufacturer_code {
+            Some(code) => format!("{}{}{}{:03}", self.year, self.plant, code, serial),
+            None => format!("{}{}{:06}", self.year, self.plant, serial),
+        };
+        let draft = format!("{}{}0{}", self.wmi, self.descriptor, vis);
+        // parts are validated when the sequence is created
+        let check_digit = compute_check_digit(&draft).unwrap();
+
+        let mut vin = [0; 17];
+        vin.copy_from_slice(format!("{}{}{}{}", self.wmi, self.descriptor, check_digit, vis).as_bytes());
+        vin
+    }
+}
+
+fn is_valid(part: &str, len: usize) -> bool {
+    part.chars().count() == len && part.chars().all(is_allowed)
+}
+
+fn check_year(year: char) -> Result<char, RegistryError> {
+    let year = year.to_ascii_uppercase();
+    if YEAR_LETTERS.contains(year) {
+        Ok(year)
+    } else {
+        Err(RegistryError::InvalidYear(year))
+    }
+}
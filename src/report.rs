@@ -0,0 +1,131 @@
+//! Shareable decode reports in Markdown or HTML.
+//!
+//! The report lists the decoded information, the position-by-position breakdown with
+//! the checksum math (see [`VIN::explain`](../struct.VIN.html#method.explain)) and
+//! the version of the built-in dataset.
+//!
+//! # Examples
+//! ```
+//! use vin::report::{render, Format};
+//!
+//! let result = vin::get_info("1M8GDM9AXKP042788").unwrap();
+//! let markdown = render(&result, Format::Markdown);
+//! assert!(markdown.starts_with("# VIN 1M8GDM9AXKP042788\n"));
+//! assert!(markdown.contains("| 9 | X | check digit | 0 | 0 | check digit, valid |"));
+//! assert!(render(&result, Format::Html).contains("<h1>VIN 1M8GDM9AXKP042788</h1>"));
+//! ```
+use crate::explain::Explanation;
+use crate::{data_version, ChecksumStatus, VIN};
+
+/// Describes format of the report
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Format {
+    /// Markdown (GitHub flavored tables)
+    Markdown,
+
+    /// HTML fragment
+    Html,
+}
+
+/// Returns report about the decoded VIN in the given format
+pub fn render(vin: &VIN, format: Format) -> String {
+    let explanation = vin.explain();
+    let years: Vec<String> = vin.years().candidates.iter().map(u32::to_string).collect();
+    let summary = [
+        ("Manufacturer", vin.manufacturer.clone().unwrap_or_default()),
+        ("Country", vin.country.clone().unwrap_or_default()),
+        ("Region", vin.region.clone().unwrap_or_default()),
+        ("Model years", years.join(", ")),
+        ("Checksum", checksum_status(vin.valid_checksum).to_string()),
+    ];
+    let version = data_version();
+    let provenance = format!("Built-in dataset version {} of {}.", version.version, version.date);
+
+    match format {
+        Format::Markdown => markdown(vin.as_str(), &summary, &explanation, &provenance),
+        Format::Html => html(vin.as_str(), &summary, &explanation, &provenance),
+    }
+}
+
+fn checksum_status(status: ChecksumStatus) -> &'static str {
+    match status {
+        ChecksumStatus::Valid => "valid",
+        ChecksumStatus::Invalid(_) => "invalid",
+        _ => "not applicable in the region",
+    }
+}
+
+/// Returns explanation of the check digit calculation
+fn checksum_math(explanation: &Explanation) -> String {
+    format!(
+        "Weighted sum {} mod 11 = {}, expected check digit {}, received {}.",
+        explanation.sum,
+        explanation.sum % 11,
+        explanation.expected_check_digit,
+        explanation.positions[8].symbol,
+    )
+}
+
+/// Returns the rows of the positions table
+fn rows(explanation: &Explanation) -> Vec<[String; 6]> {
+    explanation.positions
+        .iter()
+        .map(|x| [
+            x.position.to_string(),
+            x.symbol.to_string(),
+            x.role.to_string(),
+            x.weight.to_string(),
+            x.contribution.to_string(),
+            x.meaning.clone().unwrap_or_default(),
+        ])
+        .collect()
+}
+
+const HEADER: [&str; 6] = ["Position", "Symbol", "Role", "Weight", "Contribution", "Meaning"];
+
+fn markdown(vin: &str, summary: &[(&str, String)], explanation: &Explanation, provenance: &str) -> String {
+    let cell = |x: &str| x.replace('|', "\\|");
+    let mut report = format!("# VIN {}\n\n", vin);
+    for (name, value) in summary {
+        report.push_str(&format!("- **{}:** {}\n", name, cell(value)));
+    }
+    report.push_str(&format!("\n## Positions\n\n| {} |\n", HEADER.join(" | ")));
+    report.push_str(&format!("|{}\n", "---|".repeat(HEADER.len())));
+    for row in rows(explanation) {
+        let row: Vec<String> = row.iter().map(|x| cell(x)).collect();
+        report.push_str(&format!("| {} |\n", row.join(" | ")));
+    }
+    report.push_str(&format!("\n{}\n\n---\n{}\n", checksum_math(explanation), provenance));
+    report
+}
+
+fn html(vin: &str, summary: &[(&str, String)], explanation: &Explanation, provenance: &str) -> String {
+    let mut report = format!("<h1>VIN {}</h1>\n<ul>\n", vin);
+    for (name, value) in summary {
+        report.push_str(&format!("<li><b>{}:</b> {}</li>\n", name, escape(value)));
+    }
+    report.push_str("</ul>\n<h2>Positions</h2>\n<table>\n<tr>");
+    for name in HEADER {
+        report.push_str(&format!("<th>{}</th>", name));
+    }
+    report.push_str("</tr>\n");
+    for row in rows(explanation) {
+        report.push_str("<tr>");
+        for value in row {
+            report.push_str(&format!("<td>{}</td>", escape(&value)));
+        }
+        report.push_str("</tr>\n");
+    }
+    report.push_str(&format!("</table>\n<p>{}</p>\n<footer>{}</footer>\n", checksum_math(explanation), escape(provenance)));
+    report
+}
+
+/// Escapes special HTML characters
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,41 @@
+//! JSON responses shared by the HTTP service and the web framework integrations.
+use serde::Serialize;
+
+use crate::{Finding, VINError};
+
+/// Error response, serialized as `{"vin": ..., "error": ...}` with optional `code`
+/// (the [`Finding`](../struct.Finding.html) id, e.g. `E_CHECKSUM`)
+///
+/// # Examples
+/// ```
+/// let err = vin::get_info("1M8GDM9AXKP04278").unwrap_err();
+/// let response = vin::response::ErrorResponse::new("1M8GDM9AXKP04278", &err);
+/// assert_eq!(response.error, "Incorrect length of given string, 17 chars expected.");
+/// assert_eq!(response.with_code(&err).code, Some("E_LENGTH"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ErrorResponse {
+    /// Received number, `null` if the request does not contain VIN
+    pub vin: Option<String>,
+
+    /// Machine-readable code of the error, omitted by the `vin-service` binary
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<&'static str>,
+
+    /// Human-readable description of the error
+    pub error: String,
+}
+
+impl ErrorResponse {
+    /// Creates response describing the error of VIN parsing
+    pub fn new(vin: &str, err: &VINError) -> ErrorResponse {
+        ErrorResponse { vin: Some(vin.to_string()), code: None, error: err.to_string() }
+    }
+
+    /// Sets machine-readable code of the error
+    pub fn with_code(mut self, err: &VINError) -> ErrorResponse {
+        self.code = Some(Finding::from_error(err).id());
+        self
+    }
+}
@@ -0,0 +1,33 @@
+//! Decoding of restraint system codes of North American vehicles.
+//!
+//! Manufacturers of the North American market encode the restraint system (airbags and
+//! seatbelts) in the VDS, e.g. GM passenger cars use the 7-th position. Covered VIN prefixes
+//! are listed in `data/restraints.csv`.
+//!
+//! # Examples
+//! ```
+//! let result = vin::get_info("1G1JC5449S7123456").unwrap();
+//! let info = result.restraint_info().unwrap();
+//! assert_eq!(info.airbags, "driver and passenger front");
+//! assert_eq!(info.seatbelts, "active manual");
+//! ```
+use crate::dicts::{match_pattern, RESTRAINT_TABLE};
+use crate::VIN;
+
+/// Holds decoded restraint system of the vehicle
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct RestraintInfo {
+    /// Airbag configuration, e.g. `driver and passenger front`
+    pub airbags: &'static str,
+
+    /// Seatbelt type, e.g. `active manual`
+    pub seatbelts: &'static str,
+}
+
+impl VIN {
+    /// Returns decoded restraint system, or `None` if the VIN is not covered by the table
+    pub fn restraint_info(&self) -> Option<RestraintInfo> {
+        match_pattern(RESTRAINT_TABLE, |x| x.0, self.as_bytes())
+            .map(|(_, airbags, seatbelts)| RestraintInfo { airbags, seatbelts })
+    }
+}
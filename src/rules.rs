@@ -0,0 +1,222 @@
+//! Custom validation rules (house rules of the organization).
+//!
+//! Rules attached to [`VinParser`](../struct.VinParser.html) are checked by
+//! [`VinParser::validate`](../struct.VinParser.html#method.validate), their findings are reported
+//! in the [`ValidationReport`](../struct.ValidationReport.html) and together with the built-in
+//! ones by [`VinParser::validate_full`](../struct.VinParser.html#method.validate_full).
+//! Plain parsing does not check the rules.
+//!
+//! With `json` or `toml` feature enabled, rule sets may be loaded from configuration files
+//! (see [`load_rule_set`]).
+//!
+//! # Examples
+//! ```
+//! use vin::rules::{DecodeContext, MinModelYear};
+//! use vin::{Finding, Severity, VinParser, VIN};
+//!
+//! let parser = VinParser::new()
+//!     .with_rule(MinModelYear(2005))
+//!     .with_rule(|vin: &VIN, _: &DecodeContext| match vin.region.as_deref() {
+//!         Some("Europe") => None,
+//!         _ => Some(Finding::rule(Severity::Error, "E_HOUSE_REGION", "Only European vehicles are accepted.")),
+//!     });
+//!
+//! let result = parser.validate_full("1M8GDM9AXKP042788");
+//! let ids: Vec<&str> = result.findings.iter().map(|x| x.id()).collect();
+//! assert_eq!(ids, ["E_MODEL_YEAR", "E_HOUSE_REGION"]);
+//! assert!(!result.is_valid());
+//! ```
+use std::collections::HashSet;
+
+use crate::config::ValidationProfile;
+use crate::{Finding, Severity, YearHints, YearWindow, VIN};
+
+#[cfg(any(feature = "json", feature = "toml"))]
+mod set;
+
+#[cfg(any(feature = "json", feature = "toml"))]
+pub use self::set::{load_rule_set, load_rule_set_from, RuleSet, RuleSetError};
+
+/// Holds context of the validation passed to the rules
+#[derive(Debug, Copy, Clone)]
+#[non_exhaustive]
+pub struct DecodeContext<'a> {
+    /// VIN as received
+    pub input: &'a str,
+
+    /// Validation profile of the parser
+    pub profile: ValidationProfile,
+
+    /// Whether the VIN is listed in the watchlist, `None` if the parser has no watchlist
+    pub watchlisted: Option<bool>,
+
+    /// Window of plausible model years of the parser
+    /// (see [`VinParser::year_window`](../struct.VinParser.html#method.year_window))
+    pub year_window: YearWindow,
+}
+
+/// Custom validation rule
+pub trait Rule: Send + Sync {
+    /// Returns finding if the decoded VIN violates the rule
+    fn check(&self, vin: &VIN, ctx: &DecodeContext) -> Option<Finding>;
+}
+
+impl<F> Rule for F
+where
+    F: Fn(&VIN, &DecodeContext) -> Option<Finding> + Send + Sync,
+{
+    fn check(&self, vin: &VIN, ctx: &DecodeContext) -> Option<Finding> { self(vin, ctx) }
+}
+
+/// Rejects vehicles of model years before the given one (`E_MODEL_YEAR`), the most likely model
+/// year within the window of the parser is checked, or the latest candidate if the most likely
+/// one can not be chosen
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct MinModelYear(pub u32);
+
+impl Rule for MinModelYear {
+    fn check(&self, vin: &VIN, ctx: &DecodeContext) -> Option<Finding> {
+        let year = model_year(vin, ctx.year_window)?;
+        match year < self.0 {
+            true => Some(Finding::rule(
+                Severity::Error,
+                "E_MODEL_YEAR",
+                &format!("Model year {} is before {}.", year, self.0),
+            )),
+            false => None,
+        }
+    }
+}
+
+/// Rejects vehicles of model years after the given one (`E_MODEL_YEAR`), the year is chosen
+/// as by [`MinModelYear`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct MaxModelYear(pub u32);
+
+impl Rule for MaxModelYear {
+    fn check(&self, vin: &VIN, ctx: &DecodeContext) -> Option<Finding> {
+        let year = model_year(vin, ctx.year_window)?;
+        match year > self.0 {
+            true => Some(Finding::rule(
+                Severity::Error,
+                "E_MODEL_YEAR",
+                &format!("Model year {} is after {}.", year, self.0),
+            )),
+            false => None,
+        }
+    }
+}
+
+/// Returns the most likely model year within the window, or the latest candidate
+fn model_year(vin: &VIN, window: YearWindow) -> Option<u32> {
+    let estimate = vin.year_estimate_within(window, YearHints::default());
+    estimate.best_guess.or_else(|| estimate.candidates.last().copied())
+}
+
+/// Rejects vehicles of regions other than the given ones (`E_REGION`), VINs of unknown regions
+/// are rejected as well
+///
+/// # Examples
+/// ```
+/// use vin::rules::AllowedRegions;
+///
+/// let parser = vin::VinParser::new().with_rule(AllowedRegions::new(["Europe"]));
+/// assert!(parser.validate_full("WP0ZZZ99ZTS392124").is_valid());
+/// assert!(!parser.validate_full("1M8GDM9AXKP042788").is_valid());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllowedRegions {
+    regions: HashSet<String>,
+}
+
+impl AllowedRegions {
+    /// Creates rule allowing the given regions (names as returned by [`VIN::region`](../struct.VIN.html#structfield.region))
+    pub fn new<I, S>(regions: I) -> AllowedRegions
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        AllowedRegions { regions: regions.into_iter().map(Into::into).collect() }
+    }
+}
+
+impl Rule for AllowedRegions {
+    fn check(&self, vin: &VIN, _: &DecodeContext) -> Option<Finding> {
+        match &vin.region {
+            Some(region) if self.regions.contains(region) => None,
+            Some(region) => Some(Finding::rule(Severity::Error, "E_REGION", &format!("Region {} is not allowed.", region))),
+            None => Some(Finding::rule(Severity::Error, "E_REGION", "Unknown region is not allowed.")),
+        }
+    }
+}
+
+/// Rejects vehicles with WMIs other than the given ones (`E_WMI_NOT_ALLOWED`), e.g. for portals
+/// accepting only vehicles of their own manufacturer
+///
+/// # Examples
+/// ```
+/// use vin::rules::AllowedWmis;
+///
+/// let parser = vin::VinParser::new().with_rule(AllowedWmis::new(["WP0", "WP1"]));
+/// assert!(parser.validate_full("WP0ZZZ99ZTS392124").is_valid());
+/// assert_eq!(parser.validate_full("1M8GDM9AXKP042788").findings[0].id(), "E_WMI_NOT_ALLOWED");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllowedWmis {
+    wmis: HashSet<String>,
+}
+
+impl AllowedWmis {
+    /// Creates rule allowing the given WMIs (case-insensitive)
+    pub fn new<I, S>(wmis: I) -> AllowedWmis
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        AllowedWmis { wmis: wmis.into_iter().map(|x| x.as_ref().to_uppercase()).collect() }
+    }
+}
+
+impl Rule for AllowedWmis {
+    fn check(&self, vin: &VIN, _: &DecodeContext) -> Option<Finding> {
+        match self.wmis.contains(vin.wmi()) {
+            true => None,
+            false => Some(Finding::rule(Severity::Error, "E_WMI_NOT_ALLOWED", &format!("WMI {} is not allowed.", vin.wmi()))),
+        }
+    }
+}
+
+/// Rejects vehicles with the given WMIs (`E_WMI_DENIED`)
+///
+/// # Examples
+/// ```
+/// use vin::rules::DeniedWmis;
+///
+/// let parser = vin::VinParser::new().with_rule(DeniedWmis::new(["wp0"]));
+/// assert_eq!(parser.validate_full("WP0ZZZ99ZTS392124").findings[0].id(), "E_WMI_DENIED");
+/// assert!(parser.validate_full("1M8GDM9AXKP042788").is_valid());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeniedWmis {
+    wmis: HashSet<String>,
+}
+
+impl DeniedWmis {
+    /// Creates rule denying the given WMIs (case-insensitive)
+    pub fn new<I, S>(wmis: I) -> DeniedWmis
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        DeniedWmis { wmis: wmis.into_iter().map(|x| x.as_ref().to_uppercase()).collect() }
+    }
+}
+
+impl Rule for DeniedWmis {
+    fn check(&self, vin: &VIN, _: &DecodeContext) -> Option<Finding> {
+        match self.wmis.contains(vin.wmi()) {
+            true => Some(Finding::rule(Severity::Error, "E_WMI_DENIED", &format!("WMI {} is denied.", vin.wmi()))),
+            false => None,
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::ValidationProfile;
+use crate::dicts::Format;
+use crate::rules::{AllowedRegions, MaxModelYear, MinModelYear};
+use crate::tables::is_allowed;
+use crate::{VinParser, YearPolicy};
+
+/// Provides possible errors during rule set loading
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum RuleSetError {
+    /// Format of the file can not be guessed by its extension
+    UnknownFormat,
+
+    /// Rule set can not be read
+    Io(io::Error),
+
+    /// Rule set can not be parsed or serialized (contains description of the problem)
+    Parse(String),
+
+    /// WMI of the rule set is malformed (contains the WMI)
+    InvalidWmi(String),
+}
+
+impl fmt::Display for RuleSetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RuleSetError::UnknownFormat =>
+                write!(f, "Unknown format of rule set file, .json or .toml expected."),
+            RuleSetError::Io(err) =>
+                write!(f, "Rule set can not be read: {}.", err),
+            RuleSetError::Parse(err) =>
+                write!(f, "Rule set can not be parsed: {}.", err),
+            RuleSetError::InvalidWmi(wmi) =>
+                write!(f, "Invalid WMI in rule set: {:?}.", wmi),
+        }
+    }
+}
+
+impl std::error::Error for RuleSetError {}
+
+impl From<io::Error> for RuleSetError {
+    fn from(err: io::Error) -> Self { RuleSetError::Io(err) }
+}
+
+/// Validation policy kept in a configuration file, so it may be changed without redeploys.
+///
+/// All fields are optional in the document, absent ones do not restrict VINs.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "toml")] {
+/// use vin::dicts::Format;
+/// use vin::rules::load_rule_set_from;
+///
+/// let document = "require_checksum = true\nallowed_regions = [\"Europe\"]\nblocked_wmis = [\"wp0\"]\n";
+/// let rules = load_rule_set_from(document.as_bytes(), Format::Toml).unwrap();
+/// assert_eq!(rules.blocked_wmis, ["WP0"]);
+///
+/// let parser = rules.apply(vin::VinParser::new());
+/// assert!(parser.parse("1M8GDM9AXKP042789").is_err());
+/// assert_eq!(parser.validate_full("WP0ZZZ998TS392124").findings[0].id(), "E_WMI_DENIED");
+/// assert_eq!(parser.validate_full("1M8GDM9AXKP042788").findings[0].id(), "E_REGION");
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RuleSet {
+    /// VINs with invalid checksum are rejected
+    /// (see [`ValidationProfile::RequireChecksum`](../config/enum.ValidationProfile.html#variant.RequireChecksum))
+    pub require_checksum: bool,
+
+    /// Allowed regions, all regions are allowed if empty (see [`AllowedRegions`])
+    pub allowed_regions: Vec<String>,
+
+    /// The earliest allowed model year (see [`MinModelYear`])
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_model_year: Option<u32>,
+
+    /// The latest allowed model year (see [`MaxModelYear`])
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_model_year: Option<u32>,
+
+    /// Denied WMIs (see [`DeniedWmis`](struct.DeniedWmis.html))
+    pub blocked_wmis: Vec<String>,
+
+    /// Allowed WMIs, all WMIs are allowed if empty (see [`AllowedWmis`](struct.AllowedWmis.html))
+    pub allowed_wmis: Vec<String>,
+
+    /// Count of plausible model years in advance of the current year
+    /// (see [`YearPolicy`](../struct.YearPolicy.html))
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lookahead_years: Option<u8>,
+}
+
+impl RuleSet {
+    /// Returns the parser with the profile and the rules of the set
+    pub fn apply(&self, mut parser: VinParser) -> VinParser {
+        if self.require_checksum {
+            parser = parser.with_profile(ValidationProfile::RequireChecksum);
+        }
+        if !self.allowed_regions.is_empty() {
+            parser = parser.with_rule(AllowedRegions::new(self.allowed_regions.iter().cloned()));
+        }
+        if let Some(year) = self.min_model_year {
+            parser = parser.with_rule(MinModelYear(year));
+        }
+        if let Some(year) = self.max_model_year {
+            parser = parser.with_rule(MaxModelYear(year));
+        }
+        if !self.blocked_wmis.is_empty() {
+            parser = parser.deny_wmis(&self.blocked_wmis);
+        }
+        if !self.allowed_wmis.is_empty() {
+            parser = parser.allow_wmis(&self.allowed_wmis);
+        }
+        if let Some(lookahead_years) = self.lookahead_years {
+            parser = parser.with_year_policy(YearPolicy { lookahead_years });
+        }
+        parser
+    }
+
+    /// Returns the rule set as a document of the given format
+    pub fn to_document(&self, format: Format) -> Result<String, RuleSetError> {
+        match format {
+            #[cfg(feature = "json")]
+            Format::Json => serde_json::to_string_pretty(self)
+                .map_err(|e| RuleSetError::Parse(e.to_string())),
+            #[cfg(feature = "toml")]
+            Format::Toml => toml::to_string(self)
+                .map_err(|e| RuleSetError::Parse(e.to_string())),
+        }
+    }
+
+    fn validated(mut self) -> Result<RuleSet, RuleSetError> {
+        for wmi in self.blocked_wmis.iter_mut().chain(self.allowed_wmis.iter_mut()) {
+            *wmi = wmi.to_uppercase();
+            if wmi.chars().count() != 3 || !wmi.chars().all(is_allowed) {
+                return Err(RuleSetError::InvalidWmi(wmi.clone()));
+            }
+        }
+        Ok(self)
+    }
+}
+
+/// Loads rule set from the file, guessing its format by extension (`.json` or `.toml`)
+pub fn load_rule_set<P: AsRef<Path>>(path: P) -> Result<RuleSet, RuleSetError> {
+    let format = Format::from_path(&path).ok_or(RuleSetError::UnknownFormat)?;
+    load_rule_set_from(File::open(path)?, format)
+}
+
+/// Loads rule set from the reader (see [`RuleSet`] for the fields of the document)
+pub fn load_rule_set_from<R: Read>(mut reader: R, format: Format) -> Result<RuleSet, RuleSetError> {
+    let mut document = String::new();
+    reader.read_to_string(&mut document)?;
+
+    let rules: RuleSet = match format {
+        #[cfg(feature = "json")]
+        Format::Json => serde_json::from_str(&document)
+            .map_err(|e| RuleSetError::Parse(e.to_string()))?,
+        #[cfg(feature = "toml")]
+        Format::Toml => toml::from_str(&document)
+            .map_err(|e| RuleSetError::Parse(e.to_string()))?,
+    };
+
+    rules.validated()
+}
+
+impl VinParser {
+    /// Creates parser using built-in dictionary with the rule set loaded from the file
+    /// (see [`RuleSet`](rules/struct.RuleSet.html))
+    pub fn from_config<P: AsRef<Path>>(path: P) -> Result<VinParser, RuleSetError> {
+        Ok(load_rule_set(path)?.apply(VinParser::new()))
+    }
+}
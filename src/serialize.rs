@@ -0,0 +1,164 @@
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+use crate::{ChecksumErrorInfo, ChecksumStatus, Finding, Severity, ValidationReport, ValidationWarning, VIN};
+
+/// Serialized as `{"expected": "8", "received": "Z"}`
+impl Serialize for ChecksumErrorInfo {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("ChecksumErrorInfo", 2)?;
+        state.serialize_field("expected", &self.expected)?;
+        state.serialize_field("received", &self.received)?;
+        state.end()
+    }
+}
+
+/// Serialized as an object with `vin`, `manufacturer`, `country`, `region` (strings or nulls),
+/// `valid_checksum` (boolean), `checksum_status` (`valid`, `invalid` or `not_applicable`),
+/// `checksum_error` (object or null) and `years` (array of numbers)
+impl Serialize for VIN {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("VIN", 8)?;
+        state.serialize_field("vin", self.as_str())?;
+        state.serialize_field("manufacturer", &self.manufacturer)?;
+        state.serialize_field("country", &self.country)?;
+        state.serialize_field("region", &self.region)?;
+        state.serialize_field("valid_checksum", &self.valid_checksum.is_valid())?;
+        state.serialize_field("checksum_status", match self.valid_checksum {
+            ChecksumStatus::Valid => "valid",
+            ChecksumStatus::Invalid(_) => "invalid",
+            _ => "not_applicable",
+        })?;
+        state.serialize_field("checksum_error", &self.valid_checksum.error())?;
+        state.serialize_field("years", &self.years().candidates)?;
+        state.end()
+    }
+}
+
+/// Serialized as `identical_serial` or `sequential_serial`
+impl Serialize for ValidationWarning {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(match self {
+            ValidationWarning::IdenticalSerial => "identical_serial",
+            ValidationWarning::SequentialSerial => "sequential_serial",
+        })
+    }
+}
+
+/// Serialized as `{"id": "E_MODEL_YEAR", "severity": "error", "message": "..."}`
+impl Serialize for Finding {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Finding", 3)?;
+        state.serialize_field("id", self.id())?;
+        state.serialize_field("severity", match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+        })?;
+        state.serialize_field("message", &self.message)?;
+        state.end()
+    }
+}
+
+/// Serialized as an object with `vin` (decoded VIN), `watchlisted` and `numeric_serial`
+/// (booleans or nulls), `warnings` (array of strings) and `rule_findings` (array of objects)
+impl Serialize for ValidationReport {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("ValidationReport", 5)?;
+        state.serialize_field("vin", &self.vin)?;
+        state.serialize_field("watchlisted", &self.watchlisted)?;
+        state.serialize_field("numeric_serial", &self.numeric_serial)?;
+        state.serialize_field("warnings", &self.warnings)?;
+        state.serialize_field("rule_findings", &self.rule_findings)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "schemars")]
+mod schema {
+    use std::borrow::Cow;
+
+    use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+
+    use crate::{ChecksumErrorInfo, Finding, ValidationReport, ValidationWarning, VIN};
+
+    impl JsonSchema for ChecksumErrorInfo {
+        fn schema_name() -> Cow<'static, str> { "ChecksumErrorInfo".into() }
+
+        fn json_schema(_: &mut SchemaGenerator) -> Schema {
+            json_schema!({
+                "type": "object",
+                "properties": {
+                    "expected": { "type": "string", "minLength": 1, "maxLength": 1 },
+                    "received": { "type": "string", "minLength": 1, "maxLength": 1 },
+                },
+                "required": ["expected", "received"],
+            })
+        }
+    }
+
+    impl JsonSchema for VIN {
+        fn schema_name() -> Cow<'static, str> { "VIN".into() }
+
+        fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+            json_schema!({
+                "type": "object",
+                "properties": {
+                    "vin": { "type": "string", "pattern": "^[A-HJ-NPR-Z0-9]{17}$" },
+                    "manufacturer": { "type": ["string", "null"] },
+                    "country": { "type": ["string", "null"] },
+                    "region": { "type": ["string", "null"] },
+                    "valid_checksum": { "type": "boolean" },
+                    "checksum_status": { "type": "string", "enum": ["valid", "invalid", "not_applicable"] },
+                    "checksum_error": generator.subschema_for::<Option<ChecksumErrorInfo>>(),
+                    "years": generator.subschema_for::<Vec<u32>>(),
+                },
+                "required": [
+                    "vin", "manufacturer", "country", "region",
+                    "valid_checksum", "checksum_status", "checksum_error", "years",
+                ],
+            })
+        }
+    }
+
+    impl JsonSchema for ValidationWarning {
+        fn schema_name() -> Cow<'static, str> { "ValidationWarning".into() }
+
+        fn json_schema(_: &mut SchemaGenerator) -> Schema {
+            json_schema!({ "type": "string", "enum": ["identical_serial", "sequential_serial"] })
+        }
+    }
+
+    impl JsonSchema for Finding {
+        fn schema_name() -> Cow<'static, str> { "Finding".into() }
+
+        fn json_schema(_: &mut SchemaGenerator) -> Schema {
+            json_schema!({
+                "type": "object",
+                "properties": {
+                    "id": { "type": "string" },
+                    "severity": { "type": "string", "enum": ["error", "warning", "info"] },
+                    "message": { "type": "string" },
+                },
+                "required": ["id", "severity", "message"],
+            })
+        }
+    }
+
+    impl JsonSchema for ValidationReport {
+        fn schema_name() -> Cow<'static, str> { "ValidationReport".into() }
+
+        fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+            json_schema!({
+                "type": "object",
+                "properties": {
+                    "vin": generator.subschema_for::<VIN>(),
+                    "watchlisted": { "type": ["boolean", "null"] },
+                    "numeric_serial": { "type": ["boolean", "null"] },
+                    "warnings": generator.subschema_for::<Vec<ValidationWarning>>(),
+                    "rule_findings": generator.subschema_for::<Vec<Finding>>(),
+                },
+                "required": ["vin", "watchlisted", "numeric_serial", "warnings", "rule_findings"],
+            })
+        }
+    }
+}
@@ -0,0 +1,51 @@
+//! HTTP service decoding VINs, served by the `vin-service` binary.
+//!
+//! * `GET /vin/{number}` returns the decoded VIN (see the JSON representation of
+//!   [`VIN`](../struct.VIN.html)) or `422` with [`ErrorResponse`](../response/struct.ErrorResponse.html) for invalid numbers;
+//! * `POST /vins` takes JSON array of numbers (up to [`MAX_BATCH`]) and returns array of results,
+//!   each one is either decoded VIN or the error object.
+use axum::extract::Path;
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde_json::{json, Value};
+
+use crate::get_info;
+use crate::response::ErrorResponse;
+
+/// Maximum count of VINs in one batch request
+pub const MAX_BATCH: usize = 1000;
+
+/// Returns router of the service
+pub fn router() -> Router {
+    Router::new()
+        .route("/vin/{number}", get(decode_one))
+        .route("/vins", post(decode_batch))
+}
+
+async fn decode_one(Path(number): Path<String>) -> (StatusCode, Json<Value>) {
+    match decode(&number) {
+        Ok(result) => (StatusCode::OK, Json(result)),
+        Err(error) => (StatusCode::UNPROCESSABLE_ENTITY, Json(error)),
+    }
+}
+
+async fn decode_batch(Json(numbers): Json<Vec<String>>) -> (StatusCode, Json<Value>) {
+    if numbers.len() > MAX_BATCH {
+        let error = format!("Too many VINs in the batch, up to {} expected.", MAX_BATCH);
+        return (StatusCode::PAYLOAD_TOO_LARGE, Json(json!({ "error": error })));
+    }
+
+    let results = numbers
+        .iter()
+        .map(|number| decode(number).unwrap_or_else(|error| error))
+        .collect();
+    (StatusCode::OK, Json(Value::Array(results)))
+}
+
+fn decode(number: &str) -> Result<Value, Value> {
+    match get_info(number) {
+        Ok(vin) => Ok(json!(vin)),
+        Err(err) => Err(json!(ErrorResponse::new(number, &err))),
+    }
+}
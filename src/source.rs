@@ -0,0 +1,122 @@
+//! Sources of WMI (World Manufacturer Identifier) information.
+//!
+//! Built-in dictionary is used by default, but any other source (e.g. licensed SAE database)
+//! may be plugged into [`VinParser`](../struct.VinParser.html) by implementing [`WmiSource`].
+//!
+//! # Examples
+//! ```
+//! use vin::VinParser;
+//! use vin::source::{BuiltinSource, WmiRecord, WmiSource};
+//!
+//! struct Fleet;
+//!
+//! impl WmiSource for Fleet {
+//!     fn lookup(&self, wmi: &str) -> Option<WmiRecord> {
+//!         match wmi {
+//!             "XTA" => Some(WmiRecord::new("Lada", "Russia", "Europe")),
+//!             _ => None,
+//!         }
+//!     }
+//! }
+//!
+//! let parser = VinParser::with_source(Fleet.or(BuiltinSource));
+//! assert_eq!(parser.parse("XTA21099043576182").unwrap().manufacturer.unwrap(), "Lada");
+//! assert_eq!(parser.parse("WP0ZZZ99ZTS392124").unwrap().manufacturer.unwrap(), "Porsche car");
+//! ```
+use crate::dicts::{get_country, get_manufacturer, get_region, lookup_longest_names};
+
+/// Holds information about the manufacturer identified by WMI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WmiRecord {
+    /// Name of the manufacturer
+    pub manufacturer: String,
+
+    /// Country of the manufacturer, if known
+    pub country: Option<String>,
+
+    /// Region of the manufacturer, if known
+    pub region: Option<String>,
+}
+
+impl WmiRecord {
+    /// Creates new record
+    pub fn new(manufacturer: &str, country: &str, region: &str) -> WmiRecord {
+        WmiRecord {
+            manufacturer: manufacturer.to_string(),
+            country: Some(country.to_string()),
+            region: Some(region.to_string()),
+        }
+    }
+}
+
+/// Source of information about manufacturers
+pub trait WmiSource: Send + Sync {
+    /// Returns information about manufacturer with the given WMI (3 uppercase characters),
+    /// or `None` if the manufacturer is unknown to the source
+    fn lookup(&self, wmi: &str) -> Option<WmiRecord>;
+
+    /// Returns country with the given code (2 uppercase characters), used for WMIs unknown
+    /// to the source. Built-in dictionary is consulted by default.
+    fn lookup_country(&self, code: &str) -> Option<String> { get_country(code) }
+
+    /// Returns information about manufacturer of the given VIN (17 uppercase characters), so
+    /// sources may resolve small manufacturers by the extended code. Only WMI is consulted by default.
+    fn lookup_vin(&self, vin: &str) -> Option<WmiRecord> { self.lookup(&vin[..3]) }
+
+    /// Returns source which consults `fallback` for WMIs unknown to this source
+    fn or<S: WmiSource>(self, fallback: S) -> Layered<Self, S> where Self: Sized {
+        Layered { primary: self, fallback }
+    }
+}
+
+/// Built-in dictionary of manufacturers
+#[derive(Debug, Copy, Clone, Default)]
+pub struct BuiltinSource;
+
+impl WmiSource for BuiltinSource {
+    fn lookup(&self, wmi: &str) -> Option<WmiRecord> {
+        get_manufacturer(wmi).map(|manufacturer| WmiRecord {
+            manufacturer,
+            country: get_country(&wmi[..2]),
+            region: get_region(&wmi[..1]),
+        })
+    }
+
+    fn lookup_vin(&self, vin: &str) -> Option<WmiRecord> {
+        let (country, manufacturer) = lookup_longest_names(vin)?;
+        Some(WmiRecord {
+            manufacturer: manufacturer?.to_string(),
+            country: country.map(str::to_string),
+            region: get_region(&vin[..1]),
+        })
+    }
+}
+
+/// Source consulting the primary source first and the fallback one afterwards
+#[derive(Debug, Clone)]
+pub struct Layered<P, F> {
+    primary: P,
+    fallback: F,
+}
+
+impl<P: WmiSource, F: WmiSource> WmiSource for Layered<P, F> {
+    fn lookup(&self, wmi: &str) -> Option<WmiRecord> {
+        self.primary.lookup(wmi).or_else(|| self.fallback.lookup(wmi))
+    }
+
+    fn lookup_country(&self, code: &str) -> Option<String> {
+        self.primary.lookup_country(code).or_else(|| self.fallback.lookup_country(code))
+    }
+
+    fn lookup_vin(&self, vin: &str) -> Option<WmiRecord> {
+        self.primary.lookup_vin(vin).or_else(|| self.fallback.lookup_vin(vin))
+    }
+}
+
+impl<S: WmiSource + ?Sized> WmiSource for Box<S> {
+    fn lookup(&self, wmi: &str) -> Option<WmiRecord> { (**self).lookup(wmi) }
+
+    fn lookup_country(&self, code: &str) -> Option<String> { (**self).lookup_country(code) }
+
+    fn lookup_vin(&self, vin: &str) -> Option<WmiRecord> { (**self).lookup_vin(vin) }
+}
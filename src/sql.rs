@@ -0,0 +1,5 @@
+//! Integrations with database libraries.
+#[cfg(feature = "diesel")]
+mod diesel;
+#[cfg(feature = "sqlx")]
+mod sqlx;
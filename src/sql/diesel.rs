@@ -0,0 +1,28 @@
+use ::diesel::backend::Backend;
+use ::diesel::deserialize::{self, FromSql};
+use ::diesel::serialize::{self, Output, ToSql};
+use ::diesel::sql_types::Text;
+
+use crate::{get_info, VIN};
+
+impl<DB> ToSql<Text, DB> for VIN
+where
+    DB: Backend,
+    str: ToSql<Text, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        self.as_str().to_sql(out)
+    }
+}
+
+/// Loaded values are validated, invalid ones result in [`VINError`](../../enum.VINError.html)
+impl<DB> FromSql<Text, DB> for VIN
+where
+    DB: Backend,
+    String: FromSql<Text, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        let vin = String::from_sql(bytes)?;
+        Ok(get_info(&vin)?)
+    }
+}
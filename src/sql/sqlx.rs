@@ -0,0 +1,37 @@
+use ::sqlx::encode::IsNull;
+use ::sqlx::error::BoxDynError;
+use ::sqlx::{Database, Decode, Encode, Type};
+
+use crate::{get_info, VIN};
+
+/// VIN is stored as text (`TEXT`, `VARCHAR` or `CHAR(17)` columns)
+impl<DB: Database> Type<DB> for VIN
+where
+    str: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo { <str as Type<DB>>::type_info() }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool { <str as Type<DB>>::compatible(ty) }
+}
+
+impl<'q, DB: Database> Encode<'q, DB> for VIN
+where
+    String: Encode<'q, DB>,
+{
+    fn encode_by_ref(&self, buf: &mut <DB as Database>::ArgumentBuffer<'q>) -> Result<IsNull, BoxDynError> {
+        <String as Encode<'q, DB>>::encode(self.as_str().to_owned(), buf)
+    }
+
+    fn size_hint(&self) -> usize { 17 }
+}
+
+/// Decoded values are validated, invalid ones result in [`VINError`](../../enum.VINError.html)
+impl<'r, DB: Database> Decode<'r, DB> for VIN
+where
+    &'r str: Decode<'r, DB>,
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<VIN, BoxDynError> {
+        let vin = <&'r str as Decode<'r, DB>>::decode(value)?;
+        Ok(get_info(vin)?)
+    }
+}
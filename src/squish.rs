@@ -0,0 +1,84 @@
+use std::fmt;
+
+use crate::parse::validated;
+use crate::VINError::{self, IncorrectLength, InvalidCharacters};
+use crate::{InvalidChar, VIN};
+
+/// Holds "squish VIN" - 10 characters (1-8 and 10-11 positions of the VIN) used for
+/// recall and pattern lookups, where the check digit and the serial number are irrelevant
+///
+/// # Examples
+/// ```
+/// use vin::SquishVin;
+///
+/// let squish = SquishVin::parse("1m8gdm9akp").unwrap();
+/// assert_eq!(squish, SquishVin::from_vin("1M8GDM9AXKP042788").unwrap());
+/// assert!(squish.matches("1M8GDM9AXKP042788"));
+/// assert_eq!(squish.to_string(), "1M8GDM9AKP");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SquishVin {
+    squish: String,
+}
+
+impl SquishVin {
+    /// Parses 10-characters squish VIN
+    pub fn parse(squish: &str) -> Result<SquishVin, VINError> {
+        let squish = squish.to_uppercase();
+
+        // check length
+        if squish.chars().count() != 10 {
+            return Err(IncorrectLength);
+        }
+
+        // check alphabet
+        let odd_chars = InvalidChar::find(&squish, &[]);
+        if !odd_chars.is_empty() {
+            return Err(InvalidCharacters(odd_chars));
+        }
+
+        Ok(SquishVin { squish })
+    }
+
+    /// Builds squish VIN from the full VIN
+    pub fn from_vin(vin: &str) -> Result<SquishVin, VINError> {
+        Ok(SquishVin { squish: squish_unchecked(&validated(vin)?) })
+    }
+
+    /// Returns squish VIN as string
+    pub fn as_str(&self) -> &str { &self.squish }
+
+    /// Returns whether the given full VIN corresponds to this squish VIN
+    pub fn matches(&self, vin: &str) -> bool {
+        vin.chars().count() == 17 && squish_unchecked(&vin.to_uppercase()) == self.squish
+    }
+}
+
+impl fmt::Display for SquishVin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.squish)
+    }
+}
+
+impl From<&VIN> for SquishVin {
+    fn from(vin: &VIN) -> Self { SquishVin { squish: vin.squish() } }
+}
+
+pub(in crate) fn squish_unchecked(vin: &str) -> String {
+    vin.chars()
+        .enumerate()
+        .filter(|(i, _)| *i < 8 || *i == 9 || *i == 10)
+        .map(|(_, x)| x)
+        .collect()
+}
+
+impl VIN {
+    /// Returns squish VIN (1-8 and 10-11 positions)
+    ///
+    /// # Examples
+    /// ```
+    /// let result = vin::get_info("1M8GDM9AXKP042788").unwrap();
+    /// assert_eq!(result.squish(), "1M8GDM9AKP");
+    /// ```
+    pub fn squish(&self) -> String { squish_unchecked(self.as_str()) }
+}
@@ -0,0 +1,187 @@
+//! Distributions of decoded VINs for analytics.
+//!
+//! # Examples
+//! ```
+//! use vin::stats::aggregate;
+//!
+//! let vins: Vec<vin::VIN> = ["WP0ZZZ99ZTS392124", "1M8GDM9AXKP042788", "1M8GDM9AXKP042789"]
+//!     .iter()
+//!     .map(|x| vin::get_info(x).unwrap())
+//!     .collect();
+//! let stats = aggregate(&vins);
+//! assert_eq!(stats.total, 3);
+//! assert_eq!(stats.regions["North America"], 2);
+//! assert_eq!((stats.valid_checksum, stats.invalid_checksum, stats.checksum_not_applicable), (1, 1, 1));
+//! ```
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::VIN;
+
+/// Holds distributions of the decoded VINs by manufacturer, country, region, model year and
+/// checksum validity
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VinStats {
+    /// Count of all VINs
+    pub total: usize,
+
+    /// Count of VINs per manufacturer (VINs of unknown manufacturers are not counted)
+    pub manufacturers: HashMap<String, usize>,
+
+    /// Count of VINs per country (VINs of unknown countries are not counted)
+    pub countries: HashMap<String, usize>,
+
+    /// Count of VINs per region (VINs of unknown regions are not counted)
+    pub regions: HashMap<String, usize>,
+
+    /// Count of VINs per most likely model year (VINs without the best guess are not counted,
+    /// see [`ModelYearEstimate`](../struct.ModelYearEstimate.html))
+    pub model_years: HashMap<u32, usize>,
+
+    /// Count of VINs with valid checksum
+    pub valid_checksum: usize,
+
+    /// Count of VINs with invalid checksum in the region mandating the check digit
+    pub invalid_checksum: usize,
+
+    /// Count of VINs with invalid checksum in the region not mandating the check digit
+    pub checksum_not_applicable: usize,
+}
+
+impl VinStats {
+    /// Creates empty statistics
+    pub fn new() -> VinStats { VinStats::default() }
+
+    /// Records the decoded VIN
+    pub fn record(&mut self, vin: &VIN) {
+        self.total += 1;
+        let distributions = [
+            (&mut self.manufacturers, &vin.manufacturer),
+            (&mut self.countries, &vin.country),
+            (&mut self.regions, &vin.region),
+        ];
+        for (distribution, value) in distributions {
+            if let Some(value) = value {
+                *distribution.entry(value.clone()).or_insert(0) += 1;
+            }
+        }
+        if let Some(year) = vin.years().best_guess {
+            *self.model_years.entry(year).or_insert(0) += 1;
+        }
+        if vin.valid_checksum.is_valid() {
+            self.valid_checksum += 1;
+        } else if vin.valid_checksum.is_invalid() {
+            self.invalid_checksum += 1;
+        } else {
+            self.checksum_not_applicable += 1;
+        }
+    }
+
+    /// Adds statistics aggregated elsewhere, e.g. by another thread
+    pub fn merge(&mut self, other: &VinStats) {
+        self.total += other.total;
+        merge_counts(&mut self.manufacturers, &other.manufacturers);
+        merge_counts(&mut self.countries, &other.countries);
+        merge_counts(&mut self.regions, &other.regions);
+        merge_counts(&mut self.model_years, &other.model_years);
+        self.valid_checksum += other.valid_checksum;
+        self.invalid_checksum += other.invalid_checksum;
+        self.checksum_not_applicable += other.checksum_not_applicable;
+    }
+
+    /// Returns up to `n` most frequent manufacturers with their counts, the most frequent first
+    /// (manufacturers with equal counts are ordered by name)
+    pub fn top_manufacturers(&self, n: usize) -> Vec<(&str, usize)> {
+        let mut result: Vec<(&str, usize)> = self.manufacturers.iter().map(|(x, count)| (x.as_str(), *count)).collect();
+        result.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        result.truncate(n);
+        result
+    }
+}
+
+fn merge_counts<K: Clone + Eq + std::hash::Hash>(counts: &mut HashMap<K, usize>, other: &HashMap<K, usize>) {
+    for (key, count) in other {
+        *counts.entry(key.clone()).or_insert(0) += count;
+    }
+}
+
+/// Returns distributions of the decoded VINs
+pub fn aggregate<I>(vins: I) -> VinStats
+where
+    I: IntoIterator,
+    I::Item: Borrow<VIN>,
+{
+    let mut stats = VinStats::new();
+    for vin in vins {
+        stats.record(vin.borrow());
+    }
+    stats
+}
+
+/// Describes attribute, which values the stratified sample is balanced across
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Strata {
+    /// Name of the manufacturer
+    Manufacturer,
+
+    /// Country of the manufacturer
+    Country,
+
+    /// Region of the manufacturer
+    Region,
+
+    /// Most likely model year
+    ModelYear,
+}
+
+impl Strata {
+    /// Returns value of the attribute, `None` if unknown (such VINs form a stratum of their own)
+    fn key(&self, vin: &VIN) -> Option<String> {
+        match self {
+            Strata::Manufacturer => vin.manufacturer.clone(),
+            Strata::Country => vin.country.clone(),
+            Strata::Region => vin.region.clone(),
+            Strata::ModelYear => vin.years().best_guess.map(|x| x.to_string()),
+        }
+    }
+}
+
+/// Returns up to `n` VINs balanced across values of the attribute: every stratum contributes
+/// equally unless it is exhausted, VINs are drawn evenly spaced within the stratum, so
+/// the sample is deterministic and spans the whole collection
+///
+/// # Examples
+/// ```
+/// use vin::stats::{sample_stratified, Strata};
+///
+/// let mut vins: Vec<vin::VIN> = (0..10).map(|_| vin::get_info("1M8GDM9AXKP042788").unwrap()).collect();
+/// vins.push(vin::get_info("WP0ZZZ99ZTS392124").unwrap());
+/// let sample = sample_stratified(&vins, Strata::Region, 4);
+/// assert_eq!(sample.len(), 4);
+/// assert_eq!(sample.iter().filter(|x| x.region.as_deref() == Some("Europe")).count(), 1);
+/// ```
+pub fn sample_stratified(vins: &[VIN], by: Strata, n: usize) -> Vec<&VIN> {
+    let mut strata: BTreeMap<Option<String>, Vec<&VIN>> = BTreeMap::new();
+    for vin in vins {
+        strata.entry(by.key(vin)).or_default().push(vin);
+    }
+
+    // distribute the quota round-robin across strata, which have VINs left
+    let mut quotas = vec![0; strata.len()];
+    let mut left = n.min(vins.len());
+    while left > 0 {
+        for (quota, members) in quotas.iter_mut().zip(strata.values()) {
+            if left > 0 && *quota < members.len() {
+                *quota += 1;
+                left -= 1;
+            }
+        }
+    }
+
+    strata
+        .values()
+        .zip(quotas)
+        .flat_map(|(members, quota)| (0..quota).map(move |i| members[i * members.len() / quota]))
+        .collect()
+}
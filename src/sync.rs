@@ -0,0 +1,41 @@
+//! `OnceLock` of the statics, which is available in std only since Rust 1.70.
+//!
+//! On older toolchains (see `has_once_lock` set by the build script) the value is initialized
+//! under a mutex and leaked, which is fine for statics living until the process exits.
+
+#[cfg(has_once_lock)]
+#[allow(clippy::incompatible_msrv)]
+mod imp {
+    /// Cell of the static, initialized once
+    pub(in crate) struct OnceLock<T>(std::sync::OnceLock<T>);
+
+    impl<T> OnceLock<T> {
+        pub(in crate) const fn new() -> OnceLock<T> { OnceLock(std::sync::OnceLock::new()) }
+
+        pub(in crate) fn get(&self) -> Option<&T> { self.0.get() }
+
+        pub(in crate) fn get_or_init<F: FnOnce() -> T>(&self, init: F) -> &T { self.0.get_or_init(init) }
+    }
+}
+
+#[cfg(not(has_once_lock))]
+mod imp {
+    use std::sync::{Mutex, PoisonError};
+
+    /// Cell of the static, initialized once
+    pub(in crate) struct OnceLock<T: 'static>(Mutex<Option<&'static T>>);
+
+    impl<T> OnceLock<T> {
+        pub(in crate) const fn new() -> OnceLock<T> { OnceLock(Mutex::new(None)) }
+
+        pub(in crate) fn get(&self) -> Option<&T> { *self.0.lock().unwrap_or_else(PoisonError::into_inner) }
+
+        pub(in crate) fn get_or_init<F: FnOnce() -> T>(&self, init: F) -> &T {
+            let mut value = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+            let value: &'static T = value.get_or_insert_with(|| Box::leak(Box::new(init())));
+            value
+        }
+    }
+}
+
+pub(in crate) use self::imp::OnceLock;
@@ -0,0 +1,53 @@
+//! Constant tables of the VIN standard used by the checksum calculation and validation.
+//!
+//! Exposed for implementers of custom checks, so they do not need to copy the constants.
+//!
+//! # Examples
+//! ```
+//! use vin::tables::{char_value, ALLOWED_CHARS, WEIGHTS};
+//!
+//! // weighted sum of the VIN symbols modulo 11 gives the check digit (10 is written as X)
+//! let sum: u32 = "1M8GDM9AXKP042788".chars().zip(WEIGHTS.iter()).map(|(x, w)| char_value(x).unwrap() * w).sum();
+//! assert_eq!(sum % 11, 10);
+//! assert!(!ALLOWED_CHARS.contains(&'O'));
+//! ```
+use crate::dicts::BYTE_VALUES;
+
+/// Weights of the VIN positions in the checksum calculation (the check digit itself is weighted 0)
+pub const WEIGHTS: [u32; 17] = [8, 7, 6, 5, 4, 3, 2, 10, 0, 9, 8, 7, 6, 5, 4, 3, 2];
+
+/// Values of the symbols allowed in VIN used in the checksum calculation (transliteration)
+pub const CHAR_VALUES: [(char, u32); 33] = [
+    ('0', 0), ('1', 1), ('2', 2), ('3', 3), ('4', 4), ('5', 5), ('6', 6), ('7', 7),
+    ('8', 8), ('9', 9), ('A', 1), ('B', 2), ('C', 3), ('D', 4), ('E', 5), ('F', 6),
+    ('G', 7), ('H', 8), ('J', 1), ('K', 2), ('L', 3), ('M', 4), ('N', 5), ('P', 7),
+    ('R', 9), ('S', 2), ('T', 3), ('U', 4), ('V', 5), ('W', 6), ('X', 7), ('Y', 8),
+    ('Z', 9),
+];
+
+/// Symbols allowed in VIN (digits and uppercase latin letters except I, O and Q), in ascending order
+pub const ALLOWED_CHARS: [char; 33] = [
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'B', 'C', 'D', 'E', 'F', 'G',
+    'H', 'J', 'K', 'L', 'M', 'N', 'P', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
+];
+
+/// Returns value of the symbol used in the checksum calculation, `None` if the symbol is not
+/// allowed in VIN (lowercase symbols are not allowed either)
+///
+/// # Examples
+/// ```
+/// assert_eq!(vin::tables::char_value('P'), Some(7));
+/// assert_eq!(vin::tables::char_value('p'), None);
+/// ```
+pub fn char_value(symbol: char) -> Option<u32> {
+    if !symbol.is_ascii() {
+        return None;
+    }
+    match BYTE_VALUES[symbol as usize] {
+        u8::MAX => None,
+        value => Some(u32::from(value)),
+    }
+}
+
+/// Returns whether the symbol is allowed in VIN
+pub fn is_allowed(symbol: char) -> bool { char_value(symbol).is_some() }
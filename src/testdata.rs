@@ -0,0 +1,59 @@
+//! Curated corpora of VINs for tests.
+//!
+//! Valid VINs are built from known WMIs with synthetic `0005xx` serial numbers and correct
+//! check digits, so they can be safely used in fixtures without colliding with real vehicles.
+//!
+//! # Examples
+//! ```
+//! for (_, vin_number) in vin::testdata::valid() {
+//!     assert!(vin::verify_checksum(vin_number).is_ok());
+//! }
+//! for vin_number in vin::testdata::INVALID_CHECKSUM {
+//!     assert!(vin::check_validity(vin_number).is_ok());
+//!     assert!(vin::verify_checksum(vin_number).is_err());
+//! }
+//! ```
+
+/// Valid VINs of African manufacturers
+pub const VALID_AFRICA: &[&str] = &["AAVZZZ6R6EA000101", "AHTBB3CD2KA000102", "AFAFXXMJ4JA000103"];
+
+/// Valid VINs of Asian manufacturers
+pub const VALID_ASIA: &[&str] = &["JA3AU26U68U000201", "KL1TD566X7B000202", "MA3ERLF325S000203"];
+
+/// Valid VINs of European manufacturers
+pub const VALID_EUROPE: &[&str] = &["WP0ZZZ991SA000301", "SAJAA01N12H000302", "VF1BB05C32A000303"];
+
+/// Valid VINs of North American manufacturers
+pub const VALID_NORTH_AMERICA: &[&str] = &["1FTFW1E51DF000401", "2T1BU4EE1DC000402", "3VWDX7AJ4CM000403"];
+
+/// Valid VINs of Oceanian manufacturers
+pub const VALID_OCEANIA: &[&str] = &["6FPAAAJG0CD000501", "7A3FC2651JA000502"];
+
+/// Valid VINs of South American manufacturers
+pub const VALID_SOUTH_AMERICA: &[&str] = &["8AD4CRFJ08G000601", "9BWDB05U96T000602", "935SLKFX2AB000603"];
+
+/// Strings of incorrect length
+pub const INVALID_LENGTH: &[&str] = &["", "1FTFW1E51DF00040", "1FTFW1E51DF0004011", "1FTFW1E51DF 000401"];
+
+/// Strings of correct length containing characters not allowed in VIN
+pub const INVALID_CHARACTERS: &[&str] = &["1FTFW1E51DF00040I", "OFTFW1E51DF000401", "1FTFW1E51DQ000401", "1FTFW1E5-DF000401"];
+
+/// Well-formed VINs with wrong check digit
+pub const INVALID_CHECKSUM: &[&str] = &["1FTFW1E52DF000401", "WP0ZZZ99ZSA000301", "JA3AU26UX8U000201"];
+
+/// Returns all valid VINs with names of their regions
+pub fn valid() -> Vec<(&'static str, &'static str)> {
+    let corpora = [
+        ("Africa", VALID_AFRICA),
+        ("Asia", VALID_ASIA),
+        ("Europe", VALID_EUROPE),
+        ("North America", VALID_NORTH_AMERICA),
+        ("Oceania", VALID_OCEANIA),
+        ("South America", VALID_SOUTH_AMERICA),
+    ];
+
+    corpora
+        .iter()
+        .flat_map(|(region, vins)| vins.iter().map(move |vin| (*region, *vin)))
+        .collect()
+}
@@ -0,0 +1,33 @@
+//! [proptest](https://docs.rs/proptest) strategies generating VINs (requires `proptest` feature).
+//!
+//! # Examples
+//! ```
+//! use proptest::prelude::*;
+//!
+//! proptest!(|(vin_number in vin::testing::arb_valid_vin())| {
+//!     prop_assert!(vin::verify_checksum(&vin_number).is_ok());
+//! });
+//! ```
+use proptest::prelude::*;
+use proptest::sample::select;
+
+use crate::compute_check_digit;
+use crate::tables::ALLOWED_CHARS;
+
+fn arb_char() -> impl Strategy<Value = char> { select(&ALLOWED_CHARS[..]) }
+
+/// Generates well-formed VINs with an arbitrary symbol at the check digit place
+pub fn arb_vin() -> impl Strategy<Value = String> {
+    proptest::collection::vec(arb_char(), 17).prop_map(|x| x.into_iter().collect())
+}
+
+/// Generates well-formed VINs with correct check digit
+pub fn arb_valid_vin() -> impl Strategy<Value = String> {
+    arb_vin().prop_map(|vin| {
+        let check_digit = compute_check_digit(&vin).unwrap();
+        vin.chars()
+            .enumerate()
+            .map(|(i, x)| if i == 8 { check_digit } else { x })
+            .collect()
+    })
+}
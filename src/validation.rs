@@ -0,0 +1,90 @@
+//! Validation of VINs at the boundary of web applications.
+//!
+//! * [`validate_vin`] is a custom validator of the `validator` crate, e.g.
+//!   `#[validate(custom(function = "vin::validation::validate_vin"))]`;
+//! * [`ValidVin`] is an `axum` extractor taking the VIN from the single path parameter or
+//!   the `vin` query parameter, which rejects invalid numbers with `400` and
+//!   [`ErrorResponse`](../response/struct.ErrorResponse.html) including `code`.
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use axum::extract::{FromRequestParts, Path, Query};
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use ::validator::ValidationError;
+
+use crate::response::ErrorResponse;
+use crate::{get_info, Finding, VINError, VIN};
+
+/// Parses VIN, rejecting invalid checksum in regions where the check digit is mandatory
+fn check(number: &str) -> Result<VIN, VINError> {
+    let vin = get_info(number)?;
+    match vin.valid_checksum.error() {
+        Some(info) => Err(VINError::ChecksumError(info)),
+        None => Ok(vin),
+    }
+}
+
+/// Validates VIN as a custom validator of the `validator` crate, the error code is
+/// the [`Finding`](../struct.Finding.html) id, e.g. `E_CHECKSUM`
+///
+/// # Examples
+/// ```
+/// assert!(vin::validation::validate_vin("1M8GDM9AXKP042788").is_ok());
+/// let err = vin::validation::validate_vin("1M8GDM9AXKP042789").unwrap_err();
+/// assert_eq!(err.code, "E_CHECKSUM");
+/// ```
+pub fn validate_vin(number: &str) -> Result<(), ValidationError> {
+    check(number).map(|_| ()).map_err(|err| {
+        ValidationError::new(Finding::from_error(&err).id())
+            .with_message(Cow::Owned(err.to_string()))
+    })
+}
+
+/// Decoded VIN extracted from the request, see the [module](index.html) documentation
+#[derive(Debug, Clone)]
+pub struct ValidVin(pub VIN);
+
+/// Rejection of [`ValidVin`], responds with `400` and JSON details of the error
+#[derive(Debug)]
+pub struct InvalidVin {
+    /// Received number, if any
+    pub vin: Option<String>,
+
+    /// Error of the validation, `None` if the request does not contain VIN
+    pub error: Option<VINError>,
+}
+
+impl IntoResponse for InvalidVin {
+    fn into_response(self) -> Response {
+        let body = match (&self.vin, &self.error) {
+            (Some(vin), Some(err)) => ErrorResponse::new(vin, err).with_code(err),
+            _ => ErrorResponse {
+                vin: None,
+                code: Some("E_MISSING"),
+                error: "VIN is missing in the request.".to_string(),
+            },
+        };
+        (StatusCode::BAD_REQUEST, Json(body)).into_response()
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for ValidVin {
+    type Rejection = InvalidVin;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let number = match Path::<String>::from_request_parts(parts, state).await {
+            Ok(Path(number)) => Some(number),
+            Err(_) => Query::<HashMap<String, String>>::from_request_parts(parts, state)
+                .await
+                .ok()
+                .and_then(|Query(mut query)| query.remove("vin")),
+        };
+        let number = number.ok_or(InvalidVin { vin: None, error: None })?;
+        check(&number)
+            .map(ValidVin)
+            .map_err(|err| InvalidVin { vin: Some(number), error: Some(err) })
+    }
+}
@@ -0,0 +1,76 @@
+//! Matching of VINs against watchlists (e.g. stolen vehicles).
+//!
+//! A watchlist can be checked directly or attached to [`VinParser`](../struct.VinParser.html),
+//! so [`VinParser::validate`](../struct.VinParser.html#method.validate) flags listed VINs
+//! in the [`ValidationReport`](../struct.ValidationReport.html).
+//!
+//! # Examples
+//! ```
+//! use vin::watchlist::{MemoryWatchlist, Watchlist};
+//!
+//! let list = MemoryWatchlist::load_csv("vin,reported\nWP0ZZZ99ZTS392124,2020-01-02\n".as_bytes()).unwrap();
+//! let parser = vin::VinParser::new().with_watchlist(list);
+//!
+//! let report = parser.validate("wp0zzz99zts392124").unwrap();
+//! assert_eq!(report.watchlisted, Some(true));
+//! assert_eq!(parser.validate("1M8GDM9AXKP042788").unwrap().watchlisted, Some(false));
+//! ```
+use std::collections::HashSet;
+use std::io::{self, BufRead};
+
+use crate::{get_info, VINError, VIN};
+
+/// List of VINs to be flagged during validation
+pub trait Watchlist: Send + Sync {
+    /// Returns whether the VIN is listed
+    fn contains(&self, vin: &VIN) -> bool;
+}
+
+/// Watchlist kept in memory
+#[derive(Debug, Clone, Default)]
+pub struct MemoryWatchlist {
+    vins: HashSet<[u8; 17]>,
+}
+
+impl MemoryWatchlist {
+    /// Creates empty watchlist
+    pub fn new() -> MemoryWatchlist { MemoryWatchlist::default() }
+
+    /// Loads VINs from the first column of CSV lines. Lines with invalid VINs (including
+    /// the header) are skipped, count of skipped lines is returned by [`load_csv_counted`](#method.load_csv_counted).
+    pub fn load_csv<R: BufRead>(reader: R) -> io::Result<MemoryWatchlist> {
+        Ok(MemoryWatchlist::load_csv_counted(reader)?.0)
+    }
+
+    /// Loads VINs from the first column of CSV lines, returns the watchlist and count of skipped lines
+    pub fn load_csv_counted<R: BufRead>(reader: R) -> io::Result<(MemoryWatchlist, usize)> {
+        let mut list = MemoryWatchlist::new();
+        let mut skipped = 0;
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let vin = line.split(',').next().unwrap_or_default().trim().trim_matches('"');
+            if list.insert(vin).is_err() {
+                skipped += 1;
+            }
+        }
+        Ok((list, skipped))
+    }
+
+    /// Adds the VIN, returns whether it was not listed yet
+    pub fn insert(&mut self, vin: &str) -> Result<bool, VINError> {
+        Ok(self.vins.insert(*get_info(vin)?.as_bytes()))
+    }
+
+    /// Returns count of listed VINs
+    pub fn len(&self) -> usize { self.vins.len() }
+
+    /// Returns whether no VINs are listed
+    pub fn is_empty(&self) -> bool { self.vins.is_empty() }
+}
+
+impl Watchlist for MemoryWatchlist {
+    fn contains(&self, vin: &VIN) -> bool { self.vins.contains(vin.as_bytes()) }
+}
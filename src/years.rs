@@ -0,0 +1,451 @@
+use std::ops::RangeInclusive;
+#[cfg(feature = "chrono")]
+use chrono::{Datelike, NaiveDate};
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use std::time::SystemTime;
+
+use crate::VIN;
+
+/// Symbols encoding model year, starting from 1980, cycle repeats every 30 years
+pub(in crate) const YEAR_LETTERS: &str = "ABCDEFGHJKLMNPRSTVWXY123456789";
+
+/// First model year encoded by the VIN standard
+const FIRST_YEAR: u32 = 1980;
+
+/// Count of years after which the symbols repeat
+const CYCLE: u32 = 30;
+
+/// Year symbols and the first model years encoded by them
+const YEAR_TABLE: [(char, u32); 30] = [
+    ('A', 1980), ('B', 1981), ('C', 1982), ('D', 1983), ('E', 1984), ('F', 1985),
+    ('G', 1986), ('H', 1987), ('J', 1988), ('K', 1989), ('L', 1990), ('M', 1991),
+    ('N', 1992), ('P', 1993), ('R', 1994), ('S', 1995), ('T', 1996), ('V', 1997),
+    ('W', 1998), ('X', 1999), ('Y', 2000), ('1', 2001), ('2', 2002), ('3', 2003),
+    ('4', 2004), ('5', 2005), ('6', 2006), ('7', 2007), ('8', 2008), ('9', 2009),
+];
+
+/// Offsets of the first model years from 1980 indexed by ASCII code of the year symbol
+const YEAR_OFFSETS: [Option<u8>; 128] = year_offsets();
+
+const fn year_offsets() -> [Option<u8>; 128] {
+    let letters = YEAR_LETTERS.as_bytes();
+    let mut table = [None; 128];
+    let mut i = 0;
+    while i < letters.len() {
+        table[letters[i] as usize] = Some(i as u8);
+        i += 1;
+    }
+    table
+}
+
+/// Returns the first model year encoded by the year symbol (the 10-th one)
+fn first_year(letter: char) -> Option<u32> {
+    let offset = YEAR_OFFSETS.get(letter as usize).copied().flatten()?;
+    Some(FIRST_YEAR + u32::from(offset))
+}
+
+/// Returns year symbols with the first model years encoded by them (the symbols repeat
+/// every 30 years)
+///
+/// # Examples
+/// ```
+/// let codes = vin::year_codes();
+/// assert_eq!(codes[0], ('A', 1980));
+/// assert!(codes.contains(&('T', 1996)));
+/// ```
+pub fn year_codes() -> &'static [(char, u32); 30] { &YEAR_TABLE }
+
+/// Bounds the window of model years considered when decoding the year symbol
+///
+/// # Examples
+/// ```
+/// use vin::YearWindow;
+///
+/// let window = YearWindow::new(1995..=2015);
+/// assert_eq!(window.years('T'), vec![1996]);
+/// assert_eq!(window.years('1'), vec![2001]);
+/// assert!(window.years('Z').is_empty());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct YearWindow {
+    /// First year of the window
+    pub from: u32,
+
+    /// Last year of the window (inclusive)
+    pub to: u32,
+}
+
+impl YearWindow {
+    /// Creates window from the given range of years
+    pub fn new(range: RangeInclusive<u32>) -> YearWindow {
+        YearWindow { from: *range.start(), to: *range.end() }
+    }
+
+    /// Creates window from 1980 up to 2 years in advance of the given reference year
+    /// (see [`YearPolicy`] for other lookaheads)
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(vin::YearWindow::as_of(2020).range(), 1980..=2022);
+    /// ```
+    pub fn as_of(year: u32) -> YearWindow { YearPolicy::default().window(year) }
+
+    /// Creates window from 1980 up to 2 years in advance of the year of the given date
+    #[cfg(feature = "chrono")]
+    pub fn as_of_date(date: NaiveDate) -> YearWindow {
+        YearWindow::as_of(date.year().max(0) as u32)
+    }
+
+    /// Returns window as range of years
+    pub fn range(&self) -> RangeInclusive<u32> { self.from..=self.to }
+
+    /// Returns model years within the window encoded by the given symbol, in ascending order
+    pub fn years(&self, letter: char) -> Vec<u32> {
+        let first = match first_year(letter) {
+            Some(first) => first,
+            None => return vec![],
+        };
+
+        // first year of the cycle at or after the window start
+        let offset = self.from.max(first) - first;
+        let start = first.checked_add((offset / CYCLE + u32::from(offset % CYCLE != 0)) * CYCLE);
+        match start {
+            Some(start) => (start..=self.to).step_by(CYCLE as usize).collect(),
+            None => vec![],
+        }
+    }
+}
+
+impl Default for YearWindow {
+    /// Window from 1980 up to the lookahead of the year policy in advance of the current year,
+    /// as told by the clock, both of the configured parser (see [`config`](config/index.html));
+    /// decoded VINs use the window of their parser instead (see [`VIN::year_window`](struct.VIN.html#method.year_window))
+    fn default() -> Self { crate::config::parser().year_window() }
+}
+
+/// Bounds how many model years in advance of the current year are plausible, e.g. vehicles
+/// pre-ordered from dealerships may carry VINs coded several model years ahead
+///
+/// # Examples
+/// ```
+/// use vin::{VinParser, YearPolicy};
+///
+/// let parser = VinParser::new().with_clock(|| 2020).with_year_policy(YearPolicy { lookahead_years: 3 });
+/// assert_eq!(parser.year_window().range(), 1980..=2023);
+/// assert_eq!(YearPolicy::default().lookahead_years, 2);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct YearPolicy {
+    /// Count of model years in advance of the current year
+    pub lookahead_years: u8,
+}
+
+impl YearPolicy {
+    /// Returns window from 1980 up to the lookahead in advance of the given reference year
+    pub fn window(self, year: u32) -> YearWindow {
+        YearWindow::new(FIRST_YEAR..=year.saturating_add(u32::from(self.lookahead_years)))
+    }
+}
+
+impl Default for YearPolicy {
+    /// Policy allowing model years up to 2 years in advance
+    fn default() -> Self { YearPolicy { lookahead_years: 2 } }
+}
+
+/// Source of the current year, which bounds the decoded model years
+/// (see [`VinParser::with_clock`](struct.VinParser.html#method.with_clock))
+pub trait Clock: Send + Sync {
+    /// Returns the current year
+    fn current_year(&self) -> u32;
+}
+
+impl<F> Clock for F
+where
+    F: Fn() -> u32 + Send + Sync,
+{
+    fn current_year(&self) -> u32 { self() }
+}
+
+/// Wall clock of the system, `Date` of the JavaScript host under `wasm32-unknown-unknown`
+/// (where the system time is not available), the year is taken in UTC
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct SystemClock;
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+impl SystemClock {
+    /// Returns calendar year (UTC) of the given time
+    ///
+    /// # Examples
+    /// ```
+    /// use std::time::{Duration, SystemTime};
+    /// use vin::SystemClock;
+    ///
+    /// let new_year = SystemTime::UNIX_EPOCH + Duration::from_secs(1_767_225_600);  // 2026-01-01
+    /// assert_eq!(SystemClock::year_at(new_year - Duration::from_secs(1)), 2025);
+    /// assert_eq!(SystemClock::year_at(new_year), 2026);
+    /// ```
+    pub fn year_at(time: SystemTime) -> u32 {
+        let seconds = match time.duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(elapsed) => elapsed.as_secs() as i64,
+            Err(err) => -(err.duration().as_secs_f64().ceil() as i64),
+        };
+
+        // civil date from count of days since 1970-01-01, with years starting in March
+        let days = seconds.div_euclid(86_400) + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let march_based_month = (5 * day_of_year + 2) / 153;
+        let year = year_of_era + era * 400 + i64::from(march_based_month >= 10);
+        year.max(0) as u32
+    }
+}
+
+impl Clock for SystemClock {
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    fn current_year(&self) -> u32 { SystemClock::year_at(SystemTime::now()) }
+
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    fn current_year(&self) -> u32 { js_sys::Date::new_0().get_utc_full_year() }
+}
+
+/// Describes how the best guess of the model year was chosen
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum YearMethod {
+    /// The year symbol encodes the only year within the window
+    Unique,
+
+    /// North American 7-th symbol rule: digit for 1980-2009, letter for 2010-2039
+    Position7,
+
+    /// Chosen by the caller-provided hints
+    Hint,
+
+    /// Several candidates remain, no best guess
+    Ambiguous,
+
+    /// The year symbol encodes no year within the window
+    Unknown,
+}
+
+/// Holds contextual knowledge about the vehicle used to choose the most likely model year
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct YearHints {
+    /// Vehicle is currently registered, so the latest candidate is preferred
+    pub currently_registered: bool,
+}
+
+/// Holds contextual knowledge of the model year decade (e.g. from the title document)
+///
+/// # Examples
+/// ```
+/// use vin::DecadeHint;
+///
+/// assert!(DecadeHint::Decade(1990).contains(1996));
+/// assert!(!DecadeHint::Before(2001).contains(2001));
+/// assert!(DecadeHint::NotBefore(2030).contains(2031));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DecadeHint {
+    /// Model year is within the decade starting at the given year (e.g. 1990 for 1990-1999)
+    Decade(u32),
+
+    /// Model year is before the given year
+    Before(u32),
+
+    /// Model year is the given year or later
+    NotBefore(u32),
+}
+
+impl DecadeHint {
+    /// Returns whether the model year agrees with the hint
+    pub fn contains(&self, year: u32) -> bool {
+        match *self {
+            DecadeHint::Decade(start) => (start..start.saturating_add(10)).contains(&year),
+            DecadeHint::Before(end) => year < end,
+            DecadeHint::NotBefore(start) => year >= start,
+        }
+    }
+}
+
+/// Holds possible model years with the most likely one
+///
+/// # Examples
+/// ```
+/// let estimate = vin::get_info("1M8GDM9AXKP042788").unwrap().years();
+/// assert_eq!(estimate.candidates[0], 1989);
+/// assert_eq!(estimate.best_guess, Some(1989));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ModelYearEstimate {
+    /// Possible model years, in ascending order
+    pub candidates: Vec<u32>,
+
+    /// Most likely model year, if it can be chosen
+    pub best_guess: Option<u32>,
+
+    /// How the best guess was chosen
+    pub method: YearMethod,
+}
+
+impl ModelYearEstimate {
+    /// Returns whether the model year is known for sure
+    pub fn is_unique(&self) -> bool { self.candidates.len() == 1 }
+}
+
+impl VIN {
+    /// Returns window of model years of the parser which decoded the VIN, as of the decoding
+    /// (see [`VinParser::year_window`](struct.VinParser.html#method.year_window))
+    ///
+    /// # Examples
+    /// ```
+    /// let parser = vin::VinParser::new().with_clock(|| 2016);
+    /// let result = parser.parse("1M8GDM9AXKP042788").unwrap();
+    /// assert_eq!(result.year_window().range(), 1980..=2018);
+    /// assert_eq!(result.years().candidates, vec![1989]);
+    /// ```
+    pub fn year_window(&self) -> YearWindow { self.year_window }
+
+    /// Returns possible years of assembling within the [window](#method.year_window) with
+    /// the most likely one
+    pub fn years(&self) -> ModelYearEstimate { self.year_estimate(YearHints::default()) }
+
+    /// Returns possible years of assembling with the most likely one chosen using the given hints
+    ///
+    /// # Examples
+    /// ```
+    /// use vin::{YearHints, YearMethod};
+    ///
+    /// let result = vin::get_info("WP0ZZZ99ZTS392124").unwrap();
+    /// let hints = YearHints { currently_registered: true };
+    /// let estimate = result.year_estimate_within(vin::YearWindow::as_of(2024), hints);
+    /// assert_eq!(estimate.candidates, vec![1996, 2026]);
+    /// assert_eq!((estimate.best_guess, estimate.method), (Some(2026), YearMethod::Hint));
+    /// ```
+    pub fn year_estimate(&self, hints: YearHints) -> ModelYearEstimate {
+        self.year_estimate_within(self.year_window, hints)
+    }
+
+    /// Returns possible years of assembling within the given window with the most likely one
+    /// chosen using the given hints
+    pub fn year_estimate_within(&self, window: YearWindow, hints: YearHints) -> ModelYearEstimate {
+        self.estimate(self.years_within(window), hints)
+    }
+
+    /// Returns possible years of assembling agreeing with the given decade hint, the result is
+    /// unique if the hint leaves the only candidate
+    ///
+    /// # Examples
+    /// ```
+    /// use vin::{DecadeHint, YearMethod};
+    ///
+    /// let result = vin::get_info("WP0ZZZ99ZTS392124").unwrap();
+    /// let estimate = result.years_with_hint_within(vin::YearWindow::as_of(2024), DecadeHint::Before(2001));
+    /// assert!(estimate.is_unique());
+    /// assert_eq!((estimate.best_guess, estimate.method), (Some(1996), YearMethod::Hint));
+    /// ```
+    pub fn years_with_hint(&self, hint: DecadeHint) -> ModelYearEstimate {
+        self.years_with_hint_within(self.year_window, hint)
+    }
+
+    /// Returns possible years of assembling within the given window agreeing with the given
+    /// decade hint
+    pub fn years_with_hint_within(&self, window: YearWindow, hint: DecadeHint) -> ModelYearEstimate {
+        let mut candidates = self.years_within(window);
+        let ambiguous = candidates.len() > 1;
+        candidates.retain(|x| hint.contains(*x));
+
+        let mut estimate = self.estimate(candidates, YearHints::default());
+        if ambiguous && estimate.method == YearMethod::Unique {
+            estimate.method = YearMethod::Hint;
+        }
+        estimate
+    }
+
+    fn estimate(&self, candidates: Vec<u32>, hints: YearHints) -> ModelYearEstimate {
+        let position7 = self.as_bytes()[6];
+        let north_american = matches!(self.as_bytes()[0], b'1'..=b'5');
+
+        let (best_guess, method) = match candidates.as_slice() {
+            [] => (None, YearMethod::Unknown),
+            [year] => (Some(*year), YearMethod::Unique),
+            _ if north_american => {
+                let guess = if position7.is_ascii_digit() {
+                    candidates.iter().rev().find(|x| **x < 2010)
+                } else {
+                    candidates.iter().find(|x| (2010..2040).contains(*x))
+                };
+                match guess {
+                    Some(year) => (Some(*year), YearMethod::Position7),
+                    None if hints.currently_registered => (candidates.last().copied(), YearMethod::Hint),
+                    None => (None, YearMethod::Ambiguous),
+                }
+            }
+            _ if hints.currently_registered => (candidates.last().copied(), YearMethod::Hint),
+            _ => (None, YearMethod::Ambiguous),
+        };
+
+        ModelYearEstimate { candidates, best_guess, method }
+    }
+
+    /// Returns possible years of assembling, using the given year instead of the current one
+    ///
+    /// # Examples
+    /// ```
+    /// let result = vin::get_info("WP0ZZZ99ZTS392124").unwrap();
+    /// assert_eq!(result.years_as_of(2020), vec![1996]);
+    /// assert_eq!(result.years_as_of(2024), vec![1996, 2026]);
+    /// ```
+    pub fn years_as_of(&self, year: u32) -> Vec<u32> { self.years_within(YearWindow::as_of(year)) }
+
+    /// Returns possible years of assembling within the given range
+    ///
+    /// # Examples
+    /// ```
+    /// let result = vin::get_info("WP0ZZZ99ZTS392124").unwrap();
+    /// assert_eq!(result.years_in(1980..=2039), vec![1996, 2026]);
+    /// assert_eq!(result.years_in(1995..=2015), vec![1996]);
+    /// ```
+    pub fn years_in(&self, range: RangeInclusive<u32>) -> Vec<u32> {
+        self.years_within(YearWindow::new(range))
+    }
+
+    /// Returns possible years of assembling within the given window
+    pub fn years_within(&self, window: YearWindow) -> Vec<u32> {
+        match self.vis().chars().next() {
+            Some(letter) => window.years(letter),
+            None => vec![],
+        }
+    }
+
+    /// Returns possible years of assembling, using the year of the given date instead of
+    /// the current one
+    #[cfg(feature = "chrono")]
+    pub fn years_as_of_date(&self, date: NaiveDate) -> Vec<u32> {
+        self.years_within(YearWindow::as_of_date(date))
+    }
+
+    /// Returns the first days of possible model years, using the year of the given date instead
+    /// of the current one
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(feature = "chrono")] {
+    /// use chrono::NaiveDate;
+    ///
+    /// let result = vin::get_info("WP0ZZZ99ZTS392124").unwrap();
+    /// let today = NaiveDate::from_ymd_opt(2020, 6, 1).unwrap();
+    /// assert_eq!(result.model_year_dates_as_of(today), vec![NaiveDate::from_ymd_opt(1996, 1, 1).unwrap()]);
+    /// # }
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn model_year_dates_as_of(&self, date: NaiveDate) -> Vec<NaiveDate> {
+        self.years_as_of_date(date)
+            .into_iter()
+            .filter_map(|x| NaiveDate::from_ymd_opt(x as i32, 1, 1))
+            .collect()
+    }
+}
@@ -0,0 +1,57 @@
+// Allocations are counted by the global allocator, so they're tested in a separate test binary
+// (counters of the `metrics` feature allocate their keys, so it's skipped with the feature)
+#![cfg(not(feature = "metrics"))]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use vin::{check_validity, get_info, verify_checksum, Interner, VinInfo};
+
+struct Counting;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|x| x.set(x.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) { System.dealloc(ptr, layout) }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Returns count of allocations made by the current thread while running `f`
+fn allocations<T>(f: impl FnOnce() -> T) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    drop(f());
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+#[test]
+fn clean_input_is_not_copied() {
+    // warm up lazily initialized dictionaries
+    get_info("1M8GDM9AXKP042788").unwrap();
+
+    assert_eq!(allocations(|| check_validity("1M8GDM9AXKP042788").unwrap()), 0);
+    assert_eq!(allocations(|| verify_checksum("1M8GDM9AXKP042788").unwrap()), 0);
+    assert_eq!(allocations(|| verify_checksum("WP0ZZZ99ZTS392124").unwrap_err()), 0);
+    assert!(allocations(|| check_validity("1m8gdm9axkp042788").unwrap()) > 0);
+
+    // only the decoded manufacturer, country and region are allocated
+    assert_eq!(allocations(|| get_info("1M8GDM9AXKP042788").unwrap()), 3);
+
+    // borrowed information of the canonical VIN is not allocated at all
+    assert_eq!(allocations(|| VinInfo::parse("1M8GDM9AXKP042788").unwrap()), 0);
+    assert_eq!(allocations(|| VinInfo::parse("1m8gdm9axkp042788").unwrap()), 1);
+
+    // interned names are allocated only on the first occurrence
+    let interner = Interner::new();
+    let info = VinInfo::parse("1M8GDM9AXKP042788").unwrap();
+    interner.intern_info(&info);
+    assert_eq!(allocations(|| interner.intern_info(&info)), 0);
+}
@@ -0,0 +1,74 @@
+// Global configuration is process-wide, so it's tested in a separate test binary
+use vin::config::{Normalization, ValidationProfile};
+use vin::{configure, GlobalConfig};
+use vin::source::{BuiltinSource, WmiRecord, WmiSource};
+use vin::{get_info, verify_checksum, VINError};
+
+struct Fleet;
+
+impl WmiSource for Fleet {
+    fn lookup(&self, wmi: &str) -> Option<WmiRecord> {
+        match wmi {
+            "XTA" => Some(WmiRecord::new("Lada", "Russia", "Europe")),
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn global_config() {
+    // defaults are used before configuration
+    assert!(get_info(" 1M8GDM9AXKP042788").is_err());
+    assert!(get_info("WP0ZZZ99ZTS392124").is_ok());
+
+    configure(
+        GlobalConfig::new()
+            .with_source(Fleet.or(BuiltinSource))
+            .with_profile(ValidationProfile::RequireChecksum)
+            .with_normalization(Normalization::StripSeparators)
+            .with_clock(|| 2016)
+            .with_year_policy(vin::YearPolicy { lookahead_years: 3 }),
+    ).unwrap();
+    assert!(configure(GlobalConfig::new()).is_err());
+
+    assert_eq!(get_info(" 1m8-gdm9a-xkp042788\n").unwrap().as_str(), "1M8GDM9AXKP042788");
+    assert!(verify_checksum("1M8 GDM9A XKP 042788").is_ok());
+    assert!(matches!(get_info("WP0ZZZ99ZTS392124"), Err(VINError::ChecksumError(_))));
+    assert_eq!(get_info("XTA21099843576182").unwrap().manufacturer.unwrap(), "Lada");
+    assert_eq!(get_info("WP0ZZZ998TS392124").unwrap().manufacturer.unwrap(), "Porsche car");
+    assert_eq!(get_info("1M8GDM9AXKP042788").unwrap().years().candidates, [1989, 2019]);
+    assert_eq!(vin::YearWindow::default().range(), 1980..=2019);
+
+    // VINs decoded by other parsers use their own clock and year policy
+    let parser = vin::VinParser::new().with_clock(|| 2016).with_year_policy(vin::YearPolicy { lookahead_years: 0 });
+    let result = parser.parse("1M8GDM9AXKP042788").unwrap();
+    assert_eq!(result.year_window().range(), 1980..=2016);
+    assert_eq!(result.years().candidates, [1989]);
+    let result = vin::VinParser::new().with_clock(|| 2049).parse("1M8GDM9AXKP042788").unwrap();
+    assert_eq!(result.years().candidates, [1989, 2019, 2049]);
+
+    // barcode fields rejected by the configured profile are skipped
+    assert_eq!(vin::barcode::parse("WP0ZZZ99ZTS392124;1M8GDM9AXKP042788").unwrap().as_str(), "1M8GDM9AXKP042788");
+    assert!(matches!(vin::barcode::parse("WP0ZZZ99ZTS392124;LOT42"), Err(VINError::ChecksumError(_))));
+
+    // generated VINs are decoded with the configured source
+    let mut sequence = vin::registry::VinSequence::new("XTA", "21099", 'L', '3').unwrap();
+    assert_eq!(sequence.next().unwrap().manufacturer.as_deref(), Some("Lada"));
+
+    // packed VINs are restored without validation
+    let compact = vin::VinParser::new().parse("WP0ZZZ99ZTS392124").unwrap().to_compact();
+    let restored = vin::VIN::from_compact(&compact).unwrap();
+    assert_eq!((restored.as_str(), restored.manufacturer.as_deref()), ("WP0ZZZ99ZTS392124", Some("Porsche car")));
+
+    // positions of transposed symbols refer to the normalized VIN
+    assert_eq!(vin::detect_transposition("1m8-gdm9a-xkp402788"), Some((11, 12)));
+    assert_eq!(vin::detect_transposition("\u{3000}1M8GDMA9XKP042788"), Some((6, 7)));
+    assert_eq!(vin::transposition_candidates("\u{3000}1M8GDM9AXKP042788"), []);
+
+    // validation of the free functions normalizes the input as well
+    assert!(vin::check_validity("1M8-GDM9A-XKP042788").is_ok());
+    assert_eq!(vin::mask(" 1m8-gdm9a-xkp042788", vin::MaskPolicy::KeepWmi).unwrap(), "1M8**************");
+    let mut set = vin::index::VinSet::new();
+    assert!(set.insert("1M8 GDM9A XKP 042788").unwrap());
+    assert!(set.contains("1M8GDM9AXKP042788") && set.contains("1m8-gdm9a-xkp042788"));
+}
@@ -0,0 +1,81 @@
+// Lazily initialized dictionaries must be initialized exactly once when first touched by many
+// threads at once, so it's tested in a separate test binary (nothing is initialized beforehand)
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+use vin::config::GlobalConfig;
+use vin::rules::{DecodeContext, Rule};
+use vin::source::{BuiltinSource, WmiSource};
+use vin::watchlist::{MemoryWatchlist, Watchlist};
+use vin::{
+    CachedParser, ChecksumStatus, Clock, Finding, FullValidation, Interner, InternedVin, SystemClock,
+    ValidationReport, VINError, VinInfo, VinParser, VIN,
+};
+
+fn assert_send_sync<T: Send + Sync + ?Sized>() {}
+
+#[test]
+fn public_types_are_send_and_sync() {
+    assert_send_sync::<VIN>();
+    assert_send_sync::<VINError>();
+    assert_send_sync::<ChecksumStatus>();
+    assert_send_sync::<VinInfo<'static>>();
+    assert_send_sync::<VinParser>();
+    assert_send_sync::<CachedParser>();
+    assert_send_sync::<GlobalConfig>();
+    assert_send_sync::<ValidationReport>();
+    assert_send_sync::<FullValidation>();
+    assert_send_sync::<Finding>();
+    assert_send_sync::<Interner>();
+    assert_send_sync::<InternedVin>();
+    assert_send_sync::<MemoryWatchlist>();
+    assert_send_sync::<dyn WmiSource>();
+    assert_send_sync::<dyn Watchlist>();
+    assert_send_sync::<dyn Rule>();
+    assert_send_sync::<dyn Clock>();
+    assert_send_sync::<SystemClock>();
+    assert_send_sync::<DecodeContext<'static>>();
+    assert_send_sync::<BuiltinSource>();
+}
+
+#[test]
+fn concurrent_first_decode() {
+    const THREADS: usize = 16;
+    let numbers = ["1M8GDM9AXKP042788", "WP0ZZZ99ZTS392124", "AP0ZZZ99ZTS392124", "1m8gdm9axkp042789"];
+    let barrier = Arc::new(Barrier::new(THREADS));
+    let parser = Arc::new(VinParser::new().with_cache(8));
+
+    let handles: Vec<_> = (0..THREADS)
+        .map(|i| {
+            let barrier = barrier.clone();
+            let parser = parser.clone();
+            thread::spawn(move || {
+                // every thread touches the dictionaries for the first time simultaneously
+                barrier.wait();
+                (0..200)
+                    .map(|j| {
+                        let number = numbers[(i + j) % numbers.len()];
+                        let result = match j % 3 {
+                            0 => vin::get_info(number).unwrap(),
+                            1 => parser.parse(number).unwrap(),
+                            _ => VinInfo::parse(number).unwrap().into_owned(),
+                        };
+                        (result.manufacturer, result.valid_checksum.is_invalid())
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+
+    let expected: Vec<_> = numbers
+        .iter()
+        .map(|x| vin::get_info(x).unwrap())
+        .map(|x| (x.manufacturer, x.valid_checksum.is_invalid()))
+        .collect();
+    for (i, handle) in handles.into_iter().enumerate() {
+        for (j, result) in handle.join().unwrap().into_iter().enumerate() {
+            assert_eq!(result, expected[(i + j) % numbers.len()]);
+        }
+    }
+    assert!(parser.cache_len() <= 8);
+}
@@ -1,8 +1,9 @@
 extern crate vin;
 
-use vin::{check_validity, get_info, verify_checksum, VINError};
+use vin::{check_validity, compute_check_digit, get_info, verify_checksum, VINError};
 
 #[test]
+#[allow(clippy::match_like_matches_macro)]
 fn check_length() {
     let erroneous = check_validity("");
     assert!(erroneous.is_err() && match erroneous.unwrap_err() {
@@ -15,6 +16,7 @@ fn check_length() {
 }
 
 #[test]
+#[allow(clippy::match_like_matches_macro)]
 fn check_alphabet() {
     let erroneous = check_validity("abcdefghioq_958.!");
     assert!(erroneous.is_err() && match erroneous.unwrap_err() {
@@ -27,12 +29,14 @@ fn check_alphabet() {
 }
 
 #[test]
+#[allow(clippy::match_like_matches_macro)]
 fn checksum() {
     let erroneous = verify_checksum("WP0ZZZ99ZTS392124");
     assert!(match erroneous.unwrap_err() {
         vin::VINError::ChecksumError(vin::ChecksumErrorInfo {
                                          expected: '8',
                                          received: 'Z',
+                                         ..
                                      }) => true,
         _ => false,
     });
@@ -41,6 +45,512 @@ fn checksum() {
     assert!(valid.is_ok())
 }
 
+#[test]
+fn checksum_diagnostics() {
+    let info = match verify_checksum("1M8GEM9AXKP042788") {
+        Err(VINError::ChecksumError(info)) => info,
+        _ => panic!("checksum error expected"),
+    };
+    assert_eq!(info.sum, 355);
+    assert_eq!(info.contributions.iter().sum::<u32>(), info.sum);
+    assert_eq!(info.contributions[4], 5 * 4);
+    assert_eq!(info.contributions[8], 0);
+
+    let candidates = info.candidates();
+    assert!(candidates.contains(&(4, vec!['4', 'D', 'M', 'U'])));
+    assert!(candidates.contains(&(8, vec!['3'])));
+    assert!(candidates.iter().all(|(i, _)| *i != 5 && *i != 13));
+    assert_eq!(info.most_likely_wrong_position(), None);
+
+    let info = match verify_checksum("WP0ZZZ99ZTS392124") {
+        Err(VINError::ChecksumError(info)) => info,
+        _ => panic!("checksum error expected"),
+    };
+    assert_eq!(info.candidates(), vec![(8, vec!['8'])]);
+    assert_eq!(info.most_likely_wrong_position(), Some(8));
+}
+
+#[test]
+fn transpositions() {
+    use vin::{detect_transposition, transposition_candidates};
+
+    assert_eq!(detect_transposition("1m8gdm9axkp402788"), Some((11, 12)));
+    assert_eq!(detect_transposition("1FTFW1E51DF000410"), Some((15, 16)));
+
+    // both swaps satisfy the checksum, but "M18" is not a known WMI
+    assert_eq!(detect_transposition("M18GDM9AXKP042788"), Some((0, 1)));
+    assert_eq!(detect_transposition("1M8GDM9AXKP047288"), None);
+    assert_eq!(transposition_candidates("1M8GDM9AXKP047288"), vec![(13, 14), (14, 15)]);
+
+    assert!(transposition_candidates("1M8GDM9AXKP042788").is_empty());
+    assert!(transposition_candidates("1M8GDM9AXKP04278").is_empty());
+}
+
+#[test]
+fn barcode_payloads() {
+    use vin::barcode::parse;
+
+    assert_eq!(parse("1M8GDM9AXKP042788").unwrap().as_str(), "1M8GDM9AXKP042788");
+    assert_eq!(parse("*i1m8gdm9axkp042788*").unwrap().as_str(), "1M8GDM9AXKP042788");
+    assert_eq!(parse(" 1V1M8GDM9AXKP042788\r\n").unwrap().as_str(), "1M8GDM9AXKP042788");
+
+    // field with valid checksum is preferred
+    let payload = "[)>\u{1e}06\u{1d}P1WP0ZZZ99ZTS39212\u{1d}1VWP0ZZZ99ZTS392124\u{1d}1V1M8GDM9AXKP042788\u{1e}\u{4}";
+    assert_eq!(parse(payload).unwrap().as_str(), "1M8GDM9AXKP042788");
+    let result = parse("WP0ZZZ99ZTS392124;LOT42").unwrap();
+    assert!(!result.valid_checksum.is_valid());
+
+    assert!(matches!(parse(""), Err(VINError::IncorrectLength)));
+    assert!(matches!(parse("1M8GDM9AXKP04278"), Err(VINError::IncorrectLength)));
+    assert!(matches!(parse("1M8GDM9AXKP04278O"), Err(VINError::InvalidCharacters(_))));
+}
+
+#[test]
+fn extract_from_text() {
+    use vin::extract::find_all;
+
+    let text = "VIN: 1m8gdm9axkp042788; old one — «WP0ZZZ99ZTS392124», not XWP0ZZZ99ZTS392124 or WP0ZZZ99ZTS392124é.";
+    let found = find_all(text);
+    assert_eq!(found.len(), 2);
+
+    assert_eq!(found[0].vin, "1M8GDM9AXKP042788");
+    assert_eq!(&text[found[0].start..found[0].end], "1m8gdm9axkp042788");
+    assert!(found[0].valid_checksum);
+    assert!((found[0].confidence - 1.0).abs() < 1e-6);
+
+    assert_eq!(&text[found[1].start..found[1].end], "WP0ZZZ99ZTS392124");
+    assert!(!found[1].valid_checksum);
+    assert!((found[1].confidence - 0.7).abs() < 1e-6);
+
+    // structurally invalid sequences are skipped
+    assert!(find_all("INTERNATIONALIZED 1M8GDM9AXKP04278O 1M8GDM9AXKP04278").is_empty());
+    assert!(find_all("").is_empty());
+}
+
+#[test]
+fn registry_sequence() {
+    use vin::registry::{RegistryError, VinSequence};
+
+    let mut sequence = VinSequence::new("wp0", "zzz99", 'S', 'S').unwrap().starting_at(999_998).unwrap();
+    let first = sequence.next().unwrap();
+    assert_eq!(first.vis(), "SS999998");
+    assert!(first.valid_checksum.is_valid());
+    assert_eq!(sequence.next().unwrap().vis(), "SS999999");
+    assert!(matches!(sequence.next(), Err(RegistryError::SerialExhausted)));
+
+    // serials restart with the new model year and continue when returning to the used one
+    sequence.set_model_year('t').unwrap();
+    assert_eq!(sequence.model_year(), 'T');
+    let restarted = sequence.next().unwrap();
+    assert_eq!(restarted.vis(), "TS000001");
+    sequence.set_model_year('S').unwrap();
+    assert!(matches!(sequence.next(), Err(RegistryError::SerialExhausted)));
+    sequence.set_model_year('T').unwrap();
+
+    // externally issued VINs are skipped and duplicates detected
+    let external = VinSequence::new("WP0", "ZZZ99", 'T', 'S').unwrap().starting_at(2).unwrap().next().unwrap();
+    sequence.register(&external).unwrap();
+    assert!(matches!(sequence.register(&restarted), Err(RegistryError::Duplicate(_))));
+    assert_eq!(sequence.next().unwrap().vis(), "TS000003");
+    assert_eq!(sequence.issued(), 5);
+
+    let small = VinSequence::new("1G9", "AB123", 'L', 'A').unwrap().with_manufacturer_code("A42").unwrap();
+    assert_eq!(small.max_serial(), 999);
+    assert!(matches!(small.starting_at(1000), Err(RegistryError::InvalidSerial(1000))));
+
+    assert!(matches!(VinSequence::new("WP", "ZZZ99", 'S', 'S'), Err(RegistryError::InvalidWmi(_))));
+    assert!(matches!(VinSequence::new("WP0", "ZZZ9O", 'S', 'S'), Err(RegistryError::InvalidDescriptor(_))));
+    assert!(matches!(VinSequence::new("WP0", "ZZZ99", 'U', 'S'), Err(RegistryError::InvalidYear('U'))));
+    assert!(matches!(VinSequence::new("WP0", "ZZZ99", 'S', 'I'), Err(RegistryError::InvalidPlant('I'))));
+    assert!(matches!(
+        VinSequence::new("WP0", "ZZZ99", 'S', 'S').unwrap().with_manufacturer_code("A42"),
+        Err(RegistryError::InvalidManufacturerCode(_))
+    ));
+}
+
+#[test]
+fn vin_set() {
+    use vin::index::VinSet;
+
+    let mut set = VinSet::new();
+    assert!(set.is_empty());
+    for (_, vin_number) in vin::testdata::valid() {
+        assert!(set.insert(vin_number).unwrap());
+        assert!(!set.insert(&vin_number.to_lowercase()).unwrap());
+    }
+    assert_eq!(set.len(), vin::testdata::valid().len());
+    assert!(set.contains("wp0zzz991sa000301"));
+    assert!(!set.contains("WP0ZZZ991SA000302"));
+    assert!(!set.contains("WP0ZZZ991SA00030"));
+    assert!(matches!(set.insert(vin::testdata::INVALID_CHARACTERS[0]), Err(VINError::InvalidCharacters(_))));
+
+    // approximate set never misses inserted VINs and rarely reports absent ones
+    let mut bloom = VinSet::bloom(1000, 0.01);
+    assert!(bloom.is_approximate());
+    let vins: Vec<String> = (0..2000).map(|i| format!("1M8GDM9AXKP{:06}", i)).collect();
+    for vin_number in &vins[..1000] {
+        bloom.insert(vin_number).unwrap();
+    }
+    assert!(vins[..1000].iter().all(|x| bloom.contains(x)));
+    let false_positives = vins[1000..].iter().filter(|x| bloom.contains(x)).count();
+    assert!(false_positives < 50, "{} false positives", false_positives);
+}
+
+#[test]
+fn compact_encoding() {
+    use vin::VIN;
+
+    let mut previous: Option<(String, [u8; 11])> = None;
+    let mut vins: Vec<&str> = vin::testdata::valid().into_iter().map(|(_, x)| x).collect();
+    vins.sort_unstable();
+    for vin_number in vins {
+        let compact = vin::get_info(vin_number).unwrap().to_compact();
+        let restored = VIN::from_compact(&compact).unwrap();
+        assert_eq!(restored.as_str(), vin_number);
+        assert!(restored.valid_checksum.is_valid());
+        if let Some((vin, bytes)) = previous {
+            assert_eq!(vin.as_str() < vin_number, bytes < compact);
+        }
+        previous = Some((vin_number.to_owned(), compact));
+    }
+
+    assert_eq!(VIN::from_compact(&[0; 11]).unwrap().as_str(), "00000000000000000");
+    assert!(VIN::from_compact(&[0xff; 11]).is_none());
+}
+
+#[test]
+fn parser_cache() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use vin::source::{BuiltinSource, WmiRecord, WmiSource};
+    use vin::VinParser;
+
+    struct Counting(Arc<AtomicUsize>);
+
+    impl WmiSource for Counting {
+        fn lookup(&self, wmi: &str) -> Option<WmiRecord> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            BuiltinSource.lookup(wmi)
+        }
+    }
+
+    let lookups = Arc::new(AtomicUsize::new(0));
+    let parser = VinParser::with_source(Counting(lookups.clone())).with_cache(2);
+
+    // serial number and check digit are not part of the squish VIN
+    let first = parser.parse("WP0ZZZ998TS392124").unwrap();
+    let second = parser.parse("wp0zzz99zts392125").unwrap();
+    assert_eq!(lookups.load(Ordering::SeqCst), 1);
+    assert_eq!(second.manufacturer, first.manufacturer);
+    assert!(first.valid_checksum.is_valid() && !second.valid_checksum.is_valid());
+
+    parser.parse("1M8GDM9AXKP042788").unwrap();
+    parser.parse("WP0ZZZ998TS392124").unwrap();  // refreshes the entry
+    parser.parse("2T1BU4EE1DC000402").unwrap();  // evicts 1M8GDM9AKP
+    assert_eq!(lookups.load(Ordering::SeqCst), 3);
+    assert_eq!(parser.cache_len(), 2);
+    parser.parse("WP0ZZZ998TS392124").unwrap();
+    assert_eq!(lookups.load(Ordering::SeqCst), 3);
+    parser.parse("1M8GDM9AXKP042788").unwrap();
+    assert_eq!(lookups.load(Ordering::SeqCst), 4);
+
+    parser.clear_cache();
+    assert_eq!(parser.cache_len(), 0);
+    parser.parse("WP0ZZZ998TS392124").unwrap();
+    assert_eq!(lookups.load(Ordering::SeqCst), 5);
+
+    // zero capacity disables caching
+    let parser = VinParser::new().with_cache(0);
+    parser.parse("WP0ZZZ998TS392124").unwrap();
+    assert_eq!(parser.cache_len(), 0);
+}
+
+#[test]
+fn latin_american_wmis() {
+    let info = |vin: &str| {
+        let vin = get_info(vin).unwrap();
+        (vin.manufacturer, vin.country)
+    };
+    let expect = |manufacturer: &str, country: &str| (Some(manufacturer.to_owned()), Some(country.to_owned()));
+
+    assert_eq!(info("3AKJHHDR7KSKE1234"), expect("Freightliner Mexico", "Mexico"));
+    assert_eq!(info("8LDETA0307B123456"), expect("Chevrolet Ecuador", "Ecuador"));
+    assert_eq!(info("9GAJM52385B123456"), expect("Chevrolet Colombia", "Colombia"));
+    assert_eq!(info("9BWZZZ377VT004251"), expect("Volkswagen", "Brazil"));
+
+    // Mexico
+    assert_eq!(info("3FA6P0H73HR123456"), expect("Ford Mexico", "Mexico"));
+    assert_eq!(info("3N1CN7AP8HL123456"), expect("Nissan Mexico", "Mexico"));
+    assert_eq!(info("3HGGK5H85KM123456"), expect("Honda Mexico", "Mexico"));
+    assert_eq!(info("3D7KS28C56G123456"), expect("Dodge Mexico", "Mexico"));
+
+    // Brazil
+    assert_eq!(info("9BD17164LB5123456"), expect("Fiat Automóveis", "Brazil"));
+    assert_eq!(info("9BM958074AB123456"), expect("Mercedes Benz", "Brazil"));
+    assert_eq!(info("9BSR6X40003123456"), expect("Scania", "Brazil"));
+    assert_eq!(info("93XGNK7408C123456"), expect("Souza Ramos - Mitsubishi / Suzuki", "Brazil"));
+    assert_eq!(info("9BHBG51CAKP123456"), expect("Hyundai Motor Company / Hyundai", "Brazil"));
+
+    // Argentina
+    assert_eq!(info("8AC906633CE123456"), expect("Mercedes Benz", "Argentina"));
+    assert_eq!(info("8BCLDRFJWBG123456"), expect("Citroën", "Argentina"));
+}
+
+#[test]
+fn body_styles() {
+    use vin::body::BodyStyle;
+
+    assert_eq!(get_info("5YJ3E1EA7KF317000").unwrap().body_style(), Some(BodyStyle::Sedan));
+    assert_eq!(get_info("WP0CA2A98GS120123").unwrap().body_style(), Some(BodyStyle::Convertible));
+    assert_eq!(get_info("3C6TRVAG5JE123456").unwrap().body_style(), Some(BodyStyle::Van));
+    assert_eq!(get_info("1M8GDM9AXKP042788").unwrap().body_style(), None);
+}
+
+#[test]
+fn engine_decoding() {
+    use vin::engine::{EngineInfo, FuelType};
+
+    let camry = get_info("4T1BD1FK5EU123456").unwrap();
+    assert_eq!(camry.engine_info(), Some(EngineInfo { fuel: FuelType::Hybrid, displacement_cc: Some(2500) }));
+
+    let tesla = get_info("5YJ3E1EA7KF317000").unwrap();
+    assert_eq!(tesla.engine_info().unwrap().fuel, FuelType::Electric);
+    assert_eq!(tesla.engine_info().unwrap().displacement_cc, None);
+
+    // covered manufacturer, unknown engine code
+    assert_eq!(get_info("1C6RR7LZ5ES123456").unwrap().engine_info(), None);
+}
+
+#[test]
+fn ev_detection() {
+    use vin::ev::EvStatus;
+
+    assert_eq!(get_info("WVWZZZE1ZMP012345").unwrap().is_probably_ev(), EvStatus::Yes);
+    assert_eq!(get_info("KM8K33AG5LU012345").unwrap().is_probably_ev(), EvStatus::Yes);
+    assert_eq!(get_info("WP0ZZZ99ZTS392124").unwrap().is_probably_ev(), EvStatus::No);
+    assert_eq!(get_info("WVWZZZ1KZAW000001").unwrap().is_probably_ev(), EvStatus::Unknown);
+}
+
+#[test]
+fn gb16735_profile() {
+    use vin::config::ValidationProfile;
+
+    let parser = vin::VinParser::new().with_profile(ValidationProfile::Gb16735);
+    assert_eq!(parser.parse("LVVDB11B7AD123456").unwrap().manufacturer.as_deref(), Some("Chery"));
+    assert!(matches!(parser.parse("LVVDB11B0AD123456"), Err(VINError::ChecksumError(_))));
+    assert!(parser.parse("WP0ZZZ99ZTS392124").is_ok());
+
+    assert_eq!(get_info("LRW3E7FA0LC012345").unwrap().manufacturer.as_deref(), Some("Tesla"));
+}
+
+#[test]
+fn india_market() {
+    use vin::market::india::{self, Segment};
+
+    let hero = get_info("MBLHAR073KHM12345").unwrap();
+    assert!(india::is_indian(&hero));
+    assert_eq!(hero.manufacturer.as_deref(), Some("Hero MotoCorp"));
+    assert_eq!(india::manufacturer(&hero).unwrap().segment, Segment::TwoWheeler);
+    assert_eq!(india::manufacture_month(&hero), Some(8));
+
+    let maruti = get_info("MA3EWDE1S00123456").unwrap();
+    assert_eq!(india::manufacturer(&maruti).unwrap().name, "Maruti Suzuki");
+    assert_eq!(india::manufacture_month(&maruti), None);
+
+    let porsche = get_info("WP0ZZZ99ZTS392124").unwrap();
+    assert!(!india::is_indian(&porsche));
+    assert_eq!(india::manufacturer(&porsche), None);
+}
+
+#[test]
+fn korea_market() {
+    use vin::market::korea;
+
+    let kia = get_info("KNDPC3A27F7123456").unwrap();
+    let details = korea::decode(&kia).unwrap();
+    assert_eq!(details.plant, Some("Gwangju"));
+    assert_eq!(details.engine, None);
+
+    let genesis = get_info("KMTG34LA1KU123456").unwrap();
+    assert_eq!(genesis.manufacturer.as_deref(), Some("Genesis"));
+    assert_eq!(korea::decode(&genesis).unwrap().plant, Some("Ulsan"));
+
+    assert_eq!(korea::decode(&get_info("WP0ZZZ99ZTS392124").unwrap()), None);
+}
+
+#[test]
+fn pickup_decoding() {
+    use vin::pickup::{Cab, Drive, PickupInfo};
+
+    let ford = get_info("1FTEX1C80AKA12345").unwrap().pickup_info();
+    assert_eq!(ford, Some(PickupInfo { model: Some("F-150"), cab: Some(Cab::Extended), drive: Some(Drive::TwoWheel) }));
+
+    let gmc = get_info("1GTV2UEC5EZ123456").unwrap().pickup_info();
+    assert_eq!(gmc, None);
+    let chevrolet = get_info("1GCVKREC5EZ123456").unwrap().pickup_info().unwrap();
+    assert_eq!(chevrolet.drive, Some(Drive::FourWheel));
+    assert_eq!(chevrolet.model, None);
+
+    assert_eq!(get_info("3C6RR7LT5ES123456").unwrap().pickup_info().unwrap().model, Some("Ram 1500"));
+}
+
+#[test]
+fn recall_lookup() {
+    use vin::recalls::{RecallDb, RecallError};
+    use vin::SquishVin;
+
+    let flat = [
+        "1\t19V001000\tFORD\tF-150\t2019\tFC\tAIR BAGS\tFord\t\t\tV\t100\t\tMFR\t\t\t\t\t\tInflator may rupture",
+        "2\t19V002000\tFORD\tF-150\t9999\tFC\tBRAKES\tFord\t\t\tV\t100\t\tMFR\t\t\t\t\t\tUnknown year",
+        "3\t19V003000\tFORD\tRANGER\t2019\tFC\tBRAKES\tFord\t\t\tV\t100\t\tMFR\t\t\t\t\t\tOther model",
+    ].join("\n");
+    let db = RecallDb::load_nhtsa(flat.as_bytes(), |make, model, year| match (make, model, year) {
+        ("FORD", "F-150", 2019) => vec![SquishVin::parse("1FTEW1EPKF").unwrap()],
+        _ => vec![],
+    }).unwrap();
+    assert_eq!(db.len(), 1);
+
+    let affected = get_info("1FTEW1EP9KFA12345").unwrap();
+    assert_eq!(db.recalls_for(&affected)[0].component, "AIR BAGS");
+    assert!(db.recalls_for(&get_info("1FTEW1EP9LFA12345").unwrap()).is_empty());
+
+    assert!(matches!(RecallDb::load("20V1\t1FTEW1EPKF\t2019".as_bytes()), Err(RecallError::InvalidLine(1))));
+    assert!(matches!(RecallDb::load("20V1\tBAD\t2019\tA\tB".as_bytes()), Err(RecallError::InvalidSquish(1, _))));
+}
+
+#[test]
+fn restraint_systems() {
+    let info = get_info("2G1WF5559C1123456").unwrap().restraint_info().unwrap();
+    assert_eq!(info.airbags, "driver and passenger front, side");
+    assert_eq!(info.seatbelts, "active manual");
+
+    assert_eq!(get_info("WP0ZZZ99ZTS392124").unwrap().restraint_info(), None);
+}
+
+#[test]
+fn jdm_frame_numbers() {
+    use vin::jdm::{detect, FrameNumber, Identifier};
+
+    let frame = match detect(" JZA80-1234567 ") {
+        Ok(Identifier::FrameNumber(frame)) => frame,
+        other => panic!("unexpected {:?}", other),
+    };
+    assert_eq!(frame, FrameNumber { model_code: "JZA80".to_owned(), serial: "1234567".to_owned() });
+    assert_eq!(frame.to_string(), "JZA80-1234567");
+
+    assert!(matches!(detect("1M8GDM9AXKP042788"), Ok(Identifier::Vin(_))));
+    assert!(matches!(detect("80JZA-1234567"), Err(VINError::IncorrectLength)));
+    assert!(matches!(detect("JZA80-12A4567"), Err(VINError::IncorrectLength)));
+    assert!(matches!(detect("JZA80-1234567-JZA8"), Err(VINError::IncorrectLength)));
+}
+
+#[test]
+fn uk_market() {
+    use vin::market::uk::{self, UkIssue};
+
+    let lotus = get_info("SCCPC11125HA12345").unwrap();
+    assert!(uk::is_uk(&lotus));
+    assert_eq!(lotus.manufacturer.as_deref(), Some("Lotus Cars"));
+    assert!(uk::check(&lotus).is_empty());
+
+    let kit = get_info("SA9AB123XK1MCA123").unwrap();
+    assert_eq!(kit.manufacturer.as_deref(), Some("UK small manufacturer"));
+    assert_eq!(uk::small_manufacturer_code(&kit), Some("MCA"));
+    assert!(uk::check(&kit).is_empty());
+
+    let old = get_info("SAL000000Z0001234").unwrap();
+    assert_eq!(uk::check(&old), vec![UkIssue::NoModelYear('Z')]);
+
+    let porsche = get_info("WP0ZZZ99ZTS392124").unwrap();
+    assert!(!uk::is_uk(&porsche));
+    assert!(uk::check(&porsche).is_empty());
+}
+
+#[test]
+fn watchlist_matching() {
+    use vin::watchlist::{MemoryWatchlist, Watchlist};
+
+    let csv = "vin,plate\n1M8GDM9AXKP042788,AB123\nnot a vin,CD456\n\"wp0zzz99zts392124\",EF789\n";
+    let (list, skipped) = MemoryWatchlist::load_csv_counted(csv.as_bytes()).unwrap();
+    assert_eq!(list.len(), 2);
+    assert_eq!(skipped, 2);
+    assert!(list.contains(&get_info("WP0ZZZ99ZTS392124").unwrap()));
+
+    let report = vin::VinParser::new().validate("1M8GDM9AXKP042788").unwrap();
+    assert_eq!(report.watchlisted, None);
+    let report = vin::VinParser::new().with_watchlist(list).validate("1M8GDM9AXKP042788").unwrap();
+    assert_eq!(report.watchlisted, Some(true));
+}
+
+#[test]
+fn brand_aliases() {
+    use vin::Manufacturer;
+
+    assert_eq!(Manufacturer::from_wmi("WP1").unwrap().canonical_brand(), "Porsche");
+    assert_eq!(Manufacturer::from_wmi("JNK").unwrap().canonical_brand(), "Infiniti");
+    assert_eq!(Manufacturer::from_wmi("WAU").unwrap().canonical_brand(), "Audi");
+    assert_eq!(vin::canonical_brand("skoda"), "Škoda");
+    assert!(vin::brand_aliases().iter().all(|(_, brand)| vin::canonical_brand(brand) == *brand));
+}
+
+#[test]
+fn parent_groups() {
+    use vin::{group_of, Manufacturer};
+
+    assert_eq!(get_info("3C6RR7LT5ES123456").unwrap().manufacturer_info().unwrap().parent_group(), Some("Stellantis"));
+    assert_eq!(Manufacturer::from_wmi("SAL").unwrap().parent_group(), Some("Tata Motors"));
+    assert_eq!(Manufacturer::from_wmi("1HD").unwrap().parent_group(), None);
+    assert_eq!(Manufacturer::from_wmi("AP0"), None);
+    assert_eq!(group_of("1GC"), Some("General Motors"));
+    assert_eq!(group_of("1G"), None);
+    assert_eq!(group_of("aé"), None);
+    assert_eq!(group_of("é12"), None);
+    assert_eq!(Manufacturer::from_wmi("WPé"), None);
+}
+
+#[test]
+fn ocr_candidates() {
+    use vin::ocr::{decode_candidates, decode_candidates_with_limit};
+
+    let mut chars: Vec<Vec<(char, f32)>> = "WP0ZZZ991SA000301".chars().map(|x| vec![(x, 1.0)]).collect();
+    chars[2] = vec![('O', 0.5), ('Q', 0.2), ('D', 0.3)];
+    chars[8] = vec![('1', 0.7), ('I', 0.2), ('7', 0.1)];
+    chars[9] = vec![('S', 0.6), ('5', 0.3), ('Z', 0.1)];
+    chars[14] = vec![('3', 0.5), ('8', 0.5)];
+
+    let candidates = decode_candidates(&chars);
+    assert!(!candidates.is_empty());
+    assert_eq!(candidates[0].vin, "WP0ZZZ991SA000301");
+    assert!((candidates[0].score - 0.9 * 0.6 * 0.7 * 0.5).abs() < 1e-6);
+    for (a, b) in candidates.iter().zip(candidates.iter().skip(1)) {
+        assert!(a.score >= b.score);
+    }
+    for candidate in &candidates {
+        assert!(verify_checksum(&candidate.vin).is_ok());
+        assert_ne!(candidate.vin.chars().nth(9), Some('Z'));
+    }
+
+    assert_eq!(decode_candidates_with_limit(&chars, 1).len(), 1);
+    assert!(decode_candidates(&chars[..16]).is_empty());
+
+    chars[0] = vec![('$', 1.0)];
+    assert!(decode_candidates(&chars).is_empty());
+}
+
+#[test]
+fn check_digit() {
+    assert_eq!(compute_check_digit("1M8GDM9AXKP042788").unwrap(), 'X');
+    assert_eq!(compute_check_digit("1m8gdm9a kp042788").unwrap(), 'X');
+    assert_eq!(compute_check_digit("WP0ZZZ99ZTS392124").unwrap(), '8');
+
+    assert!(matches!(compute_check_digit("1M8GDM9AXKP04278"), Err(VINError::IncorrectLength)));
+    assert!(match compute_check_digit("1M8GDM9AXKP04278O") {
+        Err(VINError::InvalidCharacters(chars)) => chars.iter().any(|x| x.character == 'O'),
+        _ => false
+    });
+}
+
 #[test]
 fn test_info() {
     let vin = "WP0ZZZ99ZTS392124";
@@ -49,12 +559,1601 @@ fn test_info() {
     assert!(result.is_ok());
 
     let result = result.unwrap();
-    assert_eq!(result.vin, vin);
-    assert_eq!(result.country, "Germany/West Germany");
-    assert_eq!(result.manufacturer, "Porsche car");
-    assert_eq!(result.region, "Europe");
-    assert!(match result.valid_checksum {
-        Err(info) => (info.expected == '8' && info.received == 'Z'),
-        Ok(_) => false
+    assert_eq!(result.as_str(), vin);
+    assert_eq!(result.as_bytes(), b"WP0ZZZ99ZTS392124");
+    assert_eq!((result.wmi(), result.vds(), result.vis()), ("WP0", "ZZZ99Z", "TS392124"));
+    assert_eq!(result.country.as_deref(), Some("Germany/West Germany"));
+    assert_eq!(result.manufacturer.as_deref(), Some("Porsche car"));
+    assert_eq!(result.region.as_deref(), Some("Europe"));
+    assert!(matches!(result.valid_checksum, vin::ChecksumStatus::NotApplicable));
+}
+
+#[test]
+fn checksum_status() {
+    use vin::ChecksumStatus;
+
+    assert!(matches!(get_info("1M8GDM9AXKP042788").unwrap().valid_checksum, ChecksumStatus::Valid));
+    assert!(matches!(get_info("WP0ZZZ998TS392124").unwrap().valid_checksum, ChecksumStatus::Valid));
+    assert!(matches!(get_info("WP0ZZZ99ZTS392124").unwrap().valid_checksum, ChecksumStatus::NotApplicable));
+
+    let status = get_info("1M8GDM9AXKP042789").unwrap().valid_checksum;
+    assert!(status.is_invalid() && !status.is_valid());
+    assert_eq!(status.error().map(|x| x.received), Some('X'));
+    assert!(get_info("LVVDB11B7AD123457").unwrap().valid_checksum.is_invalid());
+}
+
+#[test]
+fn check_digit_accessors() {
+    let result = get_info("LVVDB11B7AD123456").unwrap();
+    assert_eq!(result.check_digit(), '7');
+    assert!(result.is_checksum_applicable());
+
+    let result = get_info("JHMCM56557C404453").unwrap();
+    assert_eq!(result.check_digit(), '5');
+    assert!(!result.is_checksum_applicable());
+}
+
+#[test]
+fn manufacturer_reverse_lookup() {
+    assert_eq!(vin::wmis_for_manufacturer("AUDI"), ["93U", "93V", "99A", "TRU", "WA1", "WAU", "WAV", "WMB", "WUA"]);
+    assert_eq!(vin::wmis_for_manufacturer("Dr. Ing. h.c. F. Porsche"), Vec::<&str>::new());
+
+    let found = vin::search_manufacturers("AUD");
+    assert!(found.contains(&"Audi"));
+    assert!(found.iter().all(|x| x.to_lowercase().contains("aud")));
+    assert!(vin::search_manufacturers("no such manufacturer").is_empty());
+}
+
+#[test]
+fn structured_country() {
+    use vin::Country;
+
+    let result = get_info("1M8GDM9AXKP042788").unwrap();
+    let country = result.country().unwrap();
+    assert_eq!(Some(country.name()), result.country.as_deref());
+    assert_eq!(Some(country.region()), result.region.as_deref());
+    assert_eq!((country.iso_alpha2(), country.iso_alpha3()), (Some("US"), Some("USA")));
+    assert!(country.contains_code("1z") && !country.contains_code("2A"));
+
+    let latvia = Country::from_code("s1").unwrap();
+    assert_eq!(latvia.codes(), ("S1".to_owned(), "S4".to_owned()));
+    assert_eq!(latvia.iso_alpha2(), Some("LV"));
+    assert_eq!(latvia.to_string(), "Latvia");
+
+    assert_eq!(Country::from_code("XS").unwrap().iso_alpha2(), None);
+    assert_eq!(Country::from_code("A0"), None);
+}
+
+#[test]
+fn dictionary_enumeration() {
+    assert_eq!(vin::all_regions().count(), 6);
+    for region in vin::all_regions() {
+        assert!(vin::countries_in(region).all(|x| x.region() == region));
+    }
+
+    let european: Vec<vin::Country> = vin::countries_in("Europe").collect();
+    assert!(european.iter().any(|x| x.iso_alpha2() == Some("LV")));
+    assert!(vin::countries_in("Atlantis").next().is_none());
+
+    let german: Vec<&str> = vin::wmis_in_country("de").collect();
+    assert!(german.contains(&"WP0") && german.contains(&"WAU"));
+    assert_eq!(german.iter().filter(|x| **x == "WP0").count(), 1);
+    assert!(vin::wmis_in_country("Germany/West Germany").all(|x| x.starts_with('W')));
+    assert!(vin::wmis_in_country("Atlantis").next().is_none());
+}
+
+#[test]
+fn wmi_trie() {
+    use vin::dicts::lookup_longest;
+
+    let found = lookup_longest("1M8GDM9AXKP042788").unwrap();
+    assert_eq!((found.code.as_str(), found.country, found.manufacturer), ("1M", Some("United States"), Some("Mercury")));
+    assert_eq!(lookup_longest("1m1").unwrap().manufacturer, Some("Mack Truck"));
+    assert_eq!(lookup_longest("WA").unwrap().manufacturer, None);
+    assert_eq!(lookup_longest("W"), None);
+    assert_eq!(lookup_longest("I"), None);
+    assert_eq!(lookup_longest(""), None);
+
+    // small manufacturers fall back to WMI without extended code
+    let found = lookup_longest("SA9000000Z0001234").unwrap();
+    assert_eq!((found.code.as_str(), found.manufacturer), ("SA9", Some("UK small manufacturer")));
+    assert_eq!(get_info("SA9000000Z0001234").unwrap().manufacturer.as_deref(), found.manufacturer);
+
+    // extended code of the small manufacturer (12-14-th characters)
+    let found = lookup_longest("YT9AB1C23KA007012").unwrap();
+    assert_eq!((found.code.as_str(), found.country, found.manufacturer), ("YT9007", Some("Sweden"), Some("Koenigsegg")));
+    assert_eq!(get_info("yt9ab1c23ka007012").unwrap().manufacturer.as_deref(), Some("Koenigsegg"));
+    assert_eq!(lookup_longest("YT9AB1C23KA008012").unwrap().code, "YT");
+    assert!(get_info("YT9AB1C23KA008012").unwrap().manufacturer.is_none());
+    assert!(!vin::wmis_for_manufacturer("Koenigsegg").contains(&"YT9007"));
+}
+
+#[test]
+fn dataset_audit() {
+    let audit = vin::data::audit();
+    assert!(audit.wmis > 500);
+    assert!(audit.countries < audit.country_codes);
+    assert!(audit.overlapping.contains(&("1M".to_owned(), "1M1".to_owned())));
+    assert!(!audit.overlapping.iter().any(|(_, wmi)| wmi == "WP0"));
+
+    assert!(audit.missing_region.is_empty());
+    assert!(!audit.is_consistent());
+    for wmi in &audit.missing_country {
+        assert!(vin::Country::from_code(&wmi[..2]).is_none());
+    }
+}
+
+#[test]
+fn numeric_serial_rule() {
+    use vin::config::ValidationProfile;
+    use vin::VinParser;
+
+    let parser = VinParser::new().with_profile(ValidationProfile::NorthAmerica);
+    assert_eq!(parser.validate("1M8GDM9AXKP042788").unwrap().numeric_serial, Some(true));
+    assert!(matches!(parser.parse("1M8GDM9AXKP042789"), Err(VINError::ChecksumError(_))));
+    assert!(parser.parse("WP0ZZZ99ZTS392124").is_ok());
+    assert_eq!(parser.validate("WP0ZZZ99ZTS392124").unwrap().numeric_serial, None);
+
+    // check digit is computed over the non-numeric serial
+    let fabricated = "1M8GDM9A3KP0A2788";
+    assert!(verify_checksum(fabricated).is_ok());
+    assert!(matches!(parser.parse(fabricated), Err(VINError::NonNumericSerial(13))));
+    assert_eq!(VinParser::new().validate(fabricated).unwrap().numeric_serial, Some(false));
+
+    // small manufacturers use 12-14-th symbols as the manufacturer code
+    let small = get_info("1G9AB12C5LABC1234").unwrap();
+    assert_eq!(small.has_numeric_serial(), Some(true));
+    assert_eq!(get_info("1G9AB12C5LABC12X4").unwrap().has_numeric_serial(), Some(false));
+}
+
+#[test]
+fn placeholder_serials() {
+    use vin::{ValidationWarning, VinParser};
+
+    let parser = VinParser::new();
+    let warnings = |vin: &str| parser.validate(vin).unwrap().warnings;
+    assert_eq!(warnings("1M8GDM9AXKP111111"), [ValidationWarning::IdenticalSerial]);
+    assert_eq!(warnings("1M8GDM9AXKP123456"), [ValidationWarning::SequentialSerial]);
+    assert_eq!(warnings("1M8GDM9AXK9876543"), [ValidationWarning::SequentialSerial]);
+    assert_eq!(warnings("WP0ZZZ99ZTSZZZZZZ"), [ValidationWarning::IdenticalSerial]);
+    assert!(warnings("1M8GDM9AXKP042788").is_empty());
+    assert!(warnings("WP0ZZZ99ZTSABCDEF").is_empty());
+    assert!(warnings("1M8GDM9AXKP124567").is_empty());
+}
+
+#[test]
+fn validation_findings() {
+    use vin::config::ValidationProfile;
+    use vin::{FindingCode, Severity, VinParser};
+
+    let parser = VinParser::new();
+    let ids = |vin: &str| -> Vec<&'static str> { parser.validate_full(vin).findings.iter().map(|x| x.id()).collect() };
+    assert!(ids("1M8GDM9AXKP042788").is_empty());
+    assert_eq!(ids("1M8GDM9AXKP04278"), ["E_LENGTH"]);
+    assert_eq!(ids("1M8GDM9AXKP04278O"), ["E_CHARS"]);
+    assert_eq!(ids("1M8GDM9A3KP0A2788"), ["W_SERIAL"]);
+    assert_eq!(ids("1M8GDM9AXKP123456"), ["E_CHECKSUM", "W_SEQUENTIAL_SERIAL"]);
+
+    let result = VinParser::new().with_profile(ValidationProfile::NorthAmerica).validate_full("1M8GDM9A3KP0A2788");
+    assert!(result.report.is_none() && !result.is_valid());
+    assert_eq!((result.findings[0].severity, result.findings[0].code), (Severity::Error, FindingCode::NonNumericSerial));
+    assert_eq!(result.findings[0].id(), "E_SERIAL");
+    assert_eq!(result.findings[0].to_string(), "E_SERIAL: Non-numeric symbol of the serial number on 13th place.");
+
+    let result = parser.validate_full("1M8GDM9AXKP042789");
+    assert!(result.report.is_some() && !result.is_valid());
+}
+
+#[cfg(feature = "i18n-messages")]
+#[test]
+fn localized_messages() {
+    use vin::messages::{set_catalog, Catalog};
+    use vin::watchlist::MemoryWatchlist;
+    use vin::VinParser;
+
+    let french = Catalog::new()
+        .with("E_CHARS", "Caractères invalides : {chars}.")
+        .with("E_SERIAL", "Symbole non numérique à la position {position}.")
+        .with("W_UNKNOWN_WMI", "Constructeur inconnu.");
+    let err = get_info("1M8GDM9AXKP04278O").unwrap_err();
+    assert_eq!(err.localized(&french), "Caractères invalides : O.");
+    let positions = Catalog::new().with("E_CHARS", "{chars} : {positions}");
+    assert_eq!(get_info("QM8GDM9AXKP04278O").unwrap_err().localized(&positions), "OQ : 1, 17");
+    assert_eq!(VINError::IncorrectLength.localized(&french), VINError::IncorrectLength.to_string());
+
+    let parser = VinParser::new().with_profile(vin::config::ValidationProfile::NorthAmerica);
+    let findings = parser.validate_full("1M8GDM9A3KP0A2788").findings;
+    assert_eq!(findings[0].localized(&french), "Symbole non numérique à la position 13.");
+    let findings = parser.validate_full("AP0ZZZ99ZTS392124").findings;
+    assert_eq!(findings[0].localized(&french), "Constructeur inconnu.");
+
+    // only the message not checked by other tests is replaced process-wide
+    set_catalog(Catalog::new().with("W_WATCHLISTED", "Véhicule signalé.")).unwrap();
+    assert!(set_catalog(Catalog::new()).is_err());
+    let list = MemoryWatchlist::load_csv("1M8GDM9AXKP042788\n".as_bytes()).unwrap();
+    let result = VinParser::new().with_watchlist(list).validate_full("1M8GDM9AXKP042788");
+    assert_eq!(result.findings[0].to_string(), "W_WATCHLISTED: Véhicule signalé.");
+    assert_eq!(result.findings[0].message, "VIN is listed in the watchlist.");
+}
+
+#[test]
+fn assembly_from_parts() {
+    use vin::{VinComponents, VIN};
+
+    assert_eq!(VIN::from_parts("1m8", "gdm9ax", "kp042788").unwrap().as_str(), "1M8GDM9AXKP042788");
+    assert!(matches!(VIN::from_parts("1M8G", "DM9AX", "KP042788"), Err(VINError::IncorrectLength)));
+    assert!(matches!(VIN::from_parts("1M8", "GDM9AX", "KP04278O"), Err(VINError::InvalidCharacters(_))));
+
+    let mut components = VinComponents {
+        wmi: "1M8",
+        attributes: "GDM9A",
+        year: 'k',
+        plant: 'P',
+        serial: "042788",
+        compute_check_digit: true,
+    };
+    let result = VIN::from_components(&components).unwrap();
+    assert_eq!(result.as_str(), "1M8GDM9AXKP042788");
+    assert!(result.valid_checksum.is_valid());
+
+    let wrong_year = VinComponents { year: 'U', ..components };
+    assert!(matches!(VIN::from_components(&wrong_year), Err(VINError::InvalidCharacters(_))));
+
+    components.compute_check_digit = false;
+    assert!(matches!(VIN::from_components(&components), Err(VINError::IncorrectLength)));
+    components.attributes = "GDM9A3";
+    assert!(VIN::from_components(&components).unwrap().valid_checksum.is_invalid());
+}
+
+#[test]
+fn owned_parts() {
+    for (_, vin) in vin::testdata::valid() {
+        let result = get_info(vin).unwrap();
+        let parts = result.clone().into_parts();
+        assert_eq!(format!("{}{}{}", parts.vds, parts.check_digit, parts.year_code), format!("{}{}", result.vds(), &result.vis()[..1]));
+        assert_eq!(parts.assemble().unwrap().as_str(), result.as_str());
+    }
+
+    let mut parts = get_info("WP0ZZZ99ZTS392124").unwrap().into_parts();
+    assert_eq!((parts.check_digit, parts.year_code, parts.plant_code), ('Z', 'T', 'S'));
+    parts.year_code = 'O';
+    assert!(matches!(parts.assemble(), Err(VINError::InvalidCharacters(_))));
+}
+
+#[cfg(all(feature = "validator", feature = "service"))]
+#[tokio::test]
+async fn framework_validation() {
+    use axum::body::{to_bytes, Body};
+    use axum::http::{Request, StatusCode};
+    use axum::routing::get;
+    use axum::Router;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+    use vin::validation::{validate_vin, ValidVin};
+
+    assert!(validate_vin("WP0ZZZ99ZTS392124").is_ok());
+    let err = validate_vin("WP0ZZZ99ZTS39212").unwrap_err();
+    assert_eq!(err.code, "E_LENGTH");
+    assert_eq!(err.message.unwrap(), "Incorrect length of given string, 17 chars expected.");
+
+    async fn call(uri: &str) -> (StatusCode, Value) {
+        let router = Router::new()
+            .route("/vin/{number}", get(|ValidVin(vin): ValidVin| async move { vin.as_str().to_string() }))
+            .route("/lookup", get(|ValidVin(vin): ValidVin| async move { vin.as_str().to_string() }));
+        let response = router.oneshot(Request::get(uri).body(Body::empty()).unwrap()).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap_or_else(|_| json!(String::from_utf8_lossy(&body))))
+    }
+
+    assert_eq!(call("/vin/1m8gdm9axkp042788").await, (StatusCode::OK, json!("1M8GDM9AXKP042788")));
+    assert_eq!(call("/lookup?vin=1M8GDM9AXKP042788").await, (StatusCode::OK, json!("1M8GDM9AXKP042788")));
+    assert_eq!(call("/vin/1M8GDM9AXKP042789").await, (StatusCode::BAD_REQUEST, json!({
+        "vin": "1M8GDM9AXKP042789",
+        "code": "E_CHECKSUM",
+        "error": "Invalid checksum symbol on 9th place, 1 expected, X received.",
+    })));
+    let (status, body) = call("/lookup").await;
+    assert_eq!((status, &body["code"]), (StatusCode::BAD_REQUEST, &json!("E_MISSING")));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn validation_report_json() {
+    use serde_json::{json, Value};
+    use vin::rules::MinModelYear;
+    use vin::VinParser;
+
+    let report = VinParser::new().with_rule(MinModelYear(2005)).validate("1M8GDM9AXKP123456").unwrap();
+    let value: Value = serde_json::from_str(&serde_json::to_string(&report).unwrap()).unwrap();
+    assert_eq!(value["rule_findings"], json!([{
+        "id": "E_MODEL_YEAR",
+        "severity": "error",
+        "message": "Model year 1989 is before 2005.",
+    }]));
+    assert_eq!(value["warnings"], json!(["sequential_serial"]));
+    assert_eq!(serde_json::to_value(&report).unwrap(), value);
+}
+
+#[cfg(feature = "schemars")]
+#[test]
+fn json_schemas() {
+    use serde_json::Value;
+    use vin::response::ErrorResponse;
+    use vin::{ValidationReport, VinParser, VIN};
+
+    fn keys(value: &Value) -> Vec<String> {
+        let mut keys: Vec<String> = value.as_object().unwrap().keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    // every serialized field is described by the schema
+    let schema = schemars::schema_for!(VIN).to_value();
+    let value = serde_json::to_value(get_info("1M8GDM9AXKP042789").unwrap()).unwrap();
+    assert_eq!(keys(&schema["properties"]), keys(&value));
+    assert_eq!(schema["required"].as_array().unwrap().len(), 8);
+    assert_eq!(schema["properties"]["checksum_status"]["enum"][2], "not_applicable");
+
+    let schema = schemars::schema_for!(ValidationReport).to_value();
+    let value = serde_json::to_value(VinParser::new().validate("1M8GDM9AXKP123456").unwrap()).unwrap();
+    assert_eq!(keys(&schema["properties"]), keys(&value));
+    assert_eq!(value["warnings"][0], "sequential_serial");
+    assert!(schema["$defs"]["VIN"].is_object());
+
+    let schema = schemars::schema_for!(ErrorResponse).to_value();
+    let err = get_info("1M8GDM9AXKP04278").unwrap_err();
+    let value = serde_json::to_value(ErrorResponse::new("1M8GDM9AXKP04278", &err).with_code(&err)).unwrap();
+    assert_eq!(keys(&schema["properties"]), keys(&value));
+    assert_eq!(value["code"], "E_LENGTH");
+}
+
+#[test]
+fn ordered_export() {
+    use vin::VIN;
+
+    let result = get_info("KMHDN45D22U123456").unwrap();
+    let fields = result.to_ordered_map();
+    assert_eq!(fields.iter().map(|(name, _)| *name).collect::<Vec<_>>(), VIN::FIELDS);
+    assert_eq!(fields[3], ("country", "Korea (South)".to_string()));
+    assert!(result.to_csv_record().starts_with("KMHDN45D22U123456,KMH,Hyundai,Korea (South),Asia,"));
+
+    let result = get_info("YS2R4X20205399401").unwrap();
+    assert!(result.to_csv_record().starts_with("YS2R4X20205399401,YS2,\"Scania, Södertälje\",Sweden,"));
+
+    let result = get_info("AP0ZZZ99ZTS392124").unwrap();
+    assert_eq!(result.to_csv_record().split(',').nth(2), Some(""));
+}
+
+#[test]
+fn batch_statistics() {
+    use vin::batch::{decode_with_stats, BatchStats};
+    use vin::FindingCode;
+
+    let input = ["1M8GDM9AXKP042788", "1M8GDM9AXKP042789", "AP0ZZZ99ZTS392124", "1M8GDM9AXKP04278", "1M8GDM9AXKP04278O"];
+    let (outcomes, stats) = decode_with_stats(input.iter());
+    assert_eq!(outcomes.len(), 5);
+    assert_eq!((outcomes[3].index, outcomes[3].input.as_str()), (3, "1M8GDM9AXKP04278"));
+    assert!(matches!(outcomes[4].result, Err(VINError::InvalidCharacters(_))));
+
+    assert_eq!((stats.total, stats.decoded, stats.invalid_checksum, stats.rejected()), (5, 3, 1, 2));
+    assert_eq!(stats.errors[&FindingCode::Length], 1);
+    assert_eq!(stats.errors[&FindingCode::Characters], 1);
+    assert_eq!(stats.regions["North America"], 2);
+    assert_eq!(stats.manufacturers.values().sum::<usize>(), 2);
+
+    let mut streamed = BatchStats::new();
+    input.iter().for_each(|x| streamed.record(&get_info(x)));
+    assert_eq!(streamed, stats);
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn mmap_dataset() {
+    use vin::mmap::{pack_csv, MmapSource};
+    use vin::source::{BuiltinSource, WmiSource};
+    use vin::VinParser;
+
+    let dir = std::env::temp_dir();
+    let path = dir.join(format!("vin_mmap_{}.bin", std::process::id()));
+    let csv = "key,manufacturer,country\n\
+        KMH,Hyundai,\"Korea (South)\"\n\
+        kmhdn45d2,Hyundai Elantra,\n\
+        WP0ZZZ99,Porsche 911,Germany\n";
+    assert_eq!(pack_csv(csv.as_bytes(), std::fs::File::create(&path).unwrap()).unwrap(), 3);
+
+    let source = MmapSource::open(&path).unwrap();
+    assert_eq!(source.len(), 3);
+    let record = source.lookup("KMH").unwrap();
+    assert_eq!((record.manufacturer.as_str(), record.country.as_deref()), ("Hyundai", Some("Korea (South)")));
+    assert_eq!(record.region.as_deref(), Some("Asia"));
+    // squish VIN skips the check digit
+    assert_eq!(source.lookup_vin("KMHDN45D22U123456").unwrap().manufacturer, "Hyundai Elantra");
+    assert_eq!(source.lookup_vin("KMHDN45D22U123456").unwrap().country, None);
+    assert_eq!(source.lookup_vin("KMHXX45D22U123456").unwrap().manufacturer, "Hyundai");
+    assert!(source.lookup("WP0").is_none());
+
+    let parser = VinParser::with_source(source.or(BuiltinSource));
+    assert_eq!(parser.parse("WP0ZZZ99ZTS392124").unwrap().manufacturer.unwrap(), "Porsche 911");
+    assert_eq!(parser.parse("1M8GDM9AXKP042788").unwrap().manufacturer.unwrap(), "Mercury");
+
+    let duplicate = "key,manufacturer,country\nKMH,Hyundai,\nkmh,Kia,\n";
+    assert!(pack_csv(duplicate.as_bytes(), std::io::sink()).is_err());
+    assert!(pack_csv("key,manufacturer,country\nKM,Hyundai,\n".as_bytes(), std::io::sink()).is_err());
+
+    std::fs::write(&path, b"VINWMI01\x05\0\0\0\0\0\0\0").unwrap();
+    assert!(MmapSource::open(&path).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn batch_checksum() {
+    use vin::parse::verify_checksum_batch;
+
+    let mut vins: Vec<&str> = vin::testdata::valid().into_iter().map(|(_, vin)| vin).collect();
+    vins.extend(vin::testdata::INVALID_LENGTH.iter());
+    vins.extend(vin::testdata::INVALID_CHARACTERS.iter());
+    vins.extend(vin::testdata::INVALID_CHECKSUM.iter());
+    vins.extend(["1m8gdm9axkp042788", "WP0ZZZ99ZTS392124", "1M8GDM9AXKP04278O"].iter());
+
+    let results = verify_checksum_batch(&vins);
+    assert_eq!(results.len(), vins.len());
+    for (vin, result) in vins.iter().zip(results) {
+        match (result, verify_checksum(vin)) {
+            (Ok(()), Ok(())) => (),
+            (Err(VINError::ChecksumError(l)), Err(VINError::ChecksumError(r))) =>
+                assert_eq!((l.expected, l.received, l.sum), (r.expected, r.received, r.sum)),
+            (Err(l), Err(r)) => assert_eq!(std::mem::discriminant(&l), std::mem::discriminant(&r)),
+            (l, r) => panic!("{}: {:?} != {:?}", vin, l, r),
+        }
+    }
+    assert!(verify_checksum_batch(&[]).is_empty());
+
+    // every ASCII symbol at every position is transliterated as by the regular verification
+    let mut variants = vec![];
+    for position in 0..17 {
+        for symbol in (0..128u8).map(char::from) {
+            let mut vin: Vec<char> = "1M8GDM9AXKP042788".chars().collect();
+            vin[position] = symbol;
+            variants.push(vin.into_iter().collect::<String>());
+        }
+    }
+    let variants: Vec<&str> = variants.iter().map(String::as_str).collect();
+    for (vin, result) in variants.iter().zip(verify_checksum_batch(&variants)) {
+        assert_eq!(result.is_ok(), verify_checksum(vin).is_ok(), "{:?}", vin);
+    }
+}
+
+#[test]
+fn single_pass_parsing() {
+    use std::convert::TryInto;
+    use vin::parse::parse_unchecked_ascii;
+
+    for vin in ["1M8GDM9AXKP042788", "1M8GDM9AXKP042789", "WP0ZZZ99ZTS392124", "AP0ZZZ99ZTS392124"] {
+        let expected = get_info(vin).unwrap();
+        let result = parse_unchecked_ascii(vin.as_bytes().try_into().unwrap()).unwrap();
+        assert_eq!(format!("{:?}", result), format!("{:?}", expected));
+    }
+    assert!(matches!(parse_unchecked_ascii(b"1m8gdm9axkp042788"), Err(VINError::InvalidCharacters(_))));
+    let err = parse_unchecked_ascii(b"1M8GDM9AXKP04278\xff").unwrap_err();
+    assert!(matches!(err, VINError::InvalidCharacters(chars) if chars.len() == 1));
+
+    // non-ASCII input is still reported as in 1.x
+    assert!(matches!(get_info("1M8GDM9AXKP04278ü"), Err(VINError::InvalidCharacters(_))));
+    assert!(matches!(get_info("1M8GDM9AXKP0427ü"), Err(VINError::IncorrectLength)));
+}
+
+#[test]
+fn region_ranges() {
+    use vin::Region;
+
+    let regions: Vec<String> = "ABCDEFGHJKLMNPRSTUVWXYZ1234567890"
+        .chars()
+        .map(|x| Region::from_code(x).unwrap().to_string())
+        .collect();
+    assert_eq!(regions.iter().filter(|x| *x == "Europe").count(), 8);
+    assert_eq!(Region::from_code('0').unwrap().name(), "South America");
+    assert_eq!(Region::from_code('7').unwrap().codes(), "67");
+    assert_eq!(Region::from_code('L').unwrap().codes(), "JKLMNPR");
+    assert!(Region::from_code('O').is_none() && Region::from_code('-').is_none());
+
+    let result = get_info("0VF12345678901234").unwrap();
+    assert_eq!(result.region.as_deref(), Some("South America"));
+    assert!(!result.is_checksum_applicable());
+    assert!(get_info("LVSHCAMB1CE054249").unwrap().is_checksum_applicable());
+}
+
+#[test]
+fn invalid_character_positions() {
+    let err = get_info("1M8GDM9AXKP04278O").unwrap_err();
+    match &err {
+        VINError::InvalidCharacters(chars) => {
+            assert_eq!(chars.len(), 1);
+            assert_eq!((chars[0].position, chars[0].character, chars[0].confusable), (16, 'O', true));
+        }
+        _ => panic!("unexpected error {:?}", err),
+    }
+    assert_eq!(err.to_string(), "Invalid characters received in given string: O (position 17).");
+
+    match get_info("1M8GDM9AX-P0427iO") {
+        Err(VINError::InvalidCharacters(chars)) => {
+            let found: Vec<_> = chars.iter().map(|x| (x.position, x.character, x.confusable)).collect();
+            assert_eq!(found, [(9, '-', false), (15, 'I', true), (16, 'O', true)]);
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn forbidden_letters() {
+    use vin::VinParser;
+
+    let parser = VinParser::new().with_forbidden_letters(true);
+    let err = parser.parse("iM8GDM9AXKP04278O").unwrap_err();
+    match &err {
+        VINError::ForbiddenLetters(letters) => {
+            let found: Vec<_> = letters.iter().map(|x| (x.position, x.suggestion())).collect();
+            assert_eq!(found, [(0, Some('1')), (16, Some('0'))]);
+        }
+        _ => panic!("unexpected error {:?}", err),
+    }
+    assert_eq!(
+        err.to_string(),
+        "Letters I, O and Q are not allowed in VIN: I (position 1, did you mean 1?), O (position 17, did you mean 0?)."
+    );
+    let findings = parser.validate_full("1M8GDM9AXKP04278O").findings;
+    assert_eq!(findings[0].id(), "E_LETTERS");
+
+    assert!(matches!(parser.parse("1M8GDM9AXKP0427$Q"), Err(VINError::InvalidCharacters(_))));
+    assert!(matches!(VinParser::new().parse("1M8GDM9AXKP04278O"), Err(VINError::InvalidCharacters(_))));
+    assert!(parser.parse("1M8GDM9AXKP042788").is_ok());
+}
+
+#[test]
+fn lenient_parsing() {
+    use vin::VinParser;
+
+    let parser = VinParser::new();
+    assert!(matches!(parser.parse("WP0 ZZZ 99Z TS 392124"), Err(VINError::IncorrectLength)));
+    let result = parser.parse_lenient("WP0 ZZZ 99Z.TS-392124").unwrap();
+    assert_eq!(result.vin.manufacturer.as_deref(), Some("Porsche car"));
+    assert_eq!(result.stripped, [(3, ' '), (7, ' '), (11, '.'), (14, '-')]);
+
+    let result = vin::parse_lenient("wp0zzz99zts392124").unwrap();
+    assert!(!result.is_formatted());
+    assert!(matches!(parser.parse_lenient("WP0 ZZZ 99Z TS 39212"), Err(VINError::IncorrectLength)));
+    assert!(matches!(parser.parse_lenient("WP0/ZZZ99ZTS392124"), Err(VINError::IncorrectLength)));
+}
+
+#[test]
+fn borrowed_info() {
+    use vin::VinInfo;
+
+    for number in ["1M8GDM9AXKP042788", "1M8GDM9AXKP042789", "wp0zzz99zts392124", "AP0ZZZ99ZTS392124"] {
+        let info = VinInfo::parse(number).unwrap();
+        let expected = get_info(number).unwrap();
+        assert_eq!(info.as_str(), expected.as_str());
+        assert_eq!(info.manufacturer, expected.manufacturer.as_deref());
+        assert_eq!(info.country, expected.country.as_deref());
+        assert_eq!(info.region, expected.region.as_deref());
+        assert_eq!(info.valid_checksum.is_invalid(), expected.valid_checksum.is_invalid());
+        assert_eq!(info.into_owned().manufacturer, expected.manufacturer);
+    }
+    assert!(VinInfo::parse("1M8GDM9AXKP042788").unwrap().is_borrowed());
+    assert!(matches!(VinInfo::parse("1M8GDM9AXKP04278O"), Err(VINError::InvalidCharacters(_))));
+    assert!(matches!(VinInfo::parse("1M8GDM9AXKP04278"), Err(VINError::IncorrectLength)));
+}
+
+#[test]
+fn interned_names() {
+    use std::sync::Arc;
+    use vin::Interner;
+
+    let interner = Interner::new();
+    let vins: Vec<_> = ["1M8GDM9AXKP042788", "1M8GDM9AXKP042789", "AP0ZZZ99ZTS392124"]
+        .iter()
+        .map(|x| interner.intern_vin(&get_info(x).unwrap()))
+        .collect();
+    assert!(Arc::ptr_eq(vins[0].manufacturer.as_ref().unwrap(), vins[1].manufacturer.as_ref().unwrap()));
+    assert_eq!(vins[1].as_str(), "1M8GDM9AXKP042789");
+    assert!(vins[1].valid_checksum.is_invalid());
+    assert_eq!(vins[2].manufacturer, None);
+    assert_eq!(vins[2].to_vin().region, get_info("AP0ZZZ99ZTS392124").unwrap().region);
+    let len = interner.len();
+
+    drop(vins);
+    interner.shrink();
+    assert!(len > 0);
+    assert!(interner.is_empty());
+}
+
+#[test]
+fn public_tables() {
+    use vin::tables::{char_value, is_allowed, ALLOWED_CHARS, CHAR_VALUES, WEIGHTS};
+
+    let allowed: Vec<char> = CHAR_VALUES.iter().map(|(x, _)| *x).collect();
+    assert_eq!(allowed, ALLOWED_CHARS);
+    for (symbol, value) in CHAR_VALUES {
+        assert_eq!(char_value(symbol), Some(value));
+    }
+    for symbol in "IOQioqa-\u{e9}".chars() {
+        assert!(!is_allowed(symbol));
+    }
+
+    let values: Vec<u32> = "1M8GDM9AXKP042788".chars().map(|x| char_value(x).unwrap()).collect();
+    let sum: u32 = values.iter().zip(WEIGHTS.iter()).map(|(x, w)| x * w).sum();
+    assert_eq!(compute_check_digit("1M8GDM9AXKP042788").unwrap(), if sum % 11 == 10 { 'X' } else { (b'0' + (sum % 11) as u8) as char });
+}
+
+#[test]
+fn explanation() {
+    use vin::explain::Role;
+
+    let explanation = get_info("1M8GDM9AXKP042788").unwrap().explain();
+    assert_eq!(explanation.positions.len(), 17);
+    assert_eq!(explanation.expected_check_digit, 'X');
+    assert_eq!(explanation.sum, explanation.positions.iter().map(|x| x.contribution).sum::<u32>());
+    assert_eq!(explanation.positions[8].meaning.as_deref(), Some("check digit, valid"));
+    assert_eq!(explanation.positions[9].meaning.as_deref(), Some("1989 or 2019"));
+    assert_eq!(explanation.positions[2].meaning, get_info("1M8GDM9AXKP042788").unwrap().manufacturer);
+    let roles: Vec<Role> = explanation.positions.iter().map(|x| x.role).collect();
+    assert_eq!(roles[10..12], [Role::Plant, Role::SerialNumber]);
+
+    let explanation = get_info("WP0ZZZ99ZTS392124").unwrap().explain();
+    assert_eq!(explanation.positions[8].meaning.as_deref(), Some("check digit, 8 expected"));
+    assert_eq!(explanation.positions[0].meaning.as_deref(), Some("Europe"));
+
+    let explanation = get_info("1G9ZZZ99ZTS392124").unwrap().explain();
+    let roles: Vec<Role> = explanation.positions.iter().map(|x| x.role).collect();
+    assert_eq!(roles[11..], [Role::SmallManufacturer, Role::SmallManufacturer, Role::SmallManufacturer, Role::SerialNumber, Role::SerialNumber, Role::SerialNumber]);
+    assert_eq!(explanation.positions.iter().map(|x| x.position).collect::<Vec<_>>(), (1..=17).collect::<Vec<_>>());
+
+    let table = explanation.render_table();
+    assert!(table.lines().all(|x| x == x.trim_end()));
+    assert!(table.lines().nth(12).unwrap().starts_with(" 12  3       small manufacturer"));
+    assert!(table.ends_with(&format!("expected check digit: {}\n", explanation.expected_check_digit)));
+}
+
+#[test]
+fn decode_report() {
+    use vin::report::{render, Format};
+
+    let result = get_info("1M8GDM9AXKP042789").unwrap();
+    let markdown = render(&result, Format::Markdown);
+    assert!(markdown.contains("- **Checksum:** invalid\n"));
+    assert!(markdown.contains("mod 11 = 1, expected check digit 1, received X."));
+    assert!(markdown.contains(vin::data_version().version));
+    assert_eq!(markdown.lines().filter(|x| x.starts_with("| ")).count(), 18);
+
+    let html = render(&get_info("WP0ZZZ99ZTS392124").unwrap(), Format::Html);
+    assert!(html.contains("<li><b>Manufacturer:</b> Porsche car</li>"));
+    assert!(html.contains("<li><b>Checksum:</b> not applicable in the region</li>"));
+    assert_eq!(html.matches("<tr>").count(), 18);
+}
+
+#[test]
+fn vin_diff() {
+    use vin::{diff, Attribute};
+
+    let a = get_info("1M8GDM9AXKP042788").unwrap();
+    assert!(diff(&a, &a).is_empty());
+
+    let result = diff(&a, &get_info("1M8GDM9AXKP042878").unwrap());
+    assert!(result.is_transposition());
+    assert_eq!(result.positions.iter().map(|x| x.position).collect::<Vec<_>>(), [14, 15]);
+    let attributes: Vec<Attribute> = result.attributes.iter().map(|x| x.attribute).collect();
+    assert_eq!(attributes, [Attribute::Checksum]);
+    assert_eq!(result.attributes[0].left.as_deref(), Some("valid"));
+
+    let result = diff(&a, &get_info("WP0ZZZ99ZTS392124").unwrap());
+    assert!(!result.is_transposition());
+    assert!(result.attributes.iter().any(|x| x.attribute == Attribute::Region && x.right.as_deref() == Some("Europe")));
+}
+
+#[test]
+fn vin_stats() {
+    use vin::stats::{aggregate, VinStats};
+
+    let vins: Vec<_> = ["WP0ZZZ99ZTS392124", "1M8GDM9AXKP042788", "1M8GDM9AXKP042789", "AP0ZZZ99ZTS392124"]
+        .iter()
+        .map(|x| get_info(x).unwrap())
+        .collect();
+    let stats = aggregate(vins.iter());
+    assert_eq!(stats.total, 4);
+    assert_eq!(stats.manufacturers.values().sum::<usize>(), 3);
+    assert_eq!(stats.top_manufacturers(1), [(vins[1].manufacturer.as_deref().unwrap(), 2)]);
+    assert_eq!(stats.model_years[&1989], 2);
+    assert_eq!(stats.checksum_not_applicable, 2);
+
+    let mut merged = aggregate(&vins[..2]);
+    merged.merge(&aggregate(vins[2..].to_vec()));
+    assert_eq!(merged, stats);
+    assert_eq!(aggregate(Vec::<vin::VIN>::new()), VinStats::new());
+}
+
+#[test]
+fn stratified_sample() {
+    use vin::stats::{sample_stratified, Strata};
+
+    let numbers = ["WP0ZZZ99ZTS392124", "1M8GDM9AXKP042788", "AP0ZZZ99ZTS392124", "1G1ZZZ99ZTS392124"];
+    let vins: Vec<_> = (0..40).map(|i| get_info(numbers[if i < 30 { 1 } else { i % 4 }]).unwrap()).collect();
+
+    let sample = sample_stratified(&vins, Strata::Manufacturer, 8);
+    assert_eq!(sample.len(), 8);
+    let mut counts = std::collections::HashMap::new();
+    for vin in &sample {
+        *counts.entry(vin.manufacturer.clone()).or_insert(0) += 1;
+    }
+    assert_eq!(counts.len(), 4);
+    assert!(counts.values().all(|x| *x == 2));
+
+    assert_eq!(sample_stratified(&vins, Strata::ModelYear, 100).len(), 40);
+    assert!(sample_stratified(&vins, Strata::Country, 0).is_empty());
+    assert!(sample_stratified(&[], Strata::Region, 5).is_empty());
+}
+
+#[test]
+fn custom_rules() {
+    use vin::rules::{DecodeContext, MinModelYear};
+    use vin::{Finding, FindingCode, Severity, VinParser, VIN};
+
+    let parser = VinParser::new()
+        .with_rule(MinModelYear(1995))
+        .with_rule(|_: &VIN, ctx: &DecodeContext| match ctx.input.contains('-') {
+            true => Some(Finding::rule(Severity::Info, "I_SEPARATORS", "VIN contains separators.")),
+            false => None,
+        })
+        .with_normalization(vin::config::Normalization::StripSeparators);
+
+    let report = parser.validate("WP0ZZZ99-ZTS392124").unwrap();
+    assert_eq!(report.rule_findings.len(), 1);
+    assert_eq!(report.rule_findings[0].code, FindingCode::Rule("I_SEPARATORS"));
+    let result = parser.validate_full("WP0ZZZ99-ZTS392124");
+    assert!(result.is_valid());
+    assert_eq!(result.findings.last().unwrap().id(), "I_SEPARATORS");
+
+    let result = parser.validate_full("1M8GDM9AXKP042788");
+    assert!(!result.is_valid());
+    assert_eq!(result.findings[0].id(), "E_MODEL_YEAR");
+    assert_eq!(result.findings[0].message, "Model year 1989 is before 1995.");
+    assert!(VinParser::new().validate("1M8GDM9AXKP042788").unwrap().rule_findings.is_empty());
+    assert!(parser.parse("1M8GDM9AXKP042788").is_ok());
+}
+
+#[test]
+fn wmi_lists() {
+    use vin::{FindingCode, VinParser};
+
+    let parser = VinParser::new().allow_wmis(vec!["wp0".to_string()]);
+    let report = parser.validate("1M8GDM9AXKP042788").unwrap();
+    assert_eq!(report.rule_findings[0].code, FindingCode::Rule("E_WMI_NOT_ALLOWED"));
+    assert_eq!(report.rule_findings[0].message, "WMI 1M8 is not allowed.");
+    assert!(parser.validate("wp0zzz99zts392124").unwrap().rule_findings.is_empty());
+
+    let parser = VinParser::new().deny_wmis(["1M8"]);
+    assert_eq!(parser.validate_full("1M8GDM9AXKP042788").findings[0].message, "WMI 1M8 is denied.");
+    assert!(parser.validate_full("WP0ZZZ99ZTS392124").is_valid());
+    let parser = VinParser::new().allow_wmis(Vec::<&str>::new());
+    assert_eq!(parser.validate_full("WP0ZZZ99ZTS392124").findings[0].id(), "E_WMI_NOT_ALLOWED");
+}
+
+#[test]
+fn cached_parser() {
+    use std::sync::Arc;
+    use vin::config::Normalization;
+    use vin::{CachedParser, VinParser};
+
+    let parser = CachedParser::new(VinParser::new().with_normalization(Normalization::StripSeparators), 16);
+    let first = parser.parse("1M8-GDM9A-XKP042788").unwrap();
+    assert!(Arc::ptr_eq(&first, &parser.parse("1m8gdm9axkp042788").unwrap()));
+    assert!(matches!(parser.parse("1M8GDM9AXKP04278"), Err(VINError::IncorrectLength)));
+    assert!(parser.parse("1M8GDM9AXKP04278O").is_err());
+    assert_eq!(parser.len(), 1);
+
+    let vins: Vec<String> = (0..40).map(|i| format!("WP0ZZZ99ZTS3921{:02}", i)).collect();
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for vin in &vins {
+                    assert_eq!(parser.parse(vin).unwrap().as_str(), vin);
+                }
+            });
+        }
+    });
+    assert!(parser.len() <= 16);
+    parser.clear();
+    assert!(parser.is_empty());
+}
+
+#[test]
+fn partial_vin() {
+    use vin::partial::{PartialIndex, PartialVin};
+
+    let partial = PartialVin::parse("*392124").unwrap();
+    assert!(partial.matches("wp0zzz99zts392124"));
+    assert!(!partial.matches("1M8GDM9AXKP042788"));
+    assert!(matches!(PartialVin::parse("..."), Err(VINError::IncorrectLength)));
+    assert!(matches!(PartialVin::parse("…04278O"), Err(VINError::InvalidCharacters(_))));
+
+    let mut index = PartialIndex::new();
+    index.insert("WP0ZZZ99ZTS392124").unwrap();
+    index.insert("WP0ZZZ99ZTS492124").unwrap();
+    index.insert("1M8GDM9AXKP042788").unwrap();
+    assert!(index.insert("1M8GDM9AXKP04278").is_err());
+    assert_eq!(index.len(), 3);
+
+    assert_eq!(index.find(&partial), vec!["WP0ZZZ99ZTS392124"]);
+    assert_eq!(index.find(&PartialVin::parse("92124").unwrap()).len(), 2);
+    assert!(index.find(&PartialVin::parse("000000").unwrap()).is_empty());
+}
+
+#[test]
+fn masking() {
+    use vin::{mask, MaskPolicy};
+
+    let vin = "WP0ZZZ99ZTS392124";
+    assert_eq!(mask(vin, MaskPolicy::default()).unwrap(), "WP0ZZZ99Z****2124");
+    assert_eq!(mask(vin, MaskPolicy::KeepWmi).unwrap(), "WP0**************");
+    assert_eq!(mask(vin, MaskPolicy::KeepDescriptor).unwrap(), "WP0ZZZ99Z********");
+    assert_eq!(mask(vin, MaskPolicy::KeepDescriptorAndLast(0)).unwrap(), "WP0ZZZ99Z********");
+    assert_eq!(mask(vin, MaskPolicy::KeepDescriptorAndLast(20)).unwrap(), vin);
+    assert_eq!(mask(vin, MaskPolicy::KeepDescriptorAndLast(17)).unwrap(), vin);
+    assert_eq!(mask(vin, MaskPolicy::KeepDescriptorAndLast(usize::MAX)).unwrap(), vin);
+
+    let mut keep = [false; 17];
+    keep[16] = true;
+    assert_eq!(mask(vin, MaskPolicy::Custom(keep)).unwrap(), "****************4");
+
+    assert!(mask("WP0ZZZ99ZTS39212", MaskPolicy::default()).is_err());
+    assert_eq!(get_info(vin).unwrap().masked(), "WP0ZZZ99Z****2124");
+}
+
+#[test]
+fn squish_vin() {
+    use vin::SquishVin;
+
+    let info = get_info("1M8GDM9AXKP042788").unwrap();
+    assert_eq!(info.squish(), "1M8GDM9AKP");
+    assert_eq!(SquishVin::from(&info), SquishVin::parse("1M8GDM9AKP").unwrap());
+
+    let squish = SquishVin::from_vin("1m8gdm9axkp042788").unwrap();
+    assert!(squish.matches("1M8GDM9A0KP999999"));
+    assert!(!squish.matches("1M8GDM9AXKR042788"));
+    assert!(!squish.matches("1M8GDM9AKP"));
+
+    assert!(matches!(SquishVin::parse("1M8GDM9AXKP"), Err(VINError::IncorrectLength)));
+    assert!(matches!(SquishVin::parse("1M8GDM9AKO"), Err(VINError::InvalidCharacters(_))));
+}
+
+#[test]
+fn pattern_groups() {
+    use vin::patterns::{PatternRule, PatternSet};
+    use vin::tables::ALLOWED_CHARS;
+
+    // 17 groups of 30 alternatives each (without 3, Y and Z)
+    let group: Vec<String> = ALLOWED_CHARS.iter().filter(|x| !"3YZ".contains(**x)).map(char::to_string).collect();
+    let group = format!("({})", group.join("|"));
+    let mut set = PatternSet::new();
+    set.add(PatternRule::new("ANY", &group.repeat(17)).unwrap());
+    set.add(PatternRule::new("PREFIX", &format!("1M8{}", group.repeat(14))).unwrap());
+    set.add(PatternRule::new("WILDCARD", "1M8**************").unwrap());
+
+    let ids = |vin: &str| -> Vec<String> {
+        set.find(&get_info(vin).unwrap()).iter().map(|x| x.id.clone()).collect()
+    };
+    assert_eq!(ids("1M8GDM9AXKP042788"), ["ANY", "PREFIX", "WILDCARD"]);
+    assert_eq!(ids("WP0ZZZ99ZTS392124"), Vec::<String>::new());
+}
+
+#[test]
+fn pattern_rules() {
+    use vin::patterns::{Pattern, PatternError, PatternSet};
+
+    let pattern = Pattern::parse("1m8*DM(8|9)A").unwrap();
+    assert!(pattern.matches("1M8GDM9AXKP042788"));
+    assert!(!pattern.matches("1M8GDM7AXKP042788"));
+    assert!(!pattern.matches("1M8GDM9A"));
+
+    assert!(matches!(Pattern::parse(""), Err(PatternError::IncorrectLength)));
+    assert!(matches!(Pattern::parse("1M8(8|9"), Err(PatternError::InvalidGroup)));
+    assert!(matches!(Pattern::parse("1M8()"), Err(PatternError::InvalidGroup)));
+    assert!(matches!(Pattern::parse("1M8(8)"), Err(PatternError::InvalidGroup)));
+    assert!(matches!(Pattern::parse("1M8(|)"), Err(PatternError::InvalidGroup)));
+    assert!(matches!(Pattern::parse("1MO"), Err(PatternError::InvalidCharacter('O'))));
+
+    let rules = "\
+        # recalls
+        R1 1M8
+        R2 1M8*DM(8|9)A
+        R3 1M8*DM7
+        R4 WP0 1990-2000
+        R5 WP0 2001
+        R6 **********P
+    ";
+    let set = PatternSet::load(rules.as_bytes()).unwrap();
+    assert_eq!(set.len(), 6);
+
+    let ids = |vin: &str| -> Vec<String> {
+        set.find(&get_info(vin).unwrap()).iter().map(|x| x.id.clone()).collect()
+    };
+    assert_eq!(ids("1M8GDM9AXKP042788"), vec!["R1", "R2", "R6"]);
+    assert_eq!(ids("WP0ZZZ99ZTS392124"), vec!["R4"]);
+    assert!(ids("JH4DA9350LS012345").is_empty());
+
+    assert!(matches!(PatternSet::load("R1".as_bytes()), Err(PatternError::InvalidLine(1))));
+    assert!(matches!(PatternSet::load("R1 WP0 19x0".as_bytes()), Err(PatternError::InvalidYears(_))));
+}
+
+#[test]
+fn custom_source() {
+    use vin::source::{BuiltinSource, WmiRecord, WmiSource};
+    use vin::VinParser;
+
+    struct Licensed;
+
+    impl WmiSource for Licensed {
+        fn lookup(&self, wmi: &str) -> Option<WmiRecord> {
+            match wmi {
+                "WP0" => Some(WmiRecord::new("Dr. Ing. h.c. F. Porsche AG", "Germany", "Europe")),
+                _ => None,
+            }
+        }
+    }
+
+    let vin = "WP0ZZZ99ZTS392124";
+    assert_eq!(BuiltinSource.lookup("WP0").unwrap().manufacturer, "Porsche car");
+    assert_eq!(BuiltinSource.lookup("0VF").unwrap().region.as_deref(), Some("South America"));
+    assert!(BuiltinSource.lookup("ZZZ").is_none());
+
+    let parser = VinParser::with_source(Licensed);
+    let result = parser.parse(vin).unwrap();
+    assert_eq!(result.manufacturer.as_deref(), Some("Dr. Ing. h.c. F. Porsche AG"));
+    assert_eq!(result.country.as_deref(), Some("Germany"));
+
+    let result = parser.parse("1M8GDM9AXKP042788").unwrap();
+    assert_eq!(result.manufacturer, None);
+    assert_eq!(result.region.as_deref(), Some("North America"));
+
+    let parser = VinParser::with_source(Licensed.or(BuiltinSource));
+    assert_eq!(parser.parse("1M8GDM9AXKP042788").unwrap().manufacturer,
+               get_info("1M8GDM9AXKP042788").unwrap().manufacturer);
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn rule_set_config() {
+    use vin::dicts::Format;
+    use vin::rules::{load_rule_set_from, RuleSet, RuleSetError};
+    use vin::VinParser;
+
+    let document = "allowed_regions = [\"North America\"]\nmin_model_year = 1985\nmax_model_year = 1988\n";
+    let rules = load_rule_set_from(document.as_bytes(), Format::Toml).unwrap();
+    assert_eq!(rules.min_model_year, Some(1985));
+    assert!(!rules.require_checksum);
+    assert_eq!(load_rule_set_from(rules.to_document(Format::Toml).unwrap().as_bytes(), Format::Toml).unwrap(), rules);
+
+    let parser = rules.apply(VinParser::new());
+    let result = parser.validate_full("1M8GDM9AXKP042788");
+    assert_eq!(result.findings[0].message, "Model year 1989 is after 1988.");
+    assert!(parser.parse("1M8GDM9AXKP042789").is_ok());
+
+    let path = std::env::temp_dir().join(format!("vin-rules-{}.toml", std::process::id()));
+    std::fs::write(&path, "require_checksum = true\n").unwrap();
+    let parser = VinParser::from_config(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(parser.parse("1M8GDM9AXKP042789").is_err());
+
+    assert!(matches!(VinParser::from_config("rules.yaml"), Err(RuleSetError::UnknownFormat)));
+    let invalid = load_rule_set_from("blocked_wmis = [\"WP\"]".as_bytes(), Format::Toml);
+    assert!(matches!(invalid, Err(RuleSetError::InvalidWmi(wmi)) if wmi == "WP"));
+    let invalid = load_rule_set_from("allowed_wmis = [\"WPO\"]".as_bytes(), Format::Toml);
+    assert!(matches!(invalid, Err(RuleSetError::InvalidWmi(wmi)) if wmi == "WPO"));
+    let parser = load_rule_set_from("lookahead_years = 5".as_bytes(), Format::Toml).unwrap().apply(VinParser::new());
+    assert_eq!(parser.year_policy().lookahead_years, 5);
+    let parser = load_rule_set_from("allowed_wmis = [\"wp0\"]".as_bytes(), Format::Toml).unwrap().apply(VinParser::new());
+    assert_eq!(parser.validate_full("1M8GDM9AXKP042788").findings[0].id(), "E_WMI_NOT_ALLOWED");
+    assert!(matches!(load_rule_set_from("unknown = 1".as_bytes(), Format::Toml), Err(RuleSetError::Parse(_))));
+    assert_eq!(RuleSet::default().apply(VinParser::new()).validate_full("1M8GDM9AXKP042788").findings.len(), 0);
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn dictionary_overrides() {
+    use vin::dicts::{load_overrides_from, Format, OverridesError};
+    use vin::VinParser;
+
+    let document = "
+        [countries]
+        XT = \"Russia\"
+
+        [manufacturers]
+        XTA = \"Lada\"
+        WP0 = \"Porsche car\"
+        1M = \"Mack Trucks\"
+    ";
+    let overrides = load_overrides_from(document.as_bytes(), Format::Toml).unwrap();
+    assert_eq!(overrides.countries_count(), 1);
+    assert_eq!(overrides.manufacturers_count(), 3);
+
+    let conflicts = overrides.conflicts();
+    assert_eq!(conflicts.len(), 3);
+    assert_eq!(conflicts[0].code, "XT");
+    assert_eq!(conflicts[0].builtin, "USSR/CIS");
+    assert_eq!(conflicts[0].replacement, "Russia");
+    assert_eq!(conflicts[1].code, "1M");
+    assert_eq!(conflicts[2].code, "XTA");
+    assert_eq!(conflicts[2].builtin, "AvtoVAZ");
+
+    let audit = vin::data::audit_with(&overrides);
+    assert_eq!(audit.wmis, vin::data::audit().wmis);
+    assert!(audit.overlapping.contains(&("1M".to_owned(), "1ME".to_owned())));
+
+    let parser = VinParser::with_source(overrides);
+    let result = parser.parse("XTA21099043576182").unwrap();
+    assert_eq!(result.manufacturer.as_deref(), Some("Lada"));
+    assert_eq!(result.country.as_deref(), Some("Russia"));
+    assert_eq!(result.region.as_deref(), Some("Europe"));
+    assert_eq!(parser.parse("WP0ZZZ99ZTS392124").unwrap().manufacturer.as_deref(), Some("Porsche car"));
+    assert_eq!(parser.parse("1M8GDM9AXKP042788").unwrap().manufacturer.as_deref(), Some("Mack Trucks"));
+
+    // overridden first 2 characters do not shadow built-in WMIs
+    let document = "[manufacturers]\n1G = \"GM Import\"";
+    let overrides = load_overrides_from(document.as_bytes(), Format::Toml).unwrap();
+    assert_eq!(overrides.conflicts().len(), 1);
+    assert_eq!(overrides.conflicts()[0].builtin, "General Motors");
+    let parser = VinParser::with_source(overrides);
+    assert_eq!(parser.parse("1G1ZT53826F109149").unwrap().manufacturer.as_deref(), Some("Chevrolet"));
+    assert_eq!(parser.parse("1GZZT53826F109149").unwrap().manufacturer.as_deref(), Some("GM Import"));
+    assert_eq!(parser.parse("YT9AB1C23KA007012").unwrap().manufacturer.as_deref(), Some("Koenigsegg"));
+
+    let invalid = load_overrides_from("[manufacturers]\nXTAB = \"Lada\"".as_bytes(), Format::Toml);
+    assert!(matches!(invalid, Err(OverridesError::InvalidCode(_))));
+    let invalid = load_overrides_from("[models]".as_bytes(), Format::Toml);
+    assert!(matches!(invalid, Err(OverridesError::Parse(_))));
+}
+
+#[test]
+fn coverage_report() {
+    use vin::coverage::{CoverageReport, DecodeOutcome};
+
+    let unknown_country = get_info("AP0ZZZ99ZTS392124").unwrap();
+    assert_eq!(unknown_country.country, None);
+    assert_eq!(unknown_country.region.as_deref(), Some("Africa"));
+
+    let mut report = CoverageReport::new();
+    assert_eq!(report.record("WP0ZZZ99ZTS392124").unwrap(), DecodeOutcome::Decoded);
+    assert_eq!(report.record("wzzzzz99zts392124").unwrap(), DecodeOutcome::UnknownWmi);
+    assert_eq!(report.record("0ZZZZZ99ZTS392124").unwrap(), DecodeOutcome::UnknownCountry);
+    assert!(report.record("0ZZZZZ99ZTS39212").is_err());
+    report.record_outcome("WZZ", DecodeOutcome::UnknownWmi);
+
+    assert_eq!(report.total(), 4);
+    assert_eq!(report.count(DecodeOutcome::UnknownWmi), 2);
+    assert_eq!(report.count(DecodeOutcome::UnknownCountry), 1);
+
+    let unknown = report.unknown();
+    assert_eq!(unknown.len(), 2);
+    assert_eq!((unknown[0].wmi.as_str(), unknown[0].count), ("WZZ", 2));
+    assert_eq!(unknown[1].outcome, DecodeOutcome::UnknownCountry);
+}
+
+#[test]
+fn year_window() {
+    use vin::YearWindow;
+
+    let result = get_info("1M8GDM9AXKP042788").unwrap();
+    assert_eq!(result.years_in(1980..=2100), vec![1989, 2019, 2049, 2079]);
+    assert_eq!(result.years_in(2019..=2019), vec![2019]);
+    assert!(result.years_in(2020..=2048).is_empty());
+    assert!(YearWindow { from: 2000, to: 1990 }.years('K').is_empty());
+    assert_eq!(result.years_in(0..=1990), vec![1989]);
+    assert_eq!(result.years_as_of(2016), vec![1989]);
+    assert_eq!(result.years_as_of(2017), vec![1989, 2019]);
+
+    assert_eq!(YearWindow::new(2030..=2100).years('A'), vec![2040, 2070, 2100]);
+    let window = YearWindow::new(u32::MAX - 40..=u32::MAX);
+    assert_eq!(*window.years('9').last().unwrap(), u32::MAX - (u32::MAX - 2009) % 30);
+    assert!(YearWindow::new(1980..=2039).years('U').is_empty());
+    assert_eq!(YearWindow::new(1980..=2039).years('Y'), vec![2000, 2030]);
+}
+
+#[test]
+fn year_clock() {
+    use vin::{Clock, SystemClock, VinParser};
+
+    assert!(SystemClock.current_year() >= 2024);
+    assert_eq!(VinParser::new().year_window(), VinParser::new().with_clock(SystemClock).year_window());
+    let parser = VinParser::new().with_clock(|| 2016);
+    assert_eq!(parser.year_window().range(), 1980..=2018);
+    assert_eq!(parser.year_window().years('K'), vec![1989]);
+}
+
+#[test]
+fn system_clock_year() {
+    use std::time::{Duration, SystemTime};
+    use vin::SystemClock;
+
+    let at = |seconds: u64| SystemClock::year_at(SystemTime::UNIX_EPOCH + Duration::from_secs(seconds));
+    assert_eq!(at(0), 1970);
+    assert_eq!(SystemClock::year_at(SystemTime::UNIX_EPOCH - Duration::from_secs(1)), 1969);
+    assert_eq!(at(951_782_400), 2000);  // 2000-02-29
+    assert_eq!(at(1_782_864_000), 2026);  // 2026-07-01
+    assert_eq!(at(1_792_108_800), 2026);  // 2026-10-16
+
+    // the last second of every year and the first one of the next
+    let mut new_year = 0;
+    for year in 1970..2200 {
+        let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+        new_year += if leap { 366 } else { 365 } * 86_400;
+        assert_eq!(at(new_year - 1), year);
+        assert_eq!(at(new_year), year + 1);
+    }
+}
+
+#[test]
+fn year_policy() {
+    use vin::rules::MinModelYear;
+    use vin::{VinParser, YearPolicy, YearWindow};
+
+    assert_eq!(YearPolicy::default().window(2020), YearWindow::as_of(2020));
+    assert_eq!(YearPolicy { lookahead_years: 0 }.window(2020).range(), 1980..=2020);
+    assert_eq!(YearPolicy { lookahead_years: 255 }.window(u32::MAX).range(), 1980..=u32::MAX);
+
+    // pre-ordered vehicle coded 3 model years ahead
+    let parser = VinParser::new().with_clock(|| 2023).with_rule(MinModelYear(2010));
+    assert_eq!(parser.year_policy().lookahead_years, 2);
+    assert_eq!(parser.validate_full("WP0ZZZ99ZTS392124").findings[0].message, "Model year 1996 is before 2010.");
+    let parser = parser.with_year_policy(YearPolicy { lookahead_years: 3 });
+    assert_eq!(parser.year_window().range(), 1980..=2026);
+    assert!(parser.validate_full("WP0ZZZ99ZTS392124").is_valid());
+}
+
+#[test]
+fn year_letters_table() {
+    use vin::YearWindow;
+
+    let letters = "ABCDEFGHJKLMNPRSTVWXY123456789";
+    for year in 1980..=2060 {
+        let expected = letters.chars().nth((year - 1980) as usize % 30).unwrap();
+        for letter in letters.chars() {
+            let years = YearWindow::new(year..=year).years(letter);
+            assert_eq!(years == vec![year], letter == expected, "{} {}", letter, year);
+            assert!(years.len() <= 1);
+        }
+        assert_eq!(YearWindow::new(1980..=year).years(expected).last(), Some(&year));
+    }
+
+    for letter in "IOQUZ0a\u{e9}\u{1f697}".chars() {
+        assert!(YearWindow::new(1980..=2060).years(letter).is_empty());
+    }
+    let codes: Vec<(char, u32)> = letters.chars().zip(1980..).collect();
+    assert_eq!(vin::year_codes().to_vec(), codes);
+    for letter in letters.chars() {
+        assert!(YearWindow::new(u32::MAX..=u32::MAX).years(letter).len() <= 1);
+    }
+}
+
+#[test]
+fn model_year_estimate() {
+    use vin::{YearHints, YearMethod, YearWindow};
+
+    let window = YearWindow::new(1980..=2039);
+    let registered = YearHints { currently_registered: true };
+
+    let estimate = get_info("1M8GDM9AXKP042788").unwrap().year_estimate_within(window, YearHints::default());
+    assert_eq!(estimate.candidates, vec![1989, 2019]);
+    assert_eq!((estimate.best_guess, estimate.method), (Some(1989), YearMethod::Position7));
+    assert!(!estimate.is_unique());
+
+    let estimate = get_info("5YJ3E1EA7KF317000").unwrap().year_estimate_within(window, YearHints::default());
+    assert_eq!((estimate.best_guess, estimate.method), (Some(2019), YearMethod::Position7));
+
+    let result = get_info("WP0ZZZ99ZTS392124").unwrap();
+    let estimate = result.year_estimate_within(window, YearHints::default());
+    assert_eq!((estimate.best_guess, estimate.method), (None, YearMethod::Ambiguous));
+    let estimate = result.year_estimate_within(window, registered);
+    assert_eq!((estimate.best_guess, estimate.method), (Some(2026), YearMethod::Hint));
+    let estimate = result.year_estimate_within(YearWindow::new(1980..=2020), registered);
+    assert_eq!((estimate.best_guess, estimate.method), (Some(1996), YearMethod::Unique));
+
+    let estimate = result.year_estimate_within(YearWindow::new(1980..=1990), registered);
+    assert_eq!((estimate.candidates.len(), estimate.best_guess, estimate.method), (0, None, YearMethod::Unknown));
+}
+
+#[test]
+fn decade_hints() {
+    use vin::{DecadeHint, YearMethod, YearWindow};
+
+    let window = YearWindow::new(1980..=2059);
+    let result = get_info("WP0ZZZ99ZTS392124").unwrap();
+    assert_eq!(result.years_within(window), vec![1996, 2026, 2056]);
+
+    let estimate = result.years_with_hint_within(window, DecadeHint::Decade(2020));
+    assert_eq!((estimate.candidates, estimate.method), (vec![2026], YearMethod::Hint));
+    let estimate = result.years_with_hint_within(window, DecadeHint::NotBefore(2001));
+    assert_eq!((estimate.candidates.len(), estimate.best_guess), (2, None));
+    let estimate = result.years_with_hint_within(window, DecadeHint::Decade(2000));
+    assert_eq!(estimate.method, YearMethod::Unknown);
+
+    // the hint does not override the only candidate
+    let estimate = result.years_with_hint_within(YearWindow::new(1980..=2020), DecadeHint::Before(2001));
+    assert_eq!(estimate.method, YearMethod::Unique);
+    assert!(DecadeHint::Decade(u32::MAX - 5).contains(u32::MAX - 1));
+}
+
+#[cfg(feature = "arrow")]
+#[test]
+fn arrow_columns() {
+    use arrow_array::{Array, StringArray};
+    use vin::arrow::decode_column;
+
+    let column = StringArray::from(vec![
+        Some("wp0zzz998ts392124"),
+        Some("WP0ZZZ99ZTS392124"),
+        None,
+        Some("WP0ZZZ99ZTS39212"),
+        Some("1M8GDM9AXKP042788"),
+    ]);
+    let decoded = decode_column(&column);
+    assert_eq!(decoded.len(), 5);
+
+    assert!(decoded.valid.value(0) && decoded.valid.value(1));
+    assert!(!decoded.valid.value(2) && !decoded.valid.value(3));
+    assert_eq!(decoded.manufacturer.value(0), "Porsche car");
+    assert_eq!(decoded.country.value(0), "Germany/West Germany");
+    assert_eq!(decoded.region.value(0), "Europe");
+    // 1996 and 2026 are equally likely
+    assert!(decoded.model_year.is_null(0));
+    assert_eq!(decoded.model_year.value(4), 1989);
+    assert!(decoded.checksum_ok.value(0));
+    assert!(decoded.checksum_ok.is_null(1));
+    assert_eq!(decoded.manufacturer.null_count(), 2);
+
+    let batch = decoded.into_record_batch().unwrap();
+    assert_eq!(batch.num_rows(), 5);
+    assert_eq!(batch.num_columns(), 6);
+}
+
+#[cfg(feature = "polars")]
+#[test]
+fn polars_series() {
+    use polars_core::prelude::*;
+    use vin::polars::decode_series;
+
+    let mut vins = Series::new("vin".into(), &[Some("wp0zzz998ts392124"), None]);
+    vins.append(&Series::new("vin".into(), &[Some("WP0ZZZ99ZTS392124"), Some("invalid")])).unwrap();
+    let decoded = decode_series(&vins).unwrap();
+    assert_eq!(decoded.name().as_str(), "vin");
+    assert_eq!(decoded.len(), 4);
+
+    let fields = decoded.struct_().unwrap().fields_as_series();
+    let names: Vec<&str> = fields.iter().map(|x| x.name().as_str()).collect();
+    assert_eq!(names, ["valid", "manufacturer", "country", "region", "model_year", "checksum_ok"]);
+
+    let valid: Vec<Option<bool>> = (0..4).map(|i| fields[0].bool().unwrap().get(i)).collect();
+    assert_eq!(valid, [Some(true), Some(false), Some(true), Some(false)]);
+    assert_eq!(fields[2].str().unwrap().get(2), Some("Germany/West Germany"));
+    assert_eq!(fields[3].str().unwrap().get(1), None);
+    // 1996 and 2026 are equally likely
+    assert_eq!(fields[4].u32().unwrap().get(0), None);
+    let checksum_ok: Vec<Option<bool>> = (0..4).map(|i| fields[5].bool().unwrap().get(i)).collect();
+    assert_eq!(checksum_ok, [Some(true), None, None, None]);
+}
+
+#[cfg(feature = "proto")]
+#[test]
+fn proto_messages() {
+    use std::convert::TryFrom;
+
+    use prost::Message;
+    use vin::proto::DecodedVin;
+    use vin::VIN;
+
+    let result = vin::get_info("WP0ZZZ99ZTS392124").unwrap();
+    let decoded = DecodedVin::from(&result);
+    assert_eq!(decoded.vin, "WP0ZZZ99ZTS392124");
+    assert_eq!(decoded.region.as_deref(), Some("Europe"));
+    assert_eq!(decoded.years, result.years().candidates);
+    assert!(decoded.checksum_error.is_none() && decoded.checksum_not_applicable);
+    let checksum_error = DecodedVin::from(vin::get_info("1M8GDM9AXKP042789").unwrap()).checksum_error.unwrap();
+    assert_eq!((checksum_error.expected.as_str(), checksum_error.received.as_str()), ("1", "X"));
+
+    let received = DecodedVin::decode(decoded.encode_to_vec().as_slice()).unwrap();
+    assert_eq!(received, decoded);
+    assert_eq!(VIN::try_from(&received).unwrap().as_str(), "WP0ZZZ99ZTS392124");
+
+    let unknown = DecodedVin::from(vin::get_info("AP0ZZZ998TS392124").unwrap());
+    assert!(unknown.manufacturer.is_none());
+    let invalid = DecodedVin { vin: "WP0ZZZ99ZTS39212".to_owned(), ..DecodedVin::default() };
+    assert!(matches!(VIN::try_from(&invalid), Err(VINError::IncorrectLength)));
+}
+
+#[cfg(feature = "proto")]
+#[test]
+fn proto_schema() {
+    // fields as "Message.field = number label type", message types are named `message`
+    fn schema_fields() -> Vec<String> {
+        let mut fields = vec![];
+        let mut message = "";
+        for line in include_str!("../proto/vin.proto").lines().map(str::trim) {
+            if let Some(name) = line.strip_prefix("message ") {
+                message = name.trim_end_matches(" {");
+            } else if !message.is_empty() && !line.starts_with("//") && line.ends_with(';') {
+                let (declaration, number) = line.trim_end_matches(';').split_once(" = ").unwrap();
+                let mut words: Vec<&str> = declaration.split_whitespace().collect();
+                let name = words.pop().unwrap();
+                let kind = match words.pop().unwrap() {
+                    kind if kind.starts_with(char::is_uppercase) => "message",
+                    kind => kind,
+                };
+                let label = words.pop().unwrap_or("");
+                fields.push(format!("{}.{} = {} {} {}", message, name, number, label, kind));
+            }
+        }
+        fields
+    }
+
+    fn rust_fields() -> Vec<String> {
+        let mut fields = vec![];
+        let mut message = "";
+        let mut attribute = None;
+        for line in include_str!("../src/proto.rs").lines().map(str::trim) {
+            if let Some(name) = line.strip_prefix("pub struct ") {
+                message = name.trim_end_matches(" {");
+            } else if let Some(args) = line.strip_prefix("#[prost(").and_then(|x| x.strip_suffix(")]")) {
+                attribute = Some(args);
+            } else if let (Some(args), Some(field)) = (attribute.take(), line.strip_prefix("pub ")) {
+                let name = field.split(':').next().unwrap();
+                let (kind, label, tag) = match args.split(", ").collect::<Vec<_>>().as_slice() {
+                    [kind, tag] => (*kind, "", *tag),
+                    [kind, label, tag] => (*kind, *label, *tag),
+                    _ => panic!("unexpected attribute of {}.{}: {}", message, name, args),
+                };
+                let number = tag.trim_start_matches("tag = ").trim_matches('"');
+                fields.push(format!("{}.{} = {} {} {}", message, name, number, label, kind));
+            }
+        }
+        fields
+    }
+
+    let fields = schema_fields();
+    assert_eq!(fields.len(), 9);
+    assert!(fields.contains(&"DecodedVin.checksum_error = 5 optional message".to_owned()));
+    assert_eq!(rust_fields(), fields);
+}
+
+#[cfg(feature = "service")]
+#[tokio::test]
+async fn service_contract() {
+    use axum::body::{to_bytes, Body};
+    use axum::http::{Request, StatusCode};
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    async fn call(request: Request<Body>) -> (StatusCode, Value) {
+        let response = vin::service::router().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+    }
+
+    let (status, body) = call(Request::get("/vin/wp0zzz99zts392124").body(Body::empty()).unwrap()).await;
+    assert_eq!(status, StatusCode::OK);
+    let years = vin::get_info("WP0ZZZ99ZTS392124").unwrap().years().candidates;
+    assert_eq!(body, json!({
+        "vin": "WP0ZZZ99ZTS392124",
+        "manufacturer": "Porsche car",
+        "country": "Germany/West Germany",
+        "region": "Europe",
+        "valid_checksum": false,
+        "checksum_status": "not_applicable",
+        "checksum_error": null,
+        "years": years,
+    }));
+
+    let (status, body) = call(Request::get("/vin/WP0ZZZ99ZTS39212").body(Body::empty()).unwrap()).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["error"], "Incorrect length of given string, 17 chars expected.");
+
+    let batch = Request::post("/vins")
+        .header("content-type", "application/json")
+        .body(Body::from(r#"["1M8GDM9AXKP042788", "invalid"]"#))
+        .unwrap();
+    let (status, body) = call(batch).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body[0]["valid_checksum"], true);
+    assert_eq!(body[0]["checksum_status"], "valid");
+    assert_eq!(body[0]["checksum_error"], Value::Null);
+    assert_eq!(body[1], json!({"vin": "invalid", "error": "Incorrect length of given string, 17 chars expected."}));
+
+    let numbers = vec!["1M8GDM9AXKP042788"; vin::service::MAX_BATCH + 1];
+    let batch = Request::post("/vins")
+        .header("content-type", "application/json")
+        .body(Body::from(serde_json::to_string(&numbers).unwrap()))
+        .unwrap();
+    assert_eq!(call(batch).await.0, StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn tracing_instrumentation() {
+    use std::fmt::Debug;
+    use std::sync::{Arc, Mutex};
+
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    // collects "span_name wmi" and "outcome" records
+    #[derive(Clone, Default)]
+    struct Collector(Arc<Mutex<Vec<String>>>);
+
+    struct Fields<'a>(&'a mut String);
+
+    impl Visit for Fields<'_> {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            if field.name() != "message" {
+                self.0.push_str(&format!(" {}={}", field.name(), value));
+            }
+        }
+        fn record_debug(&mut self, _: &Field, _: &dyn Debug) {}
+    }
+
+    impl Subscriber for Collector {
+        fn enabled(&self, _: &Metadata<'_>) -> bool { true }
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut line = span.metadata().name().to_owned();
+            span.record(&mut Fields(&mut line));
+            self.0.lock().unwrap().push(line);
+            Id::from_u64(1)
+        }
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn event(&self, event: &Event<'_>) {
+            let mut line = String::from("event");
+            event.record(&mut Fields(&mut line));
+            self.0.lock().unwrap().push(line);
+        }
+        fn enter(&self, _: &Id) {}
+        fn exit(&self, _: &Id) {}
+    }
+
+    let collector = Collector::default();
+    tracing::subscriber::with_default(collector.clone(), || {
+        get_info("AP0ZZZ99ZTS392124").unwrap();
+        get_info("WP0").unwrap_err();
+    });
+
+    let records = collector.0.lock().unwrap().clone();
+    assert_eq!(records, [
+        "parse wmi=AP0",
+        "verify_checksum wmi=AP0",
+        "event outcome=checksum_fail",
+        "event outcome=unknown_wmi",
+        "parse wmi=WP0",
+        "event outcome=invalid_length",
+    ]);
+}
+
+#[cfg(feature = "metrics")]
+#[test]
+fn metrics_counters() {
+    use std::sync::Mutex;
+
+    use metrics::{Counter, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit};
+
+    // collects "name outcome" of every counter increment
+    #[derive(Default)]
+    struct Collector(Mutex<Vec<String>>);
+
+    impl Recorder for Collector {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            let labels: Vec<&str> = key.labels().map(|x| x.value()).collect();
+            self.0.lock().unwrap().push(format!("{} {}", key.name(), labels.join(",")));
+            Counter::noop()
+        }
+        fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge { Gauge::noop() }
+        fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram { Histogram::noop() }
+    }
+
+    let collector = Collector::default();
+    metrics::with_local_recorder(&collector, || {
+        get_info("WP0ZZZ998TS392124").unwrap();
+        get_info("AP0ZZZ99ZTS392124").unwrap();
+        get_info("WP0").unwrap_err();
+        verify_checksum("WP0ZZZ99ZTS39212O").unwrap_err();
     });
+
+    let records = collector.0.lock().unwrap();
+    assert_eq!(*records, [
+        "vin_checksum_verified_total valid",
+        "vin_parsed_total valid",
+        "vin_checksum_verified_total checksum_fail",
+        "vin_parsed_total unknown_wmi",
+        "vin_parsed_total invalid_length",
+        "vin_checksum_verified_total invalid_chars",
+    ]);
+}
+
+#[cfg(feature = "color")]
+#[test]
+fn colored_explanation_table() {
+    let explanation = get_info("1M8GDM9AXKP042789").unwrap().explain();
+    let colored = explanation.render_colored_table();
+    assert!(colored.contains("\u{1b}[31mcheck digit"));
+    let plain = colored.replace("\u{1b}[0m", "").replace("\u{1b}[31m", "").replace("\u{1b}[32m", "")
+        .replace("\u{1b}[33m", "").replace("\u{1b}[35m", "").replace("\u{1b}[36m", "");
+    assert_eq!(plain, explanation.render_table());
+}
+
+#[cfg(feature = "kba")]
+#[test]
+fn kba_codes() {
+    let codes = get_info("WVWZZZ1JZXW000001").unwrap().kba_codes().unwrap();
+    assert_eq!(codes.hsn, "0603");
+    assert_eq!(codes.tsn, None);
+    assert_eq!(get_info("wba3a5c51cf256985").unwrap().kba_codes().unwrap().hsn, "0005");
+    assert!(get_info("1M8GDM9AXKP042788").unwrap().kba_codes().is_none());
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono_years() {
+    use chrono::NaiveDate;
+    use vin::YearWindow;
+
+    let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+    assert_eq!(YearWindow::as_of_date(date(2020, 12, 31)).range(), 1980..=2022);
+    assert_eq!(YearWindow::as_of_date(date(-5, 1, 1)).to, 2);
+
+    let result = get_info("1M8GDM9AXKP042788").unwrap();
+    assert_eq!(result.years_as_of_date(date(2017, 1, 1)), vec![1989, 2019]);
+    assert_eq!(result.model_year_dates_as_of(date(2017, 1, 1)), vec![date(1989, 1, 1), date(2019, 1, 1)]);
+}
+
+#[test]
+fn test_corpora() {
+    use vin::testdata;
+
+    let valid = testdata::valid();
+    assert_eq!(valid.len(), 17);
+    for (region, vin) in valid {
+        let result = get_info(vin).unwrap();
+        assert_eq!(result.region.as_deref(), Some(region));
+        assert!(result.manufacturer.is_some());
+        assert!(result.valid_checksum.is_valid());
+    }
+
+    for vin in testdata::INVALID_LENGTH {
+        assert!(matches!(check_validity(vin), Err(VINError::IncorrectLength)));
+    }
+    for vin in testdata::INVALID_CHARACTERS {
+        assert!(matches!(check_validity(vin), Err(VINError::InvalidCharacters(_))));
+    }
+    for vin in testdata::INVALID_CHECKSUM {
+        assert!(matches!(verify_checksum(vin), Err(VINError::ChecksumError(_))));
+    }
+}
+
+#[cfg(feature = "proptest")]
+mod properties {
+    use proptest::prelude::*;
+    use vin::testing::{arb_valid_vin, arb_vin};
+
+    proptest! {
+        #[test]
+        fn valid_vins_pass_checksum(vin in arb_valid_vin()) {
+            prop_assert!(vin::verify_checksum(&vin).is_ok());
+            prop_assert!(vin::get_info(&vin).is_ok());
+        }
+
+        #[test]
+        fn check_digit_is_stable(vin in arb_vin()) {
+            let check_digit = vin::compute_check_digit(&vin).unwrap();
+            prop_assert_eq!(vin::verify_checksum(&vin).is_ok(), vin.chars().nth(8) == Some(check_digit));
+        }
+    }
 }